@@ -1,8 +1,8 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use didyoumean::edit_distance;
+use didyoumean::{edit_distance, edit_distances};
 
 pub fn edit_distance_bench(c: &mut Criterion) {
-    let arr = vec!["abarthrosis", "abarticular", "abarticulation"];
+    let arr = ["abarthrosis", "abarticular", "abarticulation"];
     let search_chars = "abartclat".chars().collect::<Vec<_>>();
     c.bench_function("edit_distance", |b| {
         b.iter(|| {
@@ -13,5 +13,13 @@ pub fn edit_distance_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, edit_distance_bench);
+pub fn edit_distances_bench(c: &mut Criterion) {
+    let arr = ["abarthrosis", "abarticular", "abarticulation"].repeat(100);
+    let search_chars = "abartclat".chars().collect::<Vec<_>>();
+    c.bench_function("edit_distances", |b| {
+        b.iter(|| edit_distances(&search_chars, &arr))
+    });
+}
+
+criterion_group!(benches, edit_distance_bench, edit_distances_bench);
 criterion_main!(benches);
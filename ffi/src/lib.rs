@@ -0,0 +1,4 @@
+// The C library exports the functions of didyoumean::ffi, declared in
+// include/didyoumean.h. Kept in its own crate so that building didyoumean as a Rust
+// library, including without std, does not also link a C library.
+pub use didyoumean::ffi::*;
@@ -0,0 +1,114 @@
+/* The C API of didyoumean. Link with -ldidyoumean. */
+
+#ifndef DIDYOUMEAN_H
+#define DIDYOUMEAN_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// A [`SuggestionEngine`] created over the C API, freed with [`dym_engine_free`].
+typedef struct DymEngine DymEngine;
+
+// A suggestion returned over the C API.
+typedef struct DymSuggestion {
+  // The suggested word, as a NUL-terminated UTF-8 string.
+  char *word;
+  // The edit distance between the search term and the word.
+  size_t distance;
+  // The similarity of the search term and the word, from 0 to 1.
+  double score;
+} DymSuggestion;
+
+// The suggestions returned by [`dym_suggest`] and [`dym_engine_suggest`], closest first.
+// Owned by the caller, who frees them with [`dym_suggestions_free`].
+typedef struct DymSuggestions {
+  // The suggestions, or null if there are none.
+  struct DymSuggestion *items;
+  // The number of suggestions.
+  size_t len;
+} DymSuggestions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Return the version of the library, as a static NUL-terminated string.
+const char *dym_version(void);
+
+// Return the edit distance between `a` and `b`, or `SIZE_MAX` if either is null or not
+// UTF-8.
+//
+// # Safety
+//
+// `a` and `b` must each be null or point to a NUL-terminated string.
+size_t dym_edit_distance(const char *a, const char *b);
+
+// Return how similar `a` and `b` are, from 0 to 1, or -1 if either is null or not UTF-8.
+//
+// # Safety
+//
+// `a` and `b` must each be null or point to a NUL-terminated string.
+double dym_similarity(const char *a, const char *b);
+
+// Return the `n` of the `count` strings at `candidates` closest to `term`. Candidates that
+// are null or not UTF-8 are skipped, and such a `term` has no suggestions.
+//
+// # Safety
+//
+// `term` must be null or point to a NUL-terminated string, and `candidates` must be null
+// or point to `count` pointers that are each null or point to a NUL-terminated string.
+struct DymSuggestions dym_suggest(const char *term,
+                                  const char *const *candidates,
+                                  size_t count,
+                                  size_t n);
+
+// Free suggestions returned by [`dym_suggest`] or [`dym_engine_suggest`].
+//
+// # Safety
+//
+// `suggestions` must have been returned by this library and not freed before.
+void dym_suggestions_free(struct DymSuggestions suggestions);
+
+// Create an engine suggesting up to `limit` of the `count` words at `words`, or every word
+// close enough with a `limit` of zero. Words that are null or not UTF-8 are skipped.
+//
+// # Safety
+//
+// `words` must be null or point to `count` pointers that are each null or point to a
+// NUL-terminated string.
+struct DymEngine *dym_engine_new(const char *const *words, size_t count, size_t limit);
+
+// Create an engine suggesting up to `limit` of the words of the word list file at `path`,
+// in any format `dym` reads, or return null if it cannot be read.
+//
+// # Safety
+//
+// `path` must be null or point to a NUL-terminated string.
+struct DymEngine *dym_engine_from_file(const char *path, size_t limit);
+
+// Return the suggestions of `engine` for `term`. A `term` that is null or not UTF-8 has
+// no suggestions.
+//
+// # Safety
+//
+// `engine` must have been created by this library and not freed, and `term` must be null
+// or point to a NUL-terminated string.
+struct DymSuggestions dym_engine_suggest(const struct DymEngine *engine, const char *term);
+
+// Free an engine. Freeing null does nothing.
+//
+// # Safety
+//
+// `engine` must be null or have been created by this library and not freed before.
+void dym_engine_free(struct DymEngine *engine);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* DIDYOUMEAN_H */
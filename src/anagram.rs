@@ -0,0 +1,240 @@
+use crate::symspell::fingerprint;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Write},
+    path::Path,
+};
+
+/// The first word of a saved [`AnagramIndex`], followed by the format version.
+const MAGIC: &str = "dym-anagrams";
+
+/// The version of the file format written by [`AnagramIndex::write_to`].
+const VERSION: u32 = 1;
+
+/// Return the letters of `word`, lowercased and sorted, which all of its anagrams share.
+/// Anything other than a letter is left out, as word games ignore it.
+///
+/// # Arguments
+///
+/// * `word` - The word to take the letters of.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::anagram::letters;
+/// assert_eq!(letters("Listen"), "eilnst");
+/// assert_eq!(letters("don't"), "dnot");
+/// ```
+pub fn letters(word: &str) -> String {
+    let mut letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    letters.sort_unstable();
+    letters.into_iter().collect()
+}
+
+/// Return the number of letters that must be added or removed to turn the sorted letters
+/// `a` into the sorted letters `b`, as returned by [`letters`].
+///
+/// # Arguments
+///
+/// * `a` - The sorted letters of one word.
+/// * `b` - The sorted letters of the other.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::anagram::{difference, letters};
+/// assert_eq!(difference(&letters("listen"), &letters("silent")), 0);
+/// assert_eq!(difference(&letters("listen"), &letters("listens")), 1);
+/// assert_eq!(difference(&letters("cat"), &letters("cut")), 2);
+/// ```
+pub fn difference(a: &str, b: &str) -> usize {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    let mut count = 0;
+    loop {
+        match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if x == y => {
+                a.next();
+                b.next();
+            }
+            (Some(x), Some(y)) if x < y => {
+                a.next();
+                count += 1;
+            }
+            (Some(_), Some(_)) => {
+                b.next();
+                count += 1;
+            }
+            (Some(_), None) => {
+                a.next();
+                count += 1;
+            }
+            (None, Some(_)) => {
+                b.next();
+                count += 1;
+            }
+            (None, None) => return count,
+        }
+    }
+}
+
+/// The words of a word list grouped by their sorted [`letters`], for finding the anagrams
+/// of a term with a lookup and its near anagrams by comparing letters once per group.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::anagram::AnagramIndex;
+/// let words = ["silent", "cat", "enlist", "listens", "act", "tinsel"].map(String::from).to_vec();
+/// let index = AnagramIndex::build(&words);
+/// assert_eq!(index.find("listen", 0), vec![(0, 0), (2, 0), (5, 0)]);
+/// assert_eq!(index.find("listen", 1), vec![(0, 0), (2, 0), (5, 0), (3, 1)]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnagramIndex {
+    fingerprint: u64,
+    groups: Vec<(String, Vec<u32>)>,
+}
+
+impl AnagramIndex {
+    /// Build the index of `words`. Words without letters are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to index.
+    #[tracing::instrument(level = "debug", skip_all, fields(words = words.len()))]
+    pub fn build(words: &[String]) -> AnagramIndex {
+        let mut keyed: Vec<(String, u32)> = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (letters(word), i as u32))
+            .filter(|(key, _)| !key.is_empty())
+            .collect();
+        keyed.sort_unstable();
+
+        let mut groups: Vec<(String, Vec<u32>)> = vec![];
+        for (key, i) in keyed {
+            match groups.last_mut() {
+                Some((last, indices)) if *last == key => indices.push(i),
+                _ => groups.push((key, vec![i])),
+            }
+        }
+
+        AnagramIndex {
+            fingerprint: fingerprint(words),
+            groups,
+        }
+    }
+
+    /// Return the [`fingerprint`] of the words the index was built from.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Return the indices of the words using the letters of `term` with at most
+    /// `max_differences` letters added or removed, with their [`difference`], fewest
+    /// differences first and in the order of the word list at equal difference.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The letters to find words of.
+    /// * `max_differences` - The most letters a word may add or leave out.
+    pub fn find(&self, term: &str, max_differences: usize) -> Vec<(usize, usize)> {
+        let key = letters(term);
+        let mut found: Vec<(usize, usize)> = if max_differences == 0 {
+            self.groups
+                .binary_search_by(|(group, _)| group.as_str().cmp(&key))
+                .map(|i| self.groups[i].1.iter().map(|i| (*i as usize, 0)).collect())
+                .unwrap_or_default()
+        } else {
+            let len = key.chars().count();
+            self.groups
+                .iter()
+                .filter(|(group, _)| group.chars().count().abs_diff(len) <= max_differences)
+                .map(|(group, indices)| (difference(&key, group), indices))
+                .filter(|(diff, _)| *diff <= max_differences)
+                .flat_map(|(diff, indices)| indices.iter().map(move |i| (*i as usize, diff)))
+                .collect()
+        };
+
+        found.sort_unstable_by_key(|(i, diff)| (*diff, *i));
+        found
+    }
+
+    /// Write the index to `writer`, as a header line followed by a line per group of
+    /// anagrams holding their letters and the indices of their words.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the index.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writeln!(writer, "{} {} {:016x}", MAGIC, VERSION, self.fingerprint)?;
+        for (key, indices) in &self.groups {
+            let indices: Vec<String> = indices.iter().map(u32::to_string).collect();
+            writeln!(writer, "{}\t{}", key, indices.join(" "))?;
+        }
+        Ok(())
+    }
+
+    /// Read an index written by [`AnagramIndex::write_to`] from `reader`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Where to read the index from.
+    pub fn read_from<R: BufRead>(reader: R) -> Result<AnagramIndex, Error> {
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message.to_string());
+
+        let mut lines = reader.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid("empty anagram index"))??;
+        let fingerprint = match header.split(' ').collect::<Vec<&str>>()[..] {
+            [MAGIC, version, fingerprint] if version == VERSION.to_string() => {
+                u64::from_str_radix(fingerprint, 16).map_err(|_| invalid("bad fingerprint"))?
+            }
+            _ => return Err(invalid("not a dym anagram index")),
+        };
+
+        let mut groups = vec![];
+        for line in lines {
+            let line = line?;
+            let (key, indices) = line
+                .split_once('\t')
+                .ok_or_else(|| invalid("bad anagram group"))?;
+            let indices = indices
+                .split(' ')
+                .map(str::parse)
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|_| invalid("bad word index"))?;
+            groups.push((key.to_string(), indices));
+        }
+
+        Ok(AnagramIndex {
+            fingerprint,
+            groups,
+        })
+    }
+
+    /// Save the index to the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Load an index saved with [`AnagramIndex::save`] from the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read.
+    pub fn load(path: &Path) -> Result<AnagramIndex, Error> {
+        AnagramIndex::read_from(BufReader::new(File::open(path)?))
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{read_to_string, OpenOptions},
+    io::{Error, ErrorKind, Write},
+    path::Path,
+};
+
+/// A download recorded in the audit log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the download finished, in seconds since the Unix epoch.
+    pub time: u64,
+    /// The locale code of the word list downloaded.
+    pub lang: String,
+    /// The URL the word list was downloaded from.
+    pub url: String,
+    /// The size of the downloaded file in bytes.
+    pub bytes: u64,
+    /// The SHA-256 digest of the downloaded file, in hexadecimal.
+    pub sha256: String,
+}
+
+impl AuditEntry {
+    /// Return the time of the download as `YYYY-MM-DD HH:MM:SS UTC`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::audit::AuditEntry;
+    /// let entry = AuditEntry {
+    ///     time: 1660000000,
+    ///     lang: "en".to_string(),
+    ///     url: "https://example.com/en".to_string(),
+    ///     bytes: 0,
+    ///     sha256: String::new(),
+    /// };
+    /// assert_eq!(entry.time_utc(), "2022-08-08 23:06:40 UTC");
+    /// ```
+    pub fn time_utc(&self) -> String {
+        let (days, seconds) = (self.time / 86400, self.time % 86400);
+
+        // Convert days since the epoch to a civil date, after Howard Hinnant's
+        // days_from_civil inverse. Eras are 400 year cycles starting on March 1st.
+        let z = days as i64 + 719468;
+        let era = z.div_euclid(146097);
+        let day_of_era = z - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
+    }
+}
+
+/// Append `entry` to the audit log at `path`, one JSON object per line.
+///
+/// # Arguments
+///
+/// * `path` - The path of the audit log.
+/// * `entry` - The download to record.
+pub fn record(path: &Path, entry: &AuditEntry) -> Result<(), Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+/// Read the downloads recorded in the audit log at `path`, oldest first. A missing log
+/// has no entries.
+///
+/// # Arguments
+///
+/// * `path` - The path of the audit log.
+pub fn read(path: &Path) -> Result<Vec<AuditEntry>, Error> {
+    if !path.is_file() {
+        return Ok(vec![]);
+    }
+
+    read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|error| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{} is corrupt: {}", path.display(), error),
+                )
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,153 @@
+use crate::metric::Metric;
+
+/// A [BK-tree](https://en.wikipedia.org/wiki/BK-tree) of words, finding the words within
+/// a distance of a term without comparing the term to every word. Each child of a node is
+/// kept under its distance from the node, so the triangle inequality rules out whole
+/// subtrees. That only holds for true metrics, which is why the tree takes a [`Metric`].
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{bktree::BkTree, metric::Metric};
+/// let mut tree = BkTree::new(Metric::DamerauLevenshtein);
+/// for word in ["book", "books", "cake", "boo", "cape", "cart", "boon", "cook"] {
+///     tree.insert(word);
+/// }
+/// assert_eq!(tree.find_within("bo", 1), vec![("boo", 1)]);
+/// assert_eq!(tree.find_within("bok", 1), vec![("book", 1), ("boo", 1)]);
+/// assert_eq!(tree.len(), 8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BkTree {
+    metric: Metric,
+    nodes: Vec<Node>,
+}
+
+/// A word of a [`BkTree`] and the indices of its children by their distance from it.
+#[derive(Clone, Debug)]
+struct Node {
+    word: String,
+    chars: Vec<char>,
+    children: Vec<(usize, usize)>,
+}
+
+impl BkTree {
+    /// Create an empty tree measuring distances with `metric`.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - The distance between words.
+    pub fn new(metric: Metric) -> BkTree {
+        BkTree {
+            metric,
+            nodes: vec![],
+        }
+    }
+
+    /// Create a tree of `words` measuring distances with `metric`.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - The distance between words.
+    /// * `words` - The words to insert.
+    pub fn from_words<'a, I: IntoIterator<Item = &'a str>>(metric: Metric, words: I) -> BkTree {
+        let mut tree = BkTree::new(metric);
+        for word in words {
+            tree.insert(word);
+        }
+        tree
+    }
+
+    /// Return the words of the tree, in the order they were inserted.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(|node| node.word.as_str())
+    }
+
+    /// Return the number of words in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return whether the tree has no words.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Add `word` to the tree, unless it is already there.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to add.
+    pub fn insert(&mut self, word: &str) {
+        let chars: Vec<char> = word.chars().collect();
+        let new = Node {
+            word: word.to_string(),
+            chars,
+            children: vec![],
+        };
+        if self.nodes.is_empty() {
+            self.nodes.push(new);
+            return;
+        }
+
+        // Walk down the children at the word's distance until one is missing.
+        let mut index = 0;
+        loop {
+            let node = &self.nodes[index];
+            let dist = self.metric.chars_distance(&new.chars, &node.chars);
+            if dist == 0 {
+                return;
+            }
+            match node.children.iter().find(|(d, _)| *d == dist) {
+                Some((_, child)) => index = *child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(new);
+                    self.nodes[index].children.push((dist, child));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Return the words at most `k` from `term` with their distances, closest first. Ties
+    /// are in the order the words were inserted.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    /// * `k` - The largest distance to return words at.
+    pub fn find_within(&self, term: &str, k: usize) -> Vec<(&str, usize)> {
+        let term: Vec<char> = term.chars().collect();
+        let mut found = vec![];
+        let mut stack = vec![];
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let dist = self.metric.chars_distance(&term, &node.chars);
+            if dist <= k {
+                found.push((index, dist));
+            }
+
+            // By the triangle inequality, only children whose distance from this node is
+            // within k of the term's can hold words within k of the term.
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(d, _)| d.abs_diff(dist) <= k)
+                    .map(|(_, child)| *child),
+            );
+        }
+
+        // Nodes are stored in insertion order, so their indices break ties.
+        found.sort_unstable();
+        found.sort_by_key(|(_, dist)| *dist);
+        found
+            .into_iter()
+            .map(|(index, dist)| (self.nodes[index].word.as_str(), dist))
+            .collect()
+    }
+}
@@ -0,0 +1,401 @@
+use std::{collections::HashSet, fmt, ops::Range, str::FromStr};
+
+use crate::{
+    dictionary::Dictionary,
+    source::{top_n_merged, Source},
+    MatchOptions, Ranking,
+};
+
+/// Kinds of tokens in technical text that are not words, and are skipped when checking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Skip {
+    /// Web addresses, such as `https://example.com/page`.
+    Urls,
+    /// Email addresses, such as `someone@example.com`.
+    Emails,
+    /// Hexadecimal strings, such as `0xff` or commit hashes like `3d55ed0`.
+    Hex,
+    /// UUIDs, such as `123e4567-e89b-12d3-a456-426614174000`.
+    Uuids,
+    /// Tokens containing digits, such as `1080p` or `v2`.
+    Numbers,
+    /// Words in capitals, such as `HTTP`.
+    Acronyms,
+}
+
+impl Skip {
+    /// Every kind of token.
+    pub const ALL: [Skip; 6] = [
+        Skip::Urls,
+        Skip::Emails,
+        Skip::Hex,
+        Skip::Uuids,
+        Skip::Numbers,
+        Skip::Acronyms,
+    ];
+
+    /// Return whether `token`, a run of text between spaces without surrounding
+    /// punctuation, is of this kind.
+    fn matches(self, token: &str) -> bool {
+        let is_hex = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit());
+        match self {
+            Skip::Urls => token.contains("://") || token.starts_with("www."),
+            Skip::Emails => token
+                .split_once('@')
+                .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.')),
+            Skip::Hex => {
+                let digits = token
+                    .strip_prefix("0x")
+                    .or_else(|| token.strip_prefix("0X"));
+                match digits {
+                    Some(digits) => is_hex(digits),
+                    // Without a prefix, only long runs mixing digits and letters, since
+                    // words like "decade" are made of hexadecimal digits too.
+                    None => {
+                        token.len() >= 6
+                            && is_hex(token)
+                            && token.contains(|c: char| c.is_ascii_digit())
+                            && token.contains(|c: char| c.is_ascii_alphabetic())
+                    }
+                }
+            }
+            Skip::Uuids => {
+                let groups: Vec<&str> = token.split('-').collect();
+                groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12])
+                    && groups.iter().all(|group| is_hex(group))
+            }
+            Skip::Numbers => token.contains(|c: char| c.is_ascii_digit()),
+            Skip::Acronyms => {
+                token.chars().filter(|c| c.is_alphabetic()).count() >= 2
+                    && !token.contains(char::is_lowercase)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Skip {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Skip::Urls => "urls",
+            Skip::Emails => "emails",
+            Skip::Hex => "hex",
+            Skip::Uuids => "uuids",
+            Skip::Numbers => "numbers",
+            Skip::Acronyms => "acronyms",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Skip {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Skip, String> {
+        Skip::ALL
+            .into_iter()
+            .find(|skip| skip.to_string() == name)
+            .ok_or_else(|| format!("{} is not a kind of token to skip", name))
+    }
+}
+
+/// A word of a text that is not in the word lists, with suggested replacements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Finding {
+    /// The byte range of the word in the text.
+    pub span: Range<usize>,
+    /// The word as it appears in the text.
+    pub word: String,
+    /// The suggested replacements, closest first.
+    pub suggestions: Vec<String>,
+}
+
+/// Return the words of `text` with their byte offsets, in order. Words are runs of
+/// letters, and may contain apostrophes between letters, as in "don't".
+///
+/// # Arguments
+///
+/// * `text` - The text to split into words.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::check::words;
+/// assert_eq!(words("Don't panic, 42 times!"), vec![(0, "Don't"), (6, "panic"), (16, "times")]);
+/// assert_eq!(words("naïve 'quotes'"), vec![(0, "naïve"), (8, "quotes")]);
+/// ```
+pub fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = vec![];
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let next_is_letter = chars.peek().is_some_and(|(_, next)| next.is_alphabetic());
+        let in_word = c.is_alphabetic() || (c == '\'' && start.is_some() && next_is_letter);
+
+        match (in_word, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s, &text[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, &text[s..]));
+    }
+
+    words
+}
+
+/// Return the words of `text` with their byte offsets like [`words`], leaving out the
+/// words of any token of the kinds in `skip`.
+///
+/// # Arguments
+///
+/// * `text` - The text to split into words.
+/// * `skip` - The kinds of tokens to leave out.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::check::{words_skipping, Skip};
+/// let text = "See https://exmple.com or mail bob@exmple.com about NASA's 3rd fix, 9fceb02.";
+/// let words: Vec<&str> = words_skipping(text, &Skip::ALL).into_iter().map(|(_, word)| word).collect();
+/// assert_eq!(words, vec!["See", "or", "mail", "about", "fix"]);
+/// assert_eq!(words_skipping("NASA", &[]), vec![(0, "NASA")]);
+/// ```
+pub fn words_skipping<'a>(text: &'a str, skip: &[Skip]) -> Vec<(usize, &'a str)> {
+    // The byte ranges of the tokens to skip.
+    let mut skipped = vec![];
+    for token in text.split_whitespace() {
+        let token = token.trim_matches(|c: char| !c.is_alphanumeric());
+        // Possessives of acronyms, as in "NASA's", are acronyms too.
+        let stem = token.strip_suffix("'s").unwrap_or(token);
+        if skip.iter().any(|kind| kind.matches(stem)) {
+            // The token is a slice of `text`, so its offset is the distance between them.
+            let start = token.as_ptr() as usize - text.as_ptr() as usize;
+            skipped.push(start..start + token.len());
+        }
+    }
+
+    words(text)
+        .into_iter()
+        .filter(|(start, _)| !skipped.iter().any(|range| range.contains(start)))
+        .collect()
+}
+
+/// Which words of a text to check. The default checks every word.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// The kinds of tokens whose words are not checked.
+    pub skip: Vec<Skip>,
+    /// The fewest characters a word needs to be checked, so that short words such as
+    /// abbreviations and initials are left alone.
+    pub min_len: usize,
+}
+
+impl Filter {
+    /// Return the words of `text` to check with their byte offsets, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to split into words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::check::{Filter, Skip};
+    /// let filter = Filter { skip: vec![Skip::Urls], min_len: 3 };
+    /// assert_eq!(filter.words("Ask Al at www.exmple.com"), vec![(0, "Ask")]);
+    /// ```
+    pub fn words<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        let mut words = words_skipping(text, &self.skip);
+        if self.min_len > 0 {
+            words.retain(|(_, word)| word.chars().count() >= self.min_len);
+        }
+        words
+    }
+}
+
+/// Finds the words of texts that are in none of a set of word lists, and suggests
+/// replacements for them from those lists.
+pub struct Checker<'a> {
+    sources: &'a [Source],
+    known: HashSet<&'a str>,
+    filter: Filter,
+    number: usize,
+    ranking: Ranking,
+    options: MatchOptions,
+}
+
+impl<'a> Checker<'a> {
+    /// Create a checker accepting the words of `sources`, checking every word and
+    /// suggesting five replacements by edit distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - The word lists of known words.
+    pub fn new(sources: &'a [Source]) -> Checker<'a> {
+        Checker {
+            sources,
+            known: sources.iter().flat_map(|source| source.words()).collect(),
+            filter: Filter::default(),
+            number: 5,
+            ranking: Ranking::default(),
+            options: MatchOptions::default(),
+        }
+    }
+
+    /// Only check the words `filter` lets through.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Which words to check.
+    pub fn filter(mut self, filter: Filter) -> Checker<'a> {
+        self.filter = filter;
+        self
+    }
+
+    /// Suggest `number` replacements for each unknown word.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The number of suggestions.
+    pub fn number(mut self, number: usize) -> Checker<'a> {
+        self.number = number;
+        self
+    }
+
+    /// Rank the suggestions by `ranking`, ignoring the differences `options` ignores.
+    ///
+    /// # Arguments
+    ///
+    /// * `ranking` - How to rank the suggestions.
+    /// * `options` - Which differences between words to ignore.
+    pub fn ranking(mut self, ranking: Ranking, options: MatchOptions) -> Checker<'a> {
+        self.ranking = ranking;
+        self.options = options;
+        self
+    }
+
+    /// Return whether `word` is in the word lists. Words known in lower case are accepted
+    /// capitalized, as at the start of a sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to look up.
+    pub fn is_known(&self, word: &str) -> bool {
+        self.known.contains(word) || self.known.contains(word.to_lowercase().as_str())
+    }
+
+    /// Return the words of `text` that are not in the word lists, in order, with their
+    /// byte spans and suggested replacements.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::{check::Checker, source::{Source, SourceKind}};
+    /// let words = ["hello", "world", "word"].map(String::from).to_vec();
+    /// let sources = [Source::new(SourceKind::Language, "en", words)];
+    /// let findings = Checker::new(&sources).number(2).check_text("Hello, wrold!");
+    /// assert_eq!(findings.len(), 1);
+    /// assert_eq!((findings[0].span.clone(), findings[0].word.as_str()), (7..12, "wrold"));
+    /// assert_eq!(findings[0].suggestions, vec!["world", "word"]);
+    /// ```
+    pub fn check_text(&self, text: &str) -> Vec<Finding> {
+        self.filter
+            .words(text)
+            .into_iter()
+            .filter(|(_, word)| !self.is_known(word))
+            .map(|(start, word)| Finding {
+                span: start..start + word.len(),
+                word: word.to_string(),
+                suggestions: top_n_merged(
+                    word,
+                    self.sources,
+                    self.number,
+                    self.ranking,
+                    self.options,
+                )
+                .iter()
+                .map(|suggestion| suggestion.word.to_string())
+                .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Return the words of `text` that are in none of `sources`, with five suggested
+/// replacements each from `sources`. Use a [`Checker`] to choose which words are checked
+/// and how replacements are suggested.
+///
+/// # Arguments
+///
+/// * `text` - The text to check.
+/// * `sources` - The word lists of known words.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{check::check_text, source::{Source, SourceKind}};
+/// let words = ["the", "cat", "sat"].map(String::from).to_vec();
+/// let sources = [Source::new(SourceKind::Language, "en", words)];
+/// let findings = check_text("teh cat sat", &sources);
+/// assert_eq!((findings[0].span.clone(), findings[0].suggestions[0].as_str()), (0..3, "the"));
+/// ```
+pub fn check_text(text: &str, sources: &[Source]) -> Vec<Finding> {
+    Checker::new(sources).check_text(text)
+}
+
+/// Return `text` with every word `correct` returns a replacement for replaced, leaving
+/// everything else as it was. Replacements for capitalized words are capitalized too.
+///
+/// # Arguments
+///
+/// * `text` - The text to correct.
+/// * `filter` - Which words to correct.
+/// * `correct` - Returns the replacement for a word, or `None` to keep it.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::check::{correct, Filter, Skip};
+/// let fix = |word: &str| (word.to_lowercase() == "teh").then(|| "the".to_string());
+/// let filter = Filter::default();
+/// assert_eq!(correct("Teh cat sat on teh mat.\n", &filter, fix), "The cat sat on the mat.\n");
+/// let filter = Filter { skip: Skip::ALL.to_vec(), min_len: 0 };
+/// assert_eq!(correct("TEH cat", &filter, fix), "TEH cat");
+/// ```
+pub fn correct<F: FnMut(&str) -> Option<String>>(
+    text: &str,
+    filter: &Filter,
+    mut correct: F,
+) -> String {
+    let mut corrected = String::with_capacity(text.len());
+    let mut end = 0;
+
+    for (start, word) in filter.words(text) {
+        let replacement = match correct(word) {
+            Some(replacement) => replacement,
+            None => continue,
+        };
+        corrected.push_str(&text[end..start]);
+        if word.starts_with(char::is_uppercase) {
+            let mut chars = replacement.chars();
+            corrected.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+            corrected.push_str(chars.as_str());
+        } else {
+            corrected.push_str(&replacement);
+        }
+        end = start + word.len();
+    }
+    corrected.push_str(&text[end..]);
+
+    corrected
+}
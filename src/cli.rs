@@ -1,18 +1,35 @@
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 
 // Parse command line arguments to get the search term.
 #[derive(Parser)]
 #[clap(author = "Hisbaan Noorani", version = "1.1.3", about = "Did You Mean: A cli spelling corrector", long_about = None)]
+#[clap(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[clap(subcommand)]
+    pub command: Option<Commands>,
     pub search_term: Option<String>,
     #[clap(
         short = 'n',
         long = "number",
-        default_value_t = 5,
         help = "Change the number of matches printed",
-        long_help = "Change the number of words the program will print. The default value is five."
+        long_help = "Change the number of words the program will print. The default value is five, or the value of `number` in the configuration file. Use 0, here or as `number` in the configuration file, to print every word close enough to be what was meant, by the same measure as --auto-threshold."
     )]
-    pub number: usize,
+    pub number: Option<usize>,
+    #[clap(
+        long = "page",
+        value_name = "PAGE",
+        help = "Print only the given page of matches",
+        long_help = "Print only the given page of matches, counting from one, with --page-size matches to a page. Useful with -n 0, which can find many words."
+    )]
+    pub page: Option<usize>,
+    #[clap(
+        long = "page-size",
+        value_name = "SIZE",
+        default_value = "20",
+        help = "Change the number of matches on a page",
+        long_help = "Change the number of matches on each page printed by --page."
+    )]
+    pub page_size: usize,
     #[clap(
         short = 'c',
         long = "clean-output",
@@ -34,14 +51,290 @@ pub struct Cli {
         long_help = "Yank (copy) the selected word to the system clipboard. If no word is selected, the clipboard will not be altered."
     )]
     pub yank: bool,
+    #[clap(
+        short = 'm',
+        long = "multiple",
+        help = "Yank several words at once",
+        long_help = "Choose several words to yank (copy) to the system clipboard, toggling each with space. The chosen words are copied one per line. Implies --yank."
+    )]
+    pub multiple: bool,
+    #[clap(
+        long = "preview",
+        help = "Show the definition of the highlighted word when yanking",
+        long_help = "Show the definition of the word highlighted in the --yank menu under the suggestions. The definition is whatever `preview-command` in the configuration file prints with {} replaced by the word, by default `dict {}` which asks a dictd server, such as one serving WordNet."
+    )]
+    pub preview: bool,
+    #[clap(
+        long = "exec",
+        value_name = "COMMAND",
+        help = "Run COMMAND with the chosen word in place of {}",
+        long_help = "Choose a word from a menu like --yank does, then run COMMAND through the shell with every {} replaced by the chosen word, for example --exec \"tmux send-keys {}\". The word is not copied unless --yank is given too. With --multiple, COMMAND is run once per chosen word."
+    )]
+    pub exec: Option<String>,
+    #[clap(
+        long = "tmux-insert",
+        help = "Type the chosen word into the current tmux pane",
+        long_help = "Choose a word from a menu like --yank does, then type it into the current tmux pane with tmux send-keys, so that it lands at the cursor of the program running there. Bind dym to a tmux popup to correct the word you are typing without the clipboard. The word is not copied unless --yank is given too. With --multiple, the chosen words are separated by spaces."
+    )]
+    pub tmux_insert: bool,
+    #[clap(
+        long = "rofi",
+        conflicts_with = "preview",
+        help = "Choose the word with rofi or dmenu",
+        long_help = "Choose the word with a launcher such as rofi or dmenu instead of the terminal menu, so dym can be bound to a desktop hotkey. The suggestions are written to the launcher one per line and the line it prints back is taken as the choice, which is printed, or yanked, run or typed with --yank, --exec or --tmux-insert. The launcher is `launcher-command` in the configuration file, by default `rofi -dmenu -p dym`."
+    )]
+    pub rofi: bool,
+    #[clap(
+        long = "auto-threshold",
+        help = "Only suggest words close to the search term",
+        long_help = "Only suggest words at most a third of the search term's length in edits away, and at least one. Prints nothing and exits with an error if no word is that close, rather than suggesting unrelated words."
+    )]
+    pub auto_threshold: bool,
+    #[clap(
+        long = "metric",
+        value_enum,
+        default_value_t = Metric::EditDistance,
+        help = "Choose how words are ranked",
+        long_help = "Choose how words are ranked against the search term. edit-distance counts the edits between them, while jaro-winkler measures their similarity, favouring words that share a prefix with the search term, which often suits short names and identifiers. metaphone ranks by edit distance but puts words that do not sound like the search term by Double Metaphone three edits further away, so that \"nite\" finds \"knight\". ngram scores words by the character trigrams they share with the search term, which copes better with long words mangled in several places. lcs counts only the characters to delete and insert, so a wrong character costs two, which ranks truncated and expanded words higher. With jaro-winkler and ngram, distances are the dissimilarity in thousandths."
+    )]
+    pub metric: Metric,
+    #[clap(
+        long = "phonetic",
+        conflicts_with = "metric",
+        help = "Only suggest words that sound like the search term",
+        long_help = "Only suggest words that sound like the search term by Soundex, ranked by edit distance, so that \"fone\" finds \"phone\" even though other words are fewer edits away."
+    )]
+    pub phonetic: bool,
+    #[clap(
+        long = "keyboard",
+        conflicts_with_all = &["metric", "phonetic"],
+        help = "Make slips onto nearby keys cheaper",
+        long_help = "Rank by an edit distance where substituting a letter typed on a nearby key of a QWERTY keyboard costs less than a full edit, so \"cst\" is closer to \"cat\" than \"cpt\" is. Distances are in tenths of an edit."
+    )]
+    pub keyboard: bool,
+    #[clap(
+        short = 'i',
+        long = "ignore-case",
+        help = "Ignore differences in case",
+        long_help = "Ignore differences in case, so \"Paris\" matches \"paris\" at distance zero. Words are compared lowercased, with ß spelled out as ss, so \"STRASSE\" matches \"straße\" too."
+    )]
+    pub ignore_case: bool,
+    #[clap(
+        long = "ignore-accents",
+        help = "Ignore accents and other diacritics",
+        long_help = "Ignore accents and other diacritics, so \"resume\" matches \"résumé\" at distance zero. Letters are decomposed with Unicode normalization and their combining marks dropped before words are compared."
+    )]
+    pub ignore_accents: bool,
+    #[clap(
+        long = "insert-cost",
+        value_name = "COST",
+        conflicts_with_all = &["metric", "phonetic", "keyboard"],
+        help = "Set the cost of inserting a character",
+        long_help = "Set the cost of inserting a character into the search term when ranking by edit distance. Costs are relative to each other and default to one, so --insert-cost 10 makes it ten times as expensive as any other edit."
+    )]
+    pub insert_cost: Option<usize>,
+    #[clap(
+        long = "delete-cost",
+        value_name = "COST",
+        conflicts_with_all = &["metric", "phonetic", "keyboard"],
+        help = "Set the cost of deleting a character",
+        long_help = "Set the cost of deleting a character from the search term when ranking by edit distance. Costs are relative to each other and default to one, so --delete-cost 10 makes it ten times as expensive as any other edit."
+    )]
+    pub delete_cost: Option<usize>,
+    #[clap(
+        long = "substitute-cost",
+        value_name = "COST",
+        conflicts_with_all = &["metric", "phonetic", "keyboard"],
+        help = "Set the cost of replacing a character",
+        long_help = "Set the cost of replacing a character of the search term with another when ranking by edit distance. Costs are relative to each other and default to one, so --substitute-cost 10 makes it ten times as expensive as any other edit."
+    )]
+    pub substitute_cost: Option<usize>,
+    #[clap(
+        long = "transpose-cost",
+        value_name = "COST",
+        conflicts_with_all = &["metric", "phonetic", "keyboard"],
+        help = "Set the cost of swapping two characters",
+        long_help = "Set the cost of swapping two adjacent characters of the search term when ranking by edit distance. Costs are relative to each other and default to one, so --transpose-cost 10 makes it ten times as expensive as any other edit."
+    )]
+    pub transpose_cost: Option<usize>,
+    #[clap(
+        long = "json",
+        conflicts_with_all = &["yank", "multiple", "exec", "tmux-insert", "rofi"],
+        help = "Print the suggestions as JSON",
+        long_help = "Print the suggestions as a JSON array of objects holding the word, its edit distance from the search term and the name of the word list it came from."
+    )]
+    pub json: bool,
+    #[clap(
+        long = "histogram",
+        conflicts_with_all = &["yank", "multiple", "exec", "tmux-insert", "rofi", "nvim-rpc", "correct"],
+        help = "Print how many words are at each edit distance",
+        long_help = "Instead of listing suggestions, print how many words of the word lists are at each edit distance from the search term, up to the length of the search term, and how many are further away. Useful for exploring a word's neighbourhood and for choosing thresholds. With --json, print the counts as a JSON object."
+    )]
+    pub histogram: bool,
+    #[clap(
+        long = "suffix",
+        value_name = "SUFFIX",
+        conflicts_with_all = &["search-term", "nvim-rpc", "correct", "histogram", "anagrams", "split"],
+        help = "List the words ending with SUFFIX",
+        long_help = "Instead of searching for a word, list the words of the word lists ending with SUFFIX, shortest first."
+    )]
+    pub suffix: Option<String>,
+    #[clap(
+        long = "rhyme",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "anagrams", "split", "suffix"],
+        help = "Find words rhyming with the search term",
+        long_help = "Instead of words spelled like the search term, suggest words that roughly rhyme with it: words sharing its last group of vowels and the consonants after them, those sharing more of its ending first."
+    )]
+    pub rhyme: bool,
+    #[clap(
+        long = "record",
+        value_name = "FILE",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "split-input", "replay"],
+        help = "Save the search and its ranking to FILE",
+        long_help = "Save the search term, the ranking options, the way words were searched for, such as --suffix or --anagrams and the boost-words and max-length-difference settings, a fingerprint of each word list searched and the suggestions found to FILE as JSON, so the search can be reproduced later with --replay, for example to find out why one word was suggested over another."
+    )]
+    pub record: Option<std::path::PathBuf>,
+    #[clap(
+        long = "replay",
+        value_name = "FILE",
+        conflicts_with_all = &["search-term", "nvim-rpc", "correct", "histogram", "split-input", "anagrams", "suffix", "rhyme", "complete", "split", "plugin"],
+        help = "Repeat a search saved with --record and compare the rankings",
+        long_help = "Repeat the search saved in FILE by --record, with its search term, ranking options and way of searching, over the word lists chosen as usual, and compare the suggestions with the recorded ones. Word lists that changed since the recording are named. Exits with an error if the ranking differs."
+    )]
+    pub replay: Option<std::path::PathBuf>,
+    #[clap(
+        long = "synonyms",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "split-input", "translate", "yank", "multiple", "exec", "tmux-insert", "rofi"],
+        help = "List the synonyms of the closest suggestion",
+        long_help = "List the synonyms of the closest suggestion, the word most likely meant, instead of the suggestions. Synonyms come from the thesaurus of the language, a file named after its locale code with a .thesaurus extension, such as en.thesaurus, installed next to the word lists. Each of its lines holds a word followed by its synonyms, separated by commas or tabs, as in the Moby thesaurus."
+    )]
+    pub synonyms: bool,
+    #[clap(
+        long = "translate",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "split-input"],
+        help = "Show the translations of the suggestions from bilingual word lists",
+        long_help = "Show and copy the translation paired with each suggestion instead of the suggestion itself, for word lists that are bilingual glossaries with a term and its translation on each line, separated by a tab. The search term is matched against the terms, so a glossary becomes a fuzzy translation lookup. Suggestions without a translation are shown as they are."
+    )]
+    pub translate: bool,
+    #[clap(
+        long = "check-determinism",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "split-input"],
+        help = "Run the search twice and fail if the rankings differ",
+        long_help = "Run the search twice over the same word lists and exit with an error showing both rankings side by side if they differ. A debugging aid: the same search should always rank the same, which --record and --replay rely on."
+    )]
+    pub check_determinism: bool,
+    #[clap(
+        long = "plugin",
+        value_name = "COMMAND",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "split-input", "anagrams", "suffix", "rhyme", "complete"],
+        help = "Rerank the suggestions with an external program (experimental)",
+        long_help = "Run COMMAND through the shell to rerank and filter the suggestions, so ranking can be extended in any language. It is sent a line of JSON on standard input holding the search term and a few times more candidates than are shown, as {\"version\": 1, \"term\": \"helo\", \"candidates\": [{\"word\": \"hello\", \"distance\": 1, \"source\": \"en\"}]}, and must print a JSON array of the candidates to keep, best first, optionally with new distances, as [{\"word\": \"hello\", \"distance\": 0}]. The protocol may change."
+    )]
+    pub plugin: Option<String>,
+    #[clap(
+        long = "complete",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "anagrams", "suffix", "rhyme", "split-input"],
+        help = "Suggest completions when the search term looks abbreviated",
+        long_help = "When the search term is not a word of the word lists but starts several of them, it is more likely cut short than misspelled, so list the words it starts, shortest first, instead of the words spelled like it. A notice on standard error says when this happens."
+    )]
+    pub complete: bool,
+    #[clap(
+        long = "split",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram", "anagrams"],
+        help = "Also suggest splitting the search term into two words",
+        long_help = "Also suggest the ways of splitting the search term into two words of the word lists, such as \"hello world\" for \"helloworld\", ranked alongside the single words by their distance from the search term."
+    )]
+    pub split: bool,
+    #[clap(
+        long = "anagrams",
+        conflicts_with_all = &["nvim-rpc", "correct", "histogram"],
+        help = "Find words made of the same letters",
+        long_help = "Instead of words spelled like the search term, suggest words made of exactly the same letters, in any order, ignoring case and anything that is not a letter. Use --max-differences to also suggest words with a few letters more or fewer. The letters of each installed language are indexed in the data directory the first time they are searched."
+    )]
+    pub anagrams: bool,
+    #[clap(
+        long = "max-differences",
+        value_name = "N",
+        requires = "anagrams",
+        help = "Allow N letters more or fewer in --anagrams",
+        long_help = "Also suggest words with up to N letters added to or left out of the search term's with --anagrams, fewest differences first. A letter swapped for another counts as two differences."
+    )]
+    pub max_differences: Option<usize>,
+    #[clap(
+        short = 'q',
+        long = "quiet",
+        help = "Hide download messages",
+        long_help = "Hide the download messages and progress bars shown when a word list is fetched, so that only the results are printed."
+    )]
+    pub quiet: bool,
+    #[clap(
+        long = "progress",
+        value_enum,
+        default_value_t = Progress::Bar,
+        help = "Choose how progress is reported",
+        long_help = "Choose how progress is reported on standard error. bar shows download messages and progress bars, while json prints one JSON object per line for each download and search event, for wrappers that draw their own progress."
+    )]
+    pub progress: Progress,
     #[clap(
         short = 'l',
         long = "lang",
+        multiple_occurrences = true,
         help = "Select the desired language using the locale code (en, fr, sp, etc.)",
-        long_help = "Select the desired language using its locale code. For example, English would have the locale code en and French would have the locale code fr. See --print-langs for a list of locale codes and the corresponding languages.",
-        default_value = "en"
+        long_help = "Select the desired language using its locale code. For example, English would have the locale code en and French would have the locale code fr. See --print-langs for a list of locale codes and the corresponding languages. Repeat the option to search several languages at once. The default is en, or the value of `lang` in the configuration file."
+    )]
+    pub lang: Vec<String>,
+    #[clap(
+        long = "wordlist",
+        value_name = "PATH",
+        multiple_occurrences = true,
+        help = "Search the words in the file at PATH instead of a language",
+        long_help = "Search the words in the file at PATH instead of a downloaded language. Plain lists with one word per line, word<TAB>frequency lists and hunspell .dic files are recognised, as well as gzipped versions of each. Repeat the option to search several files at once."
+    )]
+    pub wordlist: Vec<std::path::PathBuf>,
+    #[clap(
+        long = "dictionary-cmd",
+        value_name = "COMMAND",
+        conflicts_with = "wordlist",
+        help = "Search the lines printed by COMMAND instead of a language",
+        long_help = "Run COMMAND through the shell and search the lines it prints instead of a downloaded language, for example --dictionary-cmd \"kubectl get pods -o name\"."
+    )]
+    pub dictionary_cmd: Option<String>,
+    #[clap(
+        long = "preset",
+        value_name = "NAME",
+        conflicts_with_all = &["wordlist", "dictionary-cmd"],
+        help = "Search the candidates of a named preset instead of a language",
+        long_help = "Search the lines printed by the command of a named preset instead of a downloaded language. Built-in presets are git-branches, git-tags, k8s-pods, k8s-deployments, k8s-services, docker-containers and docker-images. More can be added under presets in the configuration file."
+    )]
+    pub preset: Option<String>,
+    #[clap(
+        long = "git",
+        conflicts_with_all = &["wordlist", "dictionary-cmd", "preset"],
+        help = "Search the branches and tags of the current git repository",
+        long_help = "Search the names of the local branches and tags of the git repository containing the current directory instead of a downloaded language."
+    )]
+    pub git: bool,
+    #[clap(
+        long = "env",
+        conflicts_with_all = &["wordlist", "dictionary-cmd", "preset", "git"],
+        help = "Search the names of the environment variables",
+        long_help = "Search the names of the variables in the current environment instead of a downloaded language."
     )]
-    pub lang: String,
+    pub env: bool,
+    #[clap(
+        long = "show-values",
+        requires = "env",
+        help = "Print the values of the environment variables found",
+        long_help = "Print the value of each environment variable found by --env next to its name."
+    )]
+    pub show_values: bool,
+    #[clap(
+        long = "history",
+        conflicts_with_all = &["wordlist", "dictionary-cmd", "preset", "git", "env"],
+        help = "Search the commands in your shell history",
+        long_help = "Search the commands in your shell history instead of a downloaded language. The history file is $HISTFILE if it is set, and otherwise the default bash, zsh or fish history file for $SHELL."
+    )]
+    pub history: bool,
     #[clap(
         long = "print-langs",
         help = "Display a list of supported languages",
@@ -54,4 +347,343 @@ pub struct Cli {
         long_help = "Update all language files from the repository https://github.com/hisbaan/wordlists."
     )]
     pub update_langs: bool,
+    #[clap(
+        long = "dry-run",
+        requires = "update-langs",
+        help = "Show what --update-langs would do without doing it",
+        long_help = "Print the word lists --update-langs would delete and download, with their sizes, without changing any files."
+    )]
+    pub dry_run: bool,
+    #[clap(
+        long = "nvim-rpc",
+        conflicts_with_all = &["search-term", "yank", "multiple", "exec", "tmux-insert", "rofi", "json"],
+        help = "Answer Neovim MessagePack-RPC requests on standard input",
+        long_help = "Load the word lists once, then answer MessagePack-RPC requests on standard input until it closes, for editor plugins started with jobstart({'dym', '--nvim-rpc'}, {rpc = true}). suggest(term[, n]) returns the n closest words to term. check_buffer(lines) returns a {lnum, col, end_col, word, suggestions} map for each unknown word in the lines, with zero-based line numbers and byte columns, ready for vim.diagnostic."
+    )]
+    pub nvim_rpc: bool,
+    #[clap(
+        long = "correct",
+        conflicts_with_all = &["search-term", "yank", "multiple", "exec", "tmux-insert", "rofi", "json", "nvim-rpc"],
+        help = "Correct misspelled words in text piped through dym",
+        long_help = "Copy standard input to standard output line by line, replacing each unknown word that has exactly one word one edit away with that word, and leaving every other word untouched. Use it as a filter, as in `some-command | dym --correct`."
+    )]
+    pub correct: bool,
+    #[clap(
+        long = "split-input",
+        conflicts_with_all = &["yank", "multiple", "exec", "tmux-insert", "rofi", "nvim-rpc", "correct", "histogram", "suffix", "rhyme", "anagrams", "split", "page"],
+        help = "Search for each word of the input separately",
+        long_help = "Treat each whitespace-separated word of the search term as its own search, printing the suggestions for each under a line naming it. Without this, everything up to the end of the first line is searched for as one term, spaces included. With standard input, all of it is read rather than the first line, as in `echo \"helo wrold\" | dym --split-input`. With --json, print an array holding an object per word."
+    )]
+    pub split_input: bool,
+    #[clap(
+        long = "skip",
+        value_name = "KIND",
+        possible_values = &["urls", "emails", "hex", "uuids", "numbers", "acronyms"],
+        use_value_delimiter = true,
+        multiple_occurrences = true,
+        help = "Skip a kind of token when checking text",
+        long_help = "Leave a kind of token alone in --correct and --nvim-rpc, on top of those skipped by `skip` in the configuration file, which skips all of them by default. Repeat the option or separate kinds with commas to skip several."
+    )]
+    pub skip: Vec<String>,
+    #[clap(
+        long = "no-skip",
+        value_name = "KIND",
+        possible_values = &["urls", "emails", "hex", "uuids", "numbers", "acronyms"],
+        use_value_delimiter = true,
+        multiple_occurrences = true,
+        help = "Check a kind of token that is skipped by default",
+        long_help = "Check the words of a kind of token in --correct and --nvim-rpc even though `skip` in the configuration file skips it, such as acronyms in text where capitals are not used for them. Repeat the option or separate kinds with commas to check several."
+    )]
+    pub no_skip: Vec<String>,
+    #[clap(
+        long = "max-query-len",
+        value_name = "LENGTH",
+        help = "Refuse search terms longer than LENGTH characters",
+        long_help = "Refuse search terms longer than LENGTH characters, rather than spend a long time comparing them with every word. The default is 100, or the value of `max-query-len` in the configuration file. Use 0 for no limit."
+    )]
+    pub max_query_len: Option<usize>,
+    #[clap(
+        long = "min-check-len",
+        value_name = "LENGTH",
+        help = "Only check words of at least LENGTH letters",
+        long_help = "Leave words shorter than LENGTH letters alone in --correct and --nvim-rpc, since short words are more often abbreviations, initials or units than misspellings. The default is four, or the value of `min-check-len` in the configuration file. Use 0 to check every word."
+    )]
+    pub min_check_len: Option<usize>,
+}
+
+// Ways of reporting progress on standard error.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    Bar,
+    Json,
+}
+
+// Ways of ranking the words against the search term.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    EditDistance,
+    JaroWinkler,
+    Metaphone,
+    Ngram,
+    Lcs,
+}
+
+// Subcommands that do something other than search for a word.
+#[derive(Subcommand)]
+pub enum Commands {
+    #[clap(
+        about = "Inspect or modify the configuration file",
+        long_about = "Inspect or modify the configuration file. Values set here are used as defaults and can be overridden by the matching command line option."
+    )]
+    Config {
+        #[clap(subcommand)]
+        action: ConfigAction,
+    },
+    #[clap(
+        about = "Download word lists",
+        long_about = "Download the word lists of the given languages if they are not already installed."
+    )]
+    Fetch {
+        #[clap(
+            required = true,
+            help = "The locale codes of the word lists to download"
+        )]
+        langs: Vec<String>,
+        #[clap(
+            long = "dry-run",
+            help = "Show what would be downloaded without doing it",
+            long_help = "Print the word lists that would be downloaded, with their sizes, without changing any files."
+        )]
+        dry_run: bool,
+    },
+    #[clap(about = "Manage installed word lists")]
+    Langs {
+        #[clap(subcommand)]
+        action: LangsAction,
+    },
+    #[clap(
+        about = "Manage the search indexes of word lists",
+        long_about = "Manage the SymSpell indexes of word lists. Searches by edit distance use the index of a word list when there is one built from its current version, and scan the whole list otherwise."
+    )]
+    Index {
+        #[clap(subcommand)]
+        action: IndexAction,
+    },
+    #[clap(
+        name = "fetch-worker",
+        hide = true,
+        about = "Download a URL to standard output for a sandboxed parent process"
+    )]
+    FetchProcess {
+        url: String,
+        #[clap(long, default_value = "0")]
+        offset: u64,
+        #[clap(long)]
+        head: bool,
+    },
+    #[clap(
+        name = "gen-typos",
+        about = "Generate a corpus of typos for evaluation",
+        long_about = "Sample words from a word list, make realistic typing mistakes in them and print one typo<TAB>word pair per line, for use with dym evaluate."
+    )]
+    GenTypos {
+        #[clap(
+            short,
+            long,
+            help = "Select the word list to sample from",
+            long_help = "Select the word list to sample from. Defaults to the language set in the configuration file."
+        )]
+        lang: Option<String>,
+        #[clap(long, default_value = "1000", help = "The number of pairs to generate")]
+        count: usize,
+        #[clap(
+            long,
+            help = "Seed the random number generator",
+            long_help = "Seed the random number generator, so the same corpus can be generated again."
+        )]
+        seed: Option<u64>,
+    },
+    #[clap(
+        about = "Measure suggestion accuracy against a corpus",
+        long_about = "Suggest corrections for every typo in a corpus of typo<TAB>word pairs, such as one made by dym gen-typos, and report how often the intended word was suggested first and in the top five, the mean reciprocal rank of the intended word and the time taken per suggestion."
+    )]
+    Evaluate {
+        #[clap(help = "The corpus file to evaluate against")]
+        corpus: std::path::PathBuf,
+        #[clap(
+            short,
+            long,
+            help = "Select the word list to suggest from",
+            long_help = "Select the word list to suggest from. Defaults to the language set in the configuration file."
+        )]
+        lang: Option<String>,
+        #[clap(
+            long,
+            help = "Print the report as JSON",
+            long_help = "Print the report as a JSON object, with accuracy per edit distance bucket, mean reciprocal rank and latencies in microseconds."
+        )]
+        json: bool,
+    },
+    #[clap(
+        about = "Export the graph of words close to each other",
+        long_about = "Print one word<TAB>neighbour<TAB>distance line for every pair of words in a word list that are at most --max-distance edits apart, for research and visualization. Each pair is printed once. Use --sample to build the graph of a random subset of a large word list."
+    )]
+    Graph {
+        #[clap(
+            short,
+            long,
+            help = "Select the word list to build the graph of",
+            long_help = "Select the word list to build the graph of. Defaults to the language set in the configuration file."
+        )]
+        lang: Option<String>,
+        #[clap(
+            long = "max-distance",
+            default_value = "1",
+            help = "The largest edit distance between neighbours"
+        )]
+        max_distance: usize,
+        #[clap(
+            short,
+            long,
+            help = "Write the edges to a file",
+            long_help = "Write the edges to the given file instead of standard output."
+        )]
+        output: Option<std::path::PathBuf>,
+        #[clap(
+            long,
+            help = "Build the graph of a random sample of words",
+            long_help = "Build the graph of the given number of words picked at random from the word list, rather than of the whole list."
+        )]
+        sample: Option<usize>,
+        #[clap(
+            long,
+            help = "Seed the random number generator",
+            long_help = "Seed the random number generator, so the same sample can be drawn again."
+        )]
+        seed: Option<u64>,
+    },
+    #[clap(
+        about = "Print random words from a word list",
+        long_about = "Print words picked at random from an installed word list, one per line, for passphrases and word games. --len and --prefix only pick words of the given length or starting with the given letters. --daily picks the same words all day, for a word of the day."
+    )]
+    Random {
+        #[clap(
+            short,
+            long,
+            help = "Select the word list to pick from",
+            long_help = "Select the word list to pick from. Defaults to the language set in the configuration file."
+        )]
+        lang: Option<String>,
+        #[clap(long, help = "Only pick words with this many characters")]
+        len: Option<usize>,
+        #[clap(long, help = "Only pick words starting with this prefix")]
+        prefix: Option<String>,
+        #[clap(
+            short = 'c',
+            long,
+            default_value = "1",
+            help = "The number of words to print"
+        )]
+        count: usize,
+        #[clap(
+            long,
+            conflicts_with = "daily",
+            help = "Seed the random number generator",
+            long_help = "Seed the random number generator, so the same words can be picked again."
+        )]
+        seed: Option<u64>,
+        #[clap(
+            long,
+            help = "Pick the same words for the whole day",
+            long_help = "Seed the random number generator with the current UTC date, so the same words are picked until midnight."
+        )]
+        daily: bool,
+    },
+    #[clap(
+        about = "Print the words matching a crossword pattern",
+        long_about = "Print the words of a word list matching a crossword-style pattern, one per line, in the order of the word list. In the pattern, _ or ? stands for any one letter and * for any run of letters, possibly none. For example, c_t__n matches cotton and un*able matches unbelievable."
+    )]
+    Pattern {
+        #[clap(help = "The pattern to match, such as c_t__n")]
+        pattern: String,
+        #[clap(
+            short,
+            long,
+            help = "Select the word list to search",
+            long_help = "Select the word list to search. Defaults to the language set in the configuration file."
+        )]
+        lang: Option<String>,
+    },
+    #[clap(
+        about = "Run a language server reporting misspelled words",
+        long_about = "Run a Language Server Protocol server on standard input and output, so any editor with LSP support can show misspelled words in a document as diagnostics and replace them with a suggestion through code actions. Words in the personal and project word lists are not reported."
+    )]
+    Lsp {
+        #[clap(
+            short,
+            long,
+            help = "Select the word lists to check against",
+            long_help = "Select the word lists to check against by their locale codes. Repeat the option to accept words from several languages. Defaults to the language set in the configuration file."
+        )]
+        lang: Vec<String>,
+    },
+}
+
+// Actions of the langs subcommand.
+#[derive(Subcommand)]
+pub enum LangsAction {
+    #[clap(
+        about = "Restore the previous version of a word list",
+        long_about = "Replace an installed word list with the version it had before the last --update-langs. The number of versions kept is set by keep-versions in the configuration file."
+    )]
+    Rollback {
+        #[clap(help = "The locale code of the word list to roll back")]
+        lang: String,
+    },
+    #[clap(
+        about = "List the word lists downloaded so far",
+        long_about = "List every word list download recorded in the audit log, oldest first, with the time it finished, its size, the SHA-256 digest of the file and the URL it came from."
+    )]
+    Log,
+}
+
+// Actions of the index subcommand.
+#[derive(Subcommand)]
+pub enum IndexAction {
+    #[clap(
+        about = "Build the search index of a word list",
+        long_about = "Build the SymSpell index of a word list, downloading the word list first if needed, so searches for words at most --max-distance edits from the search term look up the handful of candidates instead of comparing the term to every word. Build it again after updating the word list."
+    )]
+    Build {
+        #[clap(help = "The locale code of the word list to index")]
+        lang: String,
+        #[clap(
+            long = "max-distance",
+            default_value = "2",
+            help = "The largest edit distance the index can find words at",
+            long_help = "The largest edit distance the index can find words at. Larger distances make the index much bigger."
+        )]
+        max_distance: usize,
+    },
+}
+
+// Actions of the config subcommand.
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    #[clap(about = "Print the value of a configuration key")]
+    Get {
+        #[clap(help = "The configuration key to print")]
+        key: String,
+    },
+    #[clap(about = "Set the value of a configuration key")]
+    Set {
+        #[clap(help = "The configuration key to set")]
+        key: String,
+        #[clap(help = "The new value of the key")]
+        value: String,
+    },
+    #[clap(about = "Print every configuration key and its value")]
+    List,
+    #[clap(about = "Print the path of the configuration file")]
+    Path,
 }
@@ -0,0 +1,71 @@
+use crate::error::DymError;
+use cli_clipboard::{ClipboardContext, ClipboardProvider};
+
+#[cfg(unix)]
+use nix::unistd::{fork, ForkResult};
+
+/// Return the system clipboard.
+fn clipboard() -> Result<ClipboardContext, DymError> {
+    ClipboardProvider::new().map_err(|error| DymError::Clipboard(error.to_string()))
+}
+
+/// Copy `string` to the system clipboard
+///
+/// # Arguments
+///
+/// * `string` - the string to be copied.
+pub fn yank(string: &str) -> Result<(), DymError> {
+    let platform = std::env::consts::OS;
+    if [
+        "linux",
+        "freebsd",
+        "netbsd",
+        "dragonfly",
+        "netbsd",
+        "openbsd",
+        "solaris",
+    ]
+    .contains(&platform)
+    {
+        // The platform is linux/*bsd and is likely using X11 or Wayland.
+        // There is a fix needed for clipboard use in cases like these.
+        // The clipboard is cleared on X11/Wayland after the process that set it exist.
+        // To combat this, we will fork and keep a process aroudn until the clipboard
+        // is cleared.
+        // Ideally, this wouldn't be an issue but it was a conscious design decision
+        // on X11/Wayland
+        #[cfg(unix)]
+        match unsafe { fork() } {
+            Ok(ForkResult::Child) => {
+                // The parent has moved on, so the child has nobody to report errors to.
+                let Ok(mut ctx) = clipboard() else {
+                    std::process::exit(1);
+                };
+                if ctx.set_contents(string.to_owned()).is_err() {
+                    std::process::exit(1);
+                }
+
+                // Keep the process running until the clipboard changes.
+                loop {
+                    let clipboard = ctx.get_contents().unwrap_or_default();
+                    std::thread::sleep(std::time::Duration::from_secs(1));
+                    if clipboard != string {
+                        std::process::exit(0);
+                    }
+                }
+            }
+            Err(error) => {
+                return Err(DymError::Clipboard(format!("fork failed: {}", error)));
+            }
+            _ => {}
+        }
+    } else {
+        // The platform is NOT running X11/Wayland and thus, we don't have to handle
+        // the clipboard clearing behaviour.
+        clipboard()?
+            .set_contents(string.to_owned())
+            .map_err(|error| DymError::Clipboard(error.to_string()))?;
+    }
+
+    Ok(())
+}
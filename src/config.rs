@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, read_to_string, write},
+    io::{Error, ErrorKind},
+    path::PathBuf,
+};
+
+use crate::presets::BUILTIN_PRESETS;
+use didyoumean::{check::Skip, langs::SUPPORTED_LANGS, manager::SYSTEM_DIRS, source::SourceKind};
+
+/// Persistent defaults read from `config.json` in the didyoumean config directory.
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub lang: String,
+    pub number: usize,
+    pub clean_output: bool,
+    pub verbose: bool,
+    pub yank: bool,
+    pub auto_threshold: bool,
+    pub ignore_case: bool,
+    pub ignore_accents: bool,
+    pub colour: bool,
+    pub prompt: String,
+    pub multiple_prompt: String,
+    pub clear_prompt: bool,
+    pub default_selection: usize,
+    pub vim_keys: bool,
+    pub preview_command: String,
+    pub launcher_command: String,
+    pub keep_versions: usize,
+    pub sandbox_downloads: bool,
+    pub system_dirs: Vec<PathBuf>,
+    pub presets: BTreeMap<String, String>,
+    pub source_priority: Vec<String>,
+    pub skip: Vec<String>,
+    pub min_check_len: usize,
+    pub max_query_len: usize,
+    pub boost_words: Vec<String>,
+    pub max_length_difference: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            lang: "en".to_string(),
+            number: 5,
+            clean_output: false,
+            verbose: false,
+            yank: false,
+            auto_threshold: false,
+            ignore_case: false,
+            ignore_accents: false,
+            colour: true,
+            prompt: "[↑↓ to move, 1-9 or ↵ to select, esc/q to cancel]".to_string(),
+            multiple_prompt: "[↑↓ to move, space to toggle, ↵ to confirm, esc/q to cancel]"
+                .to_string(),
+            clear_prompt: false,
+            default_selection: 1,
+            vim_keys: true,
+            preview_command: "dict {}".to_string(),
+            launcher_command: "rofi -dmenu -p dym".to_string(),
+            keep_versions: 1,
+            sandbox_downloads: false,
+            system_dirs: SYSTEM_DIRS.iter().map(PathBuf::from).collect(),
+            presets: BTreeMap::new(),
+            source_priority: SourceKind::ALL.map(|kind| kind.to_string()).to_vec(),
+            skip: Skip::ALL.map(|skip| skip.to_string()).to_vec(),
+            min_check_len: 4,
+            max_query_len: 100,
+            boost_words: vec![],
+            max_length_difference: None,
+        }
+    }
+}
+
+impl Config {
+    /// Return the path of the configuration file.
+    pub fn path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap()
+            .join("didyoumean")
+            .join("config.json")
+    }
+
+    /// Return whether the configuration file exists.
+    pub fn exists() -> bool {
+        Config::path().is_file()
+    }
+
+    /// Load the configuration file, falling back to the defaults if it does not exist.
+    pub fn load() -> Result<Config, Error> {
+        let path = Config::path();
+        if !Config::exists() {
+            return Ok(Config::default());
+        }
+
+        let config: Config = serde_json::from_str(&read_to_string(&path)?).map_err(|error| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "{} is not a valid configuration file: {}",
+                    path.display(),
+                    error
+                ),
+            )
+        })?;
+        config
+            .validate()
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        tracing::debug!(path = %path.display(), "loaded the configuration");
+
+        Ok(config)
+    }
+
+    /// Write the configuration to the configuration file, creating its directory if needed.
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Config::path();
+        create_dir_all(path.parent().unwrap())?;
+
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        write(path, contents)
+    }
+
+    /// Return the command of the preset called `name`, preferring presets from the
+    /// configuration file over the built-in ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the preset.
+    pub fn preset(&self, name: &str) -> Option<String> {
+        self.presets
+            .get(name)
+            .cloned()
+            .or_else(|| BUILTIN_PRESETS.get(name).map(|command| command.to_string()))
+    }
+
+    /// Return the names of all presets, built-in and configured, sorted.
+    pub fn preset_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = BUILTIN_PRESETS
+            .keys()
+            .map(|name| name.to_string())
+            .chain(self.presets.keys().cloned())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Return the kinds of word lists in the order they are preferred in at equal distance.
+    pub fn source_priority(&self) -> Vec<SourceKind> {
+        // Validated on load, so every name parses.
+        self.source_priority
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .collect()
+    }
+
+    /// Return the kinds of tokens skipped when checking text.
+    pub fn skip(&self) -> Vec<Skip> {
+        // Validated on load, so every name parses.
+        self.skip
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .collect()
+    }
+
+    /// Return every key of the configuration and its value, sorted by key.
+    pub fn entries(&self) -> Map<String, Value> {
+        match serde_json::to_value(self).unwrap() {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Return the value of `key` formatted for display.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The configuration key to look up.
+    pub fn get(&self, key: &str) -> Result<String, String> {
+        match self.entries().get(key) {
+            Some(Value::String(value)) => Ok(value.to_owned()),
+            Some(value) => Ok(value.to_string()),
+            None => Err(format!("{} is not a configuration key", key)),
+        }
+    }
+
+    /// Set `key` to `value`, parsing `value` as the type of the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The configuration key to set.
+    /// * `value` - The new value of the key, as typed on the command line.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let mut entries = self.entries();
+
+        // Parse the value according to the type of the current value.
+        let parsed = match entries.get(key) {
+            Some(Value::String(_)) => Value::String(value.to_string()),
+            Some(_) => serde_json::from_str(value)
+                .map_err(|_| format!("{} is not a valid value for {}", value, key))?,
+            None => return Err(format!("{} is not a configuration key", key)),
+        };
+        entries.insert(key.to_string(), parsed);
+
+        // Round-trip through the typed struct so mistyped values are rejected.
+        let config: Config = serde_json::from_value(Value::Object(entries))
+            .map_err(|_| format!("{} is not a valid value for {}", value, key))?;
+        config.validate()?;
+
+        *self = config;
+        Ok(())
+    }
+
+    /// Check the values that the type system alone cannot.
+    fn validate(&self) -> Result<(), String> {
+        if !SUPPORTED_LANGS.contains_key(self.lang.as_str()) {
+            return Err(format!("{} is not a supported language", self.lang));
+        }
+        if self.default_selection == 0 {
+            return Err("default-selection counts from one".to_string());
+        }
+        for name in &self.source_priority {
+            name.parse::<SourceKind>()?;
+        }
+        for name in &self.skip {
+            name.parse::<Skip>()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{
+    bktree::BkTree, pattern::LengthIndex, source::Source, top_n_multi, wordlist::WordList, Ranking,
+};
+use std::collections::{BTreeSet, HashSet};
+
+/// A set of known words to suggest from, whatever holds them: a word list file, a vector
+/// or an index. Only [`Dictionary::words`] is required; structures that can count or look
+/// up words faster than by going through them all override the other methods.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{dictionary::Dictionary, top_n};
+/// fn closest<'a>(term: &str, dictionary: &'a impl Dictionary) -> Option<&'a str> {
+///     if dictionary.contains(term) {
+///         return None;
+///     }
+///     top_n(term, dictionary.words(), 1).first().map(|(word, _)| *word)
+/// }
+///
+/// let words = vec!["hello".to_string(), "world".to_string()];
+/// assert_eq!(closest("helo", &words), Some("hello"));
+/// assert_eq!(closest("hello", &words), None);
+/// assert_eq!(words.len(), 2);
+/// ```
+pub trait Dictionary {
+    /// Return the words. Searches give ties to the earlier word.
+    fn words(&self) -> impl Iterator<Item = &str>;
+
+    /// Return the number of words.
+    fn len(&self) -> usize {
+        self.words().count()
+    }
+
+    /// Return whether there are no words.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return whether `word` is one of the words.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to look up.
+    fn contains(&self, word: &str) -> bool {
+        self.words().any(|known| known == word)
+    }
+
+    /// Return the `n` words closest to `term` under each of `rankings`, with their
+    /// distances, going through the words only once. Useful to compare how the rankings
+    /// suit the words. See [`top_n_multi`].
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    /// * `rankings` - How to measure the distance between `term` and each word.
+    /// * `n` - The number of words to return for each ranking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::{dictionary::Dictionary, Ranking};
+    /// let words = vec!["nine", "knight", "night"];
+    /// let ranked = words.suggest_multi("nite", &[Ranking::EditDistance, Ranking::Metaphone], 1);
+    /// assert_eq!(ranked, vec![vec![("nine", 1)], vec![("night", 3)]]);
+    /// ```
+    fn suggest_multi(&self, term: &str, rankings: &[Ranking], n: usize) -> Vec<Vec<(&str, usize)>> {
+        top_n_multi(term, self.words(), rankings, n)
+    }
+}
+
+impl Dictionary for [String] {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        <[String]>::len(self)
+    }
+}
+
+impl Dictionary for Vec<String> {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+impl Dictionary for [&str] {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.iter().copied()
+    }
+
+    fn len(&self) -> usize {
+        <[&str]>::len(self)
+    }
+}
+
+impl Dictionary for Vec<&str> {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.iter().copied()
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+impl Dictionary for HashSet<String> {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        // Sorted, since the order of a hash set changes from run to run and would change
+        // which of two equally close words is suggested.
+        let mut words: Vec<&str> = self.iter().map(String::as_str).collect();
+        words.sort_unstable();
+        words.into_iter()
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        HashSet::contains(self, word)
+    }
+}
+
+impl Dictionary for BTreeSet<String> {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        BTreeSet::contains(self, word)
+    }
+}
+
+impl Dictionary for Source {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+}
+
+impl Dictionary for WordList {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        WordList::words(self).iter().map(String::as_str)
+    }
+
+    fn len(&self) -> usize {
+        WordList::words(self).len()
+    }
+}
+
+impl Dictionary for BkTree {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        BkTree::words(self)
+    }
+
+    fn len(&self) -> usize {
+        BkTree::len(self)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        !self.find_within(word, 0).is_empty()
+    }
+}
+
+impl Dictionary for LengthIndex<'_> {
+    fn words(&self) -> impl Iterator<Item = &str> {
+        LengthIndex::words(self)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.with_len(word.chars().count())
+            .any(|known| known == word)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    similarity, similarity_at, top_n_by, within_by, MatchOptions, Ranking, Suggestion, Threshold,
+};
+
+/// A word list loaded once along with how to search it, for running many searches, such
+/// as from a long-running process, without loading or setting anything up again.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{engine::SuggestionEngine, Ranking};
+/// let engine = SuggestionEngine::builder()
+///     .dictionary(["hello", "help", "world", "yellow"])
+///     .ranking(Ranking::EditDistance)
+///     .limit(2)
+///     .threshold(1)
+///     .build();
+///
+/// let words = |term| -> Vec<&str> { engine.suggest(term).iter().map(|s| s.word).collect() };
+/// assert_eq!(words("helo"), vec!["hello", "help"]);
+/// assert_eq!(words("wrld"), vec!["world"]);
+/// assert!(words("xyz").is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SuggestionEngine {
+    words: Vec<String>,
+    ranking: Ranking,
+    options: MatchOptions,
+    limit: usize,
+    threshold: Option<Threshold>,
+}
+
+impl SuggestionEngine {
+    /// Start building an engine, which by default ranks by [`crate::edit_distance`] and
+    /// suggests the five closest words, however far away.
+    pub fn builder() -> SuggestionEngineBuilder {
+        SuggestionEngineBuilder::default()
+    }
+
+    /// Return the words of the dictionary.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Return the suggestions for `term`, closest first. Ties keep the order of the
+    /// dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    pub fn suggest(&self, term: &str) -> Vec<Suggestion<'_>> {
+        let candidates = self.words.iter().map(|word| (word.as_str(), word.as_str()));
+        let max_distance = self.threshold.map(|threshold| match threshold {
+            // The automatic threshold depends on how the ranking measures distance.
+            Threshold::Auto => self.ranking.auto_threshold(term),
+            Threshold::Fixed(max_distance) => max_distance,
+        });
+
+        let found = match (self.limit, max_distance) {
+            (0, max_distance) => {
+                let max_distance =
+                    max_distance.unwrap_or_else(|| self.ranking.auto_threshold(term));
+                within_by(term, candidates, max_distance, self.ranking, self.options)
+            }
+            (limit, max_distance) => {
+                let mut found = top_n_by(term, candidates, limit, self.ranking, self.options);
+                if let Some(max_distance) = max_distance {
+                    found.retain(|(_, distance)| *distance <= max_distance);
+                }
+                found
+            }
+        };
+
+        // Edit distances give the similarity directly; other rankings need it measured.
+        let plain =
+            self.ranking == Ranking::EditDistance && self.options == MatchOptions::default();
+        found
+            .into_iter()
+            .map(|(word, distance)| Suggestion {
+                word,
+                distance,
+                score: match plain {
+                    true => similarity_at(term, word, distance),
+                    false => {
+                        similarity(&self.options.normalize(term), &self.options.normalize(word))
+                    }
+                },
+            })
+            .collect()
+    }
+
+    /// Return the closest suggestion for `term`, or `None` if there is none.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    pub fn did_you_mean(&self, term: &str) -> Option<&str> {
+        self.suggest(term).first().map(|suggestion| suggestion.word)
+    }
+}
+
+/// Builds a [`SuggestionEngine`], made with [`SuggestionEngine::builder`].
+#[derive(Clone, Debug)]
+pub struct SuggestionEngineBuilder {
+    engine: SuggestionEngine,
+}
+
+impl Default for SuggestionEngineBuilder {
+    fn default() -> Self {
+        SuggestionEngineBuilder {
+            engine: SuggestionEngine {
+                words: vec![],
+                ranking: Ranking::EditDistance,
+                options: MatchOptions::default(),
+                limit: 5,
+                threshold: None,
+            },
+        }
+    }
+}
+
+impl SuggestionEngineBuilder {
+    /// Set the words to suggest from, replacing any set before.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words of the dictionary.
+    pub fn dictionary<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.engine.words = words.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set how the distance between the search term and each word is measured.
+    ///
+    /// # Arguments
+    ///
+    /// * `ranking` - The distance to rank by.
+    pub fn ranking(mut self, ranking: Ranking) -> Self {
+        self.engine.ranking = ranking;
+        self
+    }
+
+    /// Set the differences between the search term and each word to ignore.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The differences to ignore.
+    pub fn options(mut self, options: MatchOptions) -> Self {
+        self.engine.options = options;
+        self
+    }
+
+    /// Set the most suggestions to return, or zero for every word within the threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The most suggestions to return.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.engine.limit = limit;
+        self
+    }
+
+    /// Set the largest distance a suggestion may have. Without one, the closest words are
+    /// suggested however far away, unless the limit is zero, which uses
+    /// [`Threshold::Auto`].
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - The largest distance, or [`Threshold::Auto`] to scale it with the
+    ///   length of the search term.
+    pub fn threshold(mut self, threshold: impl Into<Threshold>) -> Self {
+        self.engine.threshold = Some(threshold.into());
+        self
+    }
+
+    /// Return the engine.
+    pub fn build(self) -> SuggestionEngine {
+        self.engine
+    }
+}
@@ -0,0 +1,73 @@
+use std::{error, fmt, io};
+
+/// Everything that can go wrong in didyoumean, each with its own exit code for the CLI.
+#[derive(Debug)]
+pub enum DymError {
+    /// The system clipboard could not be used.
+    Clipboard(String),
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// Downloading the file at `url` failed.
+    Network { url: String, source: io::Error },
+    /// There is no word list for the language, installed or to download.
+    MissingLanguage(String),
+}
+
+/// A result whose error is a [`DymError`].
+pub type Result<T> = std::result::Result<T, DymError>;
+
+impl DymError {
+    /// Return the exit code the CLI ends with on this error, following the BSD
+    /// `sysexits.h` conventions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::error::DymError;
+    /// assert_eq!(DymError::MissingLanguage("xx".to_string()).exit_code(), 66);
+    /// ```
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            // EX_OSERR: the clipboard is a service of the operating system.
+            DymError::Clipboard(_) => 71,
+            // EX_IOERR.
+            DymError::Io(_) => 74,
+            // EX_UNAVAILABLE: the server could not be reached or refused.
+            DymError::Network { .. } => 69,
+            // EX_NOINPUT: the word list to read does not exist.
+            DymError::MissingLanguage(_) => 66,
+        }
+    }
+}
+
+impl fmt::Display for DymError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DymError::Clipboard(message) => write!(f, "Could not use the clipboard: {}", message),
+            DymError::Io(error) => write!(f, "{}", error),
+            DymError::Network { url, source } => {
+                write!(f, "Could not download {}: {}", url, source)
+            }
+            DymError::MissingLanguage(lang) => write!(
+                f,
+                "There is no word list for {}, run `dym langs` to see the available ones",
+                lang
+            ),
+        }
+    }
+}
+
+impl error::Error for DymError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DymError::Io(error) | DymError::Network { source: error, .. } => Some(error),
+            DymError::Clipboard(_) | DymError::MissingLanguage(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for DymError {
+    fn from(error: io::Error) -> DymError {
+        DymError::Io(error)
+    }
+}
@@ -0,0 +1,174 @@
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::{edit_distance, top_n};
+
+/// The number of suggestions looked at for each typo.
+const DEPTH: usize = 5;
+
+/// How well the suggestions matched the pairs of a corpus whose typos are the same edit
+/// distance from their truth.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bucket {
+    /// The number of pairs evaluated.
+    pub cases: usize,
+    /// The number of pairs whose truth was the first suggestion.
+    pub top_1: usize,
+    /// The number of pairs whose truth was among the first five suggestions.
+    pub top_5: usize,
+}
+
+impl Bucket {
+    /// Return the fraction of pairs whose truth was the first suggestion.
+    pub fn top_1_accuracy(&self) -> f64 {
+        self.top_1 as f64 / self.cases.max(1) as f64
+    }
+
+    /// Return the fraction of pairs whose truth was among the first five suggestions.
+    pub fn top_5_accuracy(&self) -> f64 {
+        self.top_5 as f64 / self.cases.max(1) as f64
+    }
+}
+
+/// How well the suggestions matched a corpus of `(typo, truth)` pairs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Evaluation {
+    /// The totals over every pair.
+    pub overall: Bucket,
+    /// The totals per edit distance between typo and truth.
+    pub buckets: BTreeMap<usize, Bucket>,
+    /// The sum of the reciprocal ranks of the truths, counting truths outside the top
+    /// five as zero.
+    pub reciprocal_rank_sum: f64,
+    /// The time taken to suggest corrections for each pair, in corpus order.
+    pub latencies: Vec<Duration>,
+}
+
+impl Evaluation {
+    /// Return the mean reciprocal rank of the truths within the top five suggestions.
+    pub fn mean_reciprocal_rank(&self) -> f64 {
+        self.reciprocal_rank_sum / self.overall.cases.max(1) as f64
+    }
+
+    /// Return the `p`th percentile of the per-pair latencies, `p` going from 0 to 100.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The percentile to return.
+    pub fn latency_percentile(&self, p: usize) -> Duration {
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+
+        match sorted.len() {
+            0 => Duration::ZERO,
+            len => sorted[(len - 1) * p.min(100) / 100],
+        }
+    }
+
+    /// Return the mean per-pair latency.
+    pub fn mean_latency(&self) -> Duration {
+        let total: Duration = self.latencies.iter().sum();
+        total / self.latencies.len().max(1) as u32
+    }
+
+    /// Return a machine-readable report of the evaluation, with latencies in
+    /// microseconds.
+    pub fn report(&self) -> Value {
+        let bucket = |bucket: &Bucket| {
+            json!({
+                "cases": bucket.cases,
+                "top_1_accuracy": bucket.top_1_accuracy(),
+                "top_5_accuracy": bucket.top_5_accuracy(),
+            })
+        };
+        let micros = |duration: Duration| duration.as_micros() as u64;
+
+        json!({
+            "cases": self.overall.cases,
+            "top_1_accuracy": self.overall.top_1_accuracy(),
+            "top_5_accuracy": self.overall.top_5_accuracy(),
+            "mean_reciprocal_rank": self.mean_reciprocal_rank(),
+            "by_distance": self
+                .buckets
+                .iter()
+                .map(|(dist, b)| (dist.to_string(), bucket(b)))
+                .collect::<serde_json::Map<_, _>>(),
+            "latency_us": {
+                "mean": micros(self.mean_latency()),
+                "p50": micros(self.latency_percentile(50)),
+                "p95": micros(self.latency_percentile(95)),
+                "max": micros(self.latency_percentile(100)),
+            },
+        })
+    }
+}
+
+/// Parse a corpus of one `typo<TAB>truth` pair per line, skipping blank and malformed
+/// lines.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the corpus file.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::evaluate::parse_corpus;
+/// let corpus = parse_corpus("helo\thello\n\nwrold\tworld\n");
+/// assert_eq!(corpus, vec![("helo".to_string(), "hello".to_string()), ("wrold".to_string(), "world".to_string())]);
+/// ```
+pub fn parse_corpus(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(typo, truth)| (typo.to_string(), truth.trim_end().to_string()))
+        .collect()
+}
+
+/// Suggest corrections from `words` for the typo of every pair in `corpus`, and measure
+/// how well and how fast the truth was found.
+///
+/// # Arguments
+///
+/// * `corpus` - The `(typo, truth)` pairs to evaluate.
+/// * `words` - The dictionary to suggest corrections from.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::evaluate::evaluate;
+/// let words = ["hello", "help", "world"].map(String::from);
+/// let corpus = [("helo", "hello"), ("hepl", "help")].map(|(t, w)| (t.to_string(), w.to_string()));
+/// let evaluation = evaluate(&corpus, &words);
+/// assert_eq!((evaluation.overall.cases, evaluation.overall.top_1), (2, 2));
+/// assert_eq!(evaluation.buckets[&1].cases, 2);
+/// assert_eq!(evaluation.mean_reciprocal_rank(), 1.0);
+/// ```
+pub fn evaluate(corpus: &[(String, String)], words: &[String]) -> Evaluation {
+    let mut evaluation = Evaluation::default();
+
+    for (typo, truth) in corpus {
+        let start = Instant::now();
+        let suggestions = top_n(typo, words.iter().map(String::as_str), DEPTH);
+        evaluation.latencies.push(start.elapsed());
+
+        let rank = suggestions.iter().position(|(word, _)| word == truth);
+        let dist = edit_distance(&typo.chars().collect::<Vec<_>>(), truth);
+        for bucket in [
+            &mut evaluation.overall,
+            evaluation.buckets.entry(dist).or_default(),
+        ] {
+            bucket.cases += 1;
+            bucket.top_1 += usize::from(rank == Some(0));
+            bucket.top_5 += usize::from(rank.is_some());
+        }
+        if let Some(rank) = rank {
+            evaluation.reciprocal_rank_sum += 1.0 / (rank + 1) as f64;
+        }
+    }
+
+    evaluation
+}
@@ -0,0 +1,452 @@
+#[cfg(feature = "network")]
+use futures_util::StreamExt;
+#[cfg(feature = "network")]
+use reqwest::{header::RANGE, Client, StatusCode};
+use serde_json::{json, Value};
+#[cfg(feature = "network")]
+use std::sync::OnceLock;
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    io::{BufRead, BufReader, Error, ErrorKind, Read, Write},
+    path::PathBuf,
+    process::{Child, ChildStdout, Command, Stdio},
+};
+#[cfg(feature = "network")]
+use tokio::runtime::{Builder, Runtime};
+
+/// A download started by [`Fetcher::stream`].
+pub struct Download {
+    /// The number of bytes the body is made of, if the server said.
+    pub content_length: Option<u64>,
+    /// Whether the body starts at the requested offset rather than at the beginning.
+    pub resumed: bool,
+    /// The body, in the order it arrives.
+    pub chunks: Box<dyn Iterator<Item = Result<Vec<u8>, Error>>>,
+}
+
+/// Something that can download files by URL, so the network can be swapped out.
+pub trait Fetcher {
+    /// Return the size of the file at `url` without downloading it, or `None` if the
+    /// server does not say.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the file.
+    fn content_length(&self, url: &str) -> Result<Option<u64>, Error>;
+
+    /// Start downloading the file at `url`, skipping its first `offset` bytes if the
+    /// server supports it. Check [`Download::resumed`] to find out whether it did.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the file.
+    /// * `offset` - The number of bytes already downloaded.
+    fn stream(&self, url: &str, offset: u64) -> Result<Download, Error>;
+
+    /// Download the whole file at `url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the file.
+    fn get(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let mut bytes = vec![];
+        for chunk in self.stream(url, 0)?.chunks {
+            bytes.extend(chunk?);
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// A [`Fetcher`] downloading over HTTP(S) with reqwest.
+#[cfg(feature = "network")]
+#[derive(Default)]
+pub struct ReqwestFetcher {
+    // Created on first use, so that building a fetcher that is never used is free.
+    inner: OnceLock<(Client, Runtime)>,
+}
+
+#[cfg(feature = "network")]
+impl ReqwestFetcher {
+    /// Create a fetcher. Its HTTP client and runtime are set up on first use.
+    pub fn new() -> ReqwestFetcher {
+        ReqwestFetcher::default()
+    }
+
+    /// Return the HTTP client and the runtime driving it, creating them if needed.
+    fn inner(&self) -> Result<&(Client, Runtime), Error> {
+        if let Some(inner) = self.inner.get() {
+            return Ok(inner);
+        }
+
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        Ok(self.inner.get_or_init(|| (Client::new(), runtime)))
+    }
+}
+
+#[cfg(feature = "network")]
+impl Fetcher for ReqwestFetcher {
+    fn content_length(&self, url: &str) -> Result<Option<u64>, Error> {
+        tracing::debug!(url, "requesting the size");
+        let (client, runtime) = self.inner()?;
+        let response = runtime
+            .block_on(client.head(url).send())
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::other)?;
+
+        Ok(response.content_length())
+    }
+
+    fn stream(&self, url: &str, offset: u64) -> Result<Download, Error> {
+        tracing::debug!(url, offset, "requesting");
+        let (client, runtime) = self.inner()?;
+        let mut request = client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+
+        let response = runtime
+            .block_on(request.send())
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::other)?;
+        tracing::debug!(
+            url,
+            status = %response.status(),
+            content_length = ?response.content_length(),
+            "responded"
+        );
+
+        let content_length = response.content_length();
+        let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+        let handle = runtime.handle().clone();
+        let mut stream = response.bytes_stream();
+
+        Ok(Download {
+            content_length,
+            resumed,
+            chunks: Box::new(std::iter::from_fn(move || {
+                handle
+                    .block_on(stream.next())
+                    .map(|chunk| chunk.map(|bytes| bytes.to_vec()).map_err(Error::other))
+            })),
+        })
+    }
+}
+
+/// A [`Fetcher`] that refuses every download, used when didyoumean is built without the
+/// `network` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::fetcher::{Fetcher, OfflineFetcher};
+/// assert!(OfflineFetcher.get("https://example.com/en").is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OfflineFetcher;
+
+impl Fetcher for OfflineFetcher {
+    fn content_length(&self, url: &str) -> Result<Option<u64>, Error> {
+        self.stream(url, 0).map(|download| download.content_length)
+    }
+
+    fn stream(&self, url: &str, _offset: u64) -> Result<Download, Error> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            format!(
+                "Cannot download {}: didyoumean was built without the network feature",
+                url
+            ),
+        ))
+    }
+}
+
+/// A [`Fetcher`] serving files from memory, for testing download handling without the
+/// network.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::fetcher::{Fetcher, MemoryFetcher};
+/// let fetcher = MemoryFetcher::new().with_file("https://example.com/en", b"hello\nworld\n");
+/// assert_eq!(fetcher.content_length("https://example.com/en").unwrap(), Some(12));
+/// assert_eq!(fetcher.get("https://example.com/en").unwrap(), b"hello\nworld\n");
+/// assert!(fetcher.get("https://example.com/fr").is_err());
+/// ```
+#[derive(Default)]
+pub struct MemoryFetcher {
+    files: HashMap<String, (Vec<u8>, Option<u64>)>,
+    chunk_size: usize,
+    interrupt_after: Cell<Option<u64>>,
+}
+
+impl MemoryFetcher {
+    /// Create a fetcher with no files, serving bodies in chunks of 4 bytes.
+    pub fn new() -> MemoryFetcher {
+        MemoryFetcher {
+            chunk_size: 4,
+            ..Default::default()
+        }
+    }
+
+    /// Serve `contents` at `url`, with a correct content length.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to serve the file at.
+    /// * `contents` - The contents of the file.
+    pub fn with_file(self, url: &str, contents: &[u8]) -> MemoryFetcher {
+        let len = contents.len() as u64;
+        self.with_file_and_length(url, contents, Some(len))
+    }
+
+    /// Serve `contents` at `url`, announcing `content_length` as its size whether or not
+    /// it is correct.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to serve the file at.
+    /// * `contents` - The contents of the file.
+    /// * `content_length` - The size to announce.
+    pub fn with_file_and_length(
+        mut self,
+        url: &str,
+        contents: &[u8],
+        content_length: Option<u64>,
+    ) -> MemoryFetcher {
+        self.files
+            .insert(url.to_string(), (contents.to_vec(), content_length));
+        self
+    }
+
+    /// Fail the next download with an error once `bytes` bytes have been served, like a
+    /// dropped connection. Later downloads succeed.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The number of bytes to serve before failing.
+    pub fn interrupt_after(self, bytes: u64) -> MemoryFetcher {
+        self.interrupt_after.set(Some(bytes));
+        self
+    }
+
+    fn file(&self, url: &str) -> Result<&(Vec<u8>, Option<u64>), Error> {
+        self.files
+            .get(url)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("{} not found", url)))
+    }
+}
+
+impl Fetcher for MemoryFetcher {
+    fn content_length(&self, url: &str) -> Result<Option<u64>, Error> {
+        Ok(self.file(url)?.1)
+    }
+
+    fn stream(&self, url: &str, offset: u64) -> Result<Download, Error> {
+        let (contents, content_length) = self.file(url)?;
+        let start = (offset as usize).min(contents.len());
+        let interrupt = self.interrupt_after.take();
+        let end = match interrupt {
+            Some(bytes) => contents.len().min(start + bytes as usize),
+            None => contents.len(),
+        };
+
+        let mut body: Vec<Result<Vec<u8>, Error>> = contents[start..end]
+            .chunks(self.chunk_size)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect();
+        if interrupt.is_some() {
+            body.push(Err(Error::new(
+                ErrorKind::ConnectionReset,
+                "connection reset",
+            )));
+        }
+
+        Ok(Download {
+            content_length: content_length.map(|len| len.saturating_sub(start as u64)),
+            resumed: start > 0,
+            chunks: Box::new(body.into_iter()),
+        })
+    }
+}
+
+/// The environment variables passed on to the child process of a [`ProcessFetcher`], so
+/// proxies and certificate locations keep working.
+const PASSTHROUGH_VARS: [&str; 9] = [
+    "PATH",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "SSL_CERT_FILE",
+    "SSL_CERT_DIR",
+];
+
+/// A [`Fetcher`] running every request in a child process, which streams the response
+/// back over a pipe with [`serve`]. The child runs with a cleared environment, so it
+/// need not see the user's home directory or hold files open in it.
+pub struct ProcessFetcher {
+    program: PathBuf,
+    args: Vec<String>,
+}
+
+impl ProcessFetcher {
+    /// Create a fetcher running `program` with `args`, followed by the URL, then
+    /// `--offset <bytes>` or `--head`. The child must answer with [`serve`].
+    ///
+    /// # Arguments
+    ///
+    /// * `program` - The program to run.
+    /// * `args` - The arguments to pass before the request.
+    pub fn new(program: PathBuf, args: Vec<String>) -> ProcessFetcher {
+        ProcessFetcher { program, args }
+    }
+
+    /// Start the child process for a request to `url`, with the request arguments
+    /// `request`, and read the header line it answers with.
+    fn spawn(
+        &self,
+        url: &str,
+        request: &[String],
+    ) -> Result<(Child, BufReader<ChildStdout>, Value), Error> {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .arg(url)
+            .args(request)
+            .env_clear()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        for var in PASSTHROUGH_VARS {
+            if let Some(value) = std::env::var_os(var) {
+                command.env(var, value);
+            }
+        }
+        tracing::debug!(?command, "running");
+
+        let mut child = command.spawn()?;
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut header = String::new();
+        stdout.read_line(&mut header)?;
+
+        // An empty header means the child failed before answering.
+        if header.is_empty() {
+            return Err(child_error(&mut child));
+        }
+        let header = serde_json::from_str(&header).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Invalid header from the fetch process",
+            )
+        })?;
+
+        Ok((child, stdout, header))
+    }
+}
+
+impl Fetcher for ProcessFetcher {
+    fn content_length(&self, url: &str) -> Result<Option<u64>, Error> {
+        let (mut child, _, header) = self.spawn(url, &["--head".to_string()])?;
+        if !child.wait()?.success() {
+            return Err(child_error(&mut child));
+        }
+
+        Ok(header["content_length"].as_u64())
+    }
+
+    fn stream(&self, url: &str, offset: u64) -> Result<Download, Error> {
+        let (mut child, mut stdout, header) =
+            self.spawn(url, &["--offset".to_string(), offset.to_string()])?;
+        let mut finished = false;
+
+        Ok(Download {
+            content_length: header["content_length"].as_u64(),
+            resumed: header["resumed"].as_bool().unwrap_or(false),
+            chunks: Box::new(std::iter::from_fn(move || {
+                if finished {
+                    return None;
+                }
+
+                let mut buffer = vec![0; 8192];
+                match stdout.read(&mut buffer) {
+                    Ok(0) => {
+                        // The body ends when the pipe closes, successfully or not.
+                        finished = true;
+                        match child.wait() {
+                            Ok(status) if status.success() => None,
+                            Ok(_) => Some(Err(child_error(&mut child))),
+                            Err(error) => Some(Err(error)),
+                        }
+                    }
+                    Ok(len) => {
+                        buffer.truncate(len);
+                        Some(Ok(buffer))
+                    }
+                    Err(error) => {
+                        finished = true;
+                        Some(Err(error))
+                    }
+                }
+            })),
+        })
+    }
+}
+
+/// Return an error holding what the failed child process of a [`ProcessFetcher`] printed
+/// on its standard error.
+fn child_error(child: &mut Child) -> Error {
+    let _ = child.wait();
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+
+    Error::other(format!("The fetch process failed: {}", stderr.trim()))
+}
+
+/// Answer a request from a [`ProcessFetcher`] on `out`, downloading with `fetcher`: a line
+/// of JSON holding the content length and whether the download was resumed, followed by
+/// the body unless `head` is set.
+///
+/// # Arguments
+///
+/// * `fetcher` - The fetcher to download with.
+/// * `url` - The URL of the file.
+/// * `offset` - The number of bytes already downloaded.
+/// * `head` - Whether to only send the content length.
+/// * `out` - Where to write the answer, usually standard output.
+pub fn serve(
+    fetcher: &dyn Fetcher,
+    url: &str,
+    offset: u64,
+    head: bool,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    if head {
+        let content_length = fetcher.content_length(url)?;
+        writeln!(out, "{}", json!({ "content_length": content_length }))?;
+        return out.flush();
+    }
+
+    let download = fetcher.stream(url, offset)?;
+    writeln!(
+        out,
+        "{}",
+        json!({
+            "content_length": download.content_length,
+            "resumed": download.resumed,
+        })
+    )?;
+    for chunk in download.chunks {
+        out.write_all(&chunk?)?;
+    }
+
+    out.flush()
+}
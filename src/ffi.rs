@@ -0,0 +1,232 @@
+use crate::{edit_distance, engine::SuggestionEngine, similarity, suggest, wordlist, Suggestion};
+use std::{
+    ffi::{c_char, CStr, CString},
+    path::Path,
+    ptr, slice,
+};
+
+/// A suggestion returned over the C API.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DymSuggestion {
+    /// The suggested word, as a NUL-terminated UTF-8 string.
+    pub word: *mut c_char,
+    /// The edit distance between the search term and the word.
+    pub distance: usize,
+    /// The similarity of the search term and the word, from 0 to 1.
+    pub score: f64,
+}
+
+/// The suggestions returned by [`dym_suggest`] and [`dym_engine_suggest`], closest first.
+/// Owned by the caller, who frees them with [`dym_suggestions_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct DymSuggestions {
+    /// The suggestions, or null if there are none.
+    pub items: *mut DymSuggestion,
+    /// The number of suggestions.
+    pub len: usize,
+}
+
+/// A [`SuggestionEngine`] created over the C API, freed with [`dym_engine_free`].
+#[derive(Debug)]
+pub struct DymEngine(SuggestionEngine);
+
+/// Return the string at `s`, or `None` if it is null or not UTF-8.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string.
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Return the strings of the array of `count` strings at `strings`, leaving out those that
+/// are null or not UTF-8.
+///
+/// # Safety
+///
+/// `strings` must be null or point to `count` pointers that are each null or point to a
+/// NUL-terminated string.
+unsafe fn to_strs<'a>(strings: *const *const c_char, count: usize) -> Vec<&'a str> {
+    if strings.is_null() {
+        return vec![];
+    }
+    slice::from_raw_parts(strings, count)
+        .iter()
+        .filter_map(|s| to_str(*s))
+        .collect()
+}
+
+/// Hand `suggestions` over to C.
+fn to_c(suggestions: Vec<Suggestion>) -> DymSuggestions {
+    let items: Box<[DymSuggestion]> = suggestions
+        .into_iter()
+        // Words from C strings hold no NUL.
+        .filter_map(|suggestion| {
+            Some(DymSuggestion {
+                word: CString::new(suggestion.word).ok()?.into_raw(),
+                distance: suggestion.distance,
+                score: suggestion.score,
+            })
+        })
+        .collect();
+    let len = items.len();
+    if len == 0 {
+        return DymSuggestions {
+            items: ptr::null_mut(),
+            len,
+        };
+    }
+
+    DymSuggestions {
+        items: Box::into_raw(items).cast(),
+        len,
+    }
+}
+
+/// Return the version of the library, as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn dym_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Return the edit distance between `a` and `b`, or `SIZE_MAX` if either is null or not
+/// UTF-8.
+///
+/// # Safety
+///
+/// `a` and `b` must each be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dym_edit_distance(a: *const c_char, b: *const c_char) -> usize {
+    match (to_str(a), to_str(b)) {
+        (Some(a), Some(b)) => edit_distance(&a.chars().collect::<Vec<_>>(), b),
+        _ => usize::MAX,
+    }
+}
+
+/// Return how similar `a` and `b` are, from 0 to 1, or -1 if either is null or not UTF-8.
+///
+/// # Safety
+///
+/// `a` and `b` must each be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dym_similarity(a: *const c_char, b: *const c_char) -> f64 {
+    match (to_str(a), to_str(b)) {
+        (Some(a), Some(b)) => similarity(a, b),
+        _ => -1.0,
+    }
+}
+
+/// Return the `n` of the `count` strings at `candidates` closest to `term`. Candidates that
+/// are null or not UTF-8 are skipped, and such a `term` has no suggestions.
+///
+/// # Safety
+///
+/// `term` must be null or point to a NUL-terminated string, and `candidates` must be null
+/// or point to `count` pointers that are each null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dym_suggest(
+    term: *const c_char,
+    candidates: *const *const c_char,
+    count: usize,
+    n: usize,
+) -> DymSuggestions {
+    let suggestions = match to_str(term) {
+        Some(term) => suggest(term, to_strs(candidates, count), n),
+        None => vec![],
+    };
+    to_c(suggestions)
+}
+
+/// Free suggestions returned by [`dym_suggest`] or [`dym_engine_suggest`].
+///
+/// # Safety
+///
+/// `suggestions` must have been returned by this library and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn dym_suggestions_free(suggestions: DymSuggestions) {
+    if suggestions.items.is_null() {
+        return;
+    }
+    let items = Box::from_raw(ptr::slice_from_raw_parts_mut(
+        suggestions.items,
+        suggestions.len,
+    ));
+    for item in items.iter() {
+        drop(CString::from_raw(item.word));
+    }
+}
+
+/// Create an engine suggesting up to `limit` of the `count` words at `words`, or every word
+/// close enough with a `limit` of zero. Words that are null or not UTF-8 are skipped.
+///
+/// # Safety
+///
+/// `words` must be null or point to `count` pointers that are each null or point to a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dym_engine_new(
+    words: *const *const c_char,
+    count: usize,
+    limit: usize,
+) -> *mut DymEngine {
+    let engine = SuggestionEngine::builder()
+        .dictionary(to_strs(words, count))
+        .limit(limit)
+        .build();
+    Box::into_raw(Box::new(DymEngine(engine)))
+}
+
+/// Create an engine suggesting up to `limit` of the words of the word list file at `path`,
+/// in any format `dym` reads, or return null if it cannot be read.
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dym_engine_from_file(path: *const c_char, limit: usize) -> *mut DymEngine {
+    let words = match to_str(path).map(|path| wordlist::load(Path::new(path))) {
+        Some(Ok(words)) => words,
+        _ => return ptr::null_mut(),
+    };
+    let engine = SuggestionEngine::builder()
+        .dictionary(words)
+        .limit(limit)
+        .build();
+    Box::into_raw(Box::new(DymEngine(engine)))
+}
+
+/// Return the suggestions of `engine` for `term`. A `term` that is null or not UTF-8 has
+/// no suggestions.
+///
+/// # Safety
+///
+/// `engine` must have been created by this library and not freed, and `term` must be null
+/// or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dym_engine_suggest(
+    engine: *const DymEngine,
+    term: *const c_char,
+) -> DymSuggestions {
+    let suggestions = match (engine.as_ref(), to_str(term)) {
+        (Some(engine), Some(term)) => engine.0.suggest(term),
+        _ => vec![],
+    };
+    to_c(suggestions)
+}
+
+/// Free an engine. Freeing null does nothing.
+///
+/// # Safety
+///
+/// `engine` must be null or have been created by this library and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn dym_engine_free(engine: *mut DymEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
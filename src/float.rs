@@ -0,0 +1,60 @@
+/// Return `x` rounded to the nearest integer, halfway cases away from zero.
+///
+/// # Arguments
+///
+/// * `x` - The number to round.
+#[cfg(feature = "std")]
+pub fn round(x: f64) -> f64 {
+    x.round()
+}
+
+/// Return `x` rounded to the nearest integer, halfway cases away from zero. `core` has no
+/// `f64::round`, so without the standard library it is computed here.
+///
+/// # Arguments
+///
+/// * `x` - The number to round, small enough to fit an `i64`.
+#[cfg(not(feature = "std"))]
+pub fn round(x: f64) -> f64 {
+    let truncated = x as i64 as f64;
+    match x - truncated {
+        fraction if fraction >= 0.5 => truncated + 1.0,
+        fraction if fraction <= -0.5 => truncated - 1.0,
+        _ => truncated,
+    }
+}
+
+/// Return the length of the hypotenuse of a right triangle with legs `x` and `y`.
+///
+/// # Arguments
+///
+/// * `x` - The length of one leg.
+/// * `y` - The length of the other leg.
+#[cfg(feature = "std")]
+pub fn hypot(x: f64, y: f64) -> f64 {
+    x.hypot(y)
+}
+
+/// Return the length of the hypotenuse of a right triangle with legs `x` and `y`, by
+/// Newton's method.
+///
+/// # Arguments
+///
+/// * `x` - The length of one leg.
+/// * `y` - The length of the other leg.
+#[cfg(not(feature = "std"))]
+pub fn hypot(x: f64, y: f64) -> f64 {
+    let square = x * x + y * y;
+    if square == 0.0 {
+        return 0.0;
+    }
+    let mut root = if square > 1.0 { square } else { 1.0 };
+    loop {
+        let next = (root + square / root) / 2.0;
+        // From above the estimates shrink until rounding stops them.
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fs::{read_dir, read_to_string},
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Return the git directory of the repository containing `start`, looking in `start` and
+/// then each of its parents.
+///
+/// # Arguments
+///
+/// * `start` - The directory to start looking from.
+pub fn find_git_dir(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let dot_git = dir.join(".git");
+
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+
+        // Worktrees and submodules have a `.git` file pointing at the real git directory.
+        if dot_git.is_file() {
+            let contents = read_to_string(&dot_git).ok()?;
+            let target = contents.strip_prefix("gitdir:")?.trim();
+            return Some(dir.join(target));
+        }
+    }
+
+    None
+}
+
+/// Return the sorted names of the local branches and tags of the git directory `git_dir`,
+/// without their `refs/heads/` or `refs/tags/` prefixes.
+///
+/// # Arguments
+///
+/// * `git_dir` - The git directory of the repository.
+pub fn ref_names(git_dir: &Path) -> Result<Vec<String>, Error> {
+    // Linked worktrees keep their refs in the main git directory.
+    let common_dir = match read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    };
+
+    let mut names = vec![];
+    for kind in ["heads", "tags"] {
+        let refs = common_dir.join("refs").join(kind);
+        if refs.is_dir() {
+            collect_loose_refs(&refs, "", &mut names)?;
+        }
+    }
+
+    // Refs that have been packed are listed one per line after their object id.
+    if let Ok(packed) = read_to_string(common_dir.join("packed-refs")) {
+        for line in packed.lines() {
+            let name = match line.split_once(' ') {
+                Some((_, name)) => name,
+                None => continue,
+            };
+            if let Some(name) = name
+                .strip_prefix("refs/heads/")
+                .or_else(|| name.strip_prefix("refs/tags/"))
+            {
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    if names.is_empty() && !common_dir.join("refs").is_dir() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("{} is not a git directory", git_dir.display()),
+        ));
+    }
+
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Add the names of the loose refs under `dir` to `names`, prefixed by `prefix`.
+fn collect_loose_refs(dir: &Path, prefix: &str, names: &mut Vec<String>) -> Result<(), Error> {
+    for entry in read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+
+        if entry.file_type()?.is_dir() {
+            collect_loose_refs(&entry.path(), &format!("{}/", name), names)?;
+        } else {
+            names.push(name);
+        }
+    }
+
+    Ok(())
+}
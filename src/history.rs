@@ -0,0 +1,152 @@
+use std::{
+    collections::HashSet,
+    env,
+    fs::read,
+    io::Error,
+    path::{Path, PathBuf},
+};
+
+/// The shell history file formats that can be read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryFormat {
+    /// One command per line, optionally preceded by `#<timestamp>` lines.
+    Bash,
+    /// One command per line, optionally as `: <timestamp>:<duration>;<command>`.
+    Zsh,
+    /// `- cmd: <command>` entries followed by indented metadata.
+    Fish,
+}
+
+/// Return the path of the current user's shell history file, using `$HISTFILE` if it is
+/// set and otherwise guessing from `$SHELL`.
+pub fn history_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("HISTFILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    let shell = env::var("SHELL").unwrap_or_default();
+    if shell.ends_with("fish") {
+        dirs::data_dir().map(|dir| dir.join("fish").join("fish_history"))
+    } else if shell.ends_with("zsh") {
+        dirs::home_dir().map(|dir| dir.join(".zsh_history"))
+    } else {
+        dirs::home_dir().map(|dir| dir.join(".bash_history"))
+    }
+}
+
+/// Read the shell history at `path` and return its distinct commands, most recent first.
+///
+/// # Arguments
+///
+/// * `path` - The path of the history file.
+pub fn load(path: &Path) -> Result<Vec<String>, Error> {
+    let contents = String::from_utf8_lossy(&read(path)?).to_string();
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    let format = if name.contains("fish") {
+        HistoryFormat::Fish
+    } else if name.contains("zsh") || contents.starts_with(": ") {
+        HistoryFormat::Zsh
+    } else {
+        HistoryFormat::Bash
+    };
+
+    Ok(parse(&contents, format))
+}
+
+/// Extract the distinct commands from the history `contents`, most recent first.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the history file.
+/// * `format` - The format the history is stored in.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::history::{parse, HistoryFormat};
+/// let zsh = ": 1660000000:0;git status\n: 1660000001:0;ls\n: 1660000002:0;git status\n";
+/// assert_eq!(parse(zsh, HistoryFormat::Zsh), vec!["git status", "ls"]);
+///
+/// let fish = "- cmd: cargo build\n  when: 1660000000\n- cmd: echo a\\\\b\n  when: 1660000001\n";
+/// assert_eq!(parse(fish, HistoryFormat::Fish), vec!["echo a\\b", "cargo build"]);
+///
+/// let bash = "#1660000000\nmake\nmake test\n";
+/// assert_eq!(parse(bash, HistoryFormat::Bash), vec!["make test", "make"]);
+/// ```
+pub fn parse(contents: &str, format: HistoryFormat) -> Vec<String> {
+    let mut commands: Vec<String> = vec![];
+
+    match format {
+        HistoryFormat::Bash => {
+            for line in contents.lines() {
+                // Skip the timestamps written with HISTTIMEFORMAT set.
+                let is_timestamp = line.len() > 1
+                    && line.starts_with('#')
+                    && line[1..].chars().all(|c| c.is_ascii_digit());
+                if !is_timestamp {
+                    commands.push(line.to_string());
+                }
+            }
+        }
+        HistoryFormat::Zsh => {
+            let mut continued = false;
+            for line in contents.lines() {
+                // Strip the extended history metadata.
+                let command = match line.strip_prefix(": ").and_then(|l| l.split_once(';')) {
+                    Some((_, command)) if !continued => command,
+                    _ => line,
+                };
+
+                // Multi-line commands end each line but the last with a backslash.
+                if continued {
+                    let last = commands.last_mut().unwrap();
+                    last.push('\n');
+                    last.push_str(command.strip_suffix('\\').unwrap_or(command));
+                } else {
+                    commands.push(command.strip_suffix('\\').unwrap_or(command).to_string());
+                }
+                continued = command.ends_with('\\');
+            }
+        }
+        HistoryFormat::Fish => {
+            for line in contents.lines() {
+                if let Some(command) = line.strip_prefix("- cmd: ") {
+                    commands.push(unescape_fish(command));
+                }
+            }
+        }
+    }
+
+    // Keep the most recent occurrence of each command.
+    let mut seen = HashSet::new();
+    commands
+        .into_iter()
+        .rev()
+        .filter(|command| !command.trim().is_empty() && seen.insert(command.clone()))
+        .collect()
+}
+
+/// Undo the escaping fish applies to newlines and backslashes in its history file.
+fn unescape_fish(command: &str) -> String {
+    let mut unescaped = String::with_capacity(command.len());
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
@@ -0,0 +1,497 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Error, ErrorKind, Read, Write},
+    path::Path,
+};
+
+/// The bytes a saved [`Trie`] starts with.
+const MAGIC: &[u8; 4] = b"DYMT";
+
+/// The version of the file format written by [`Trie::write_to`].
+const VERSION: u32 = 1;
+
+/// A prefix tree of words, searched for the words within an edit distance of a term by
+/// walking it in step with a [`LevenshteinAutomaton`]. Branches the automaton cannot
+/// accept from are never entered, so only a small part of a large dictionary is visited.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::index::Trie;
+/// let trie = Trie::from_words(["cat", "cart", "cut", "dog", "cats"]);
+/// assert_eq!(trie.find_within("cat", 1), vec![("cat".to_string(), 0), ("cart".to_string(), 1), ("cats".to_string(), 1), ("cut".to_string(), 1)]);
+/// assert!(trie.contains("dog"));
+/// assert!(!trie.contains("do"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trie {
+    nodes: Vec<Node>,
+    len: usize,
+}
+
+/// A node of a [`Trie`]: whether a word ends there, and its children sorted by character.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Node {
+    terminal: bool,
+    children: Vec<(char, u32)>,
+}
+
+impl Trie {
+    /// Create an empty trie.
+    pub fn new() -> Trie {
+        Trie {
+            nodes: vec![Node::default()],
+            len: 0,
+        }
+    }
+
+    /// Create a trie of `words`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to insert.
+    pub fn from_words<I, S>(words: I) -> Trie
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut trie = Trie::new();
+        for word in words {
+            trie.insert(word.as_ref());
+        }
+        trie
+    }
+
+    /// Return the number of words in the trie.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether the trie has no words.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add `word` to the trie, unless it is already there.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to add.
+    pub fn insert(&mut self, word: &str) {
+        if self.nodes.is_empty() {
+            self.nodes.push(Node::default());
+        }
+
+        let mut index = 0;
+        for c in word.chars() {
+            let children = &self.nodes[index].children;
+            index = match children.binary_search_by_key(&c, |(child, _)| *child) {
+                Ok(i) => children[i].1 as usize,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[index].children.insert(i, (c, child as u32));
+                    child
+                }
+            };
+        }
+
+        if !self.nodes[index].terminal {
+            self.nodes[index].terminal = true;
+            self.len += 1;
+        }
+    }
+
+    /// Return whether `word` is in the trie.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to look up.
+    pub fn contains(&self, word: &str) -> bool {
+        let mut index = 0;
+        for c in word.chars() {
+            match self.child(index, c) {
+                Some(child) => index = child,
+                None => return false,
+            }
+        }
+        self.nodes.get(index).is_some_and(|node| node.terminal)
+    }
+
+    /// Return the words at most `k` edits from `term` with their distances, closest first
+    /// and in alphabetical order at equal distance. Distances are those of
+    /// [`crate::edit_distance`], counted by character.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    /// * `k` - The largest distance to return words at.
+    pub fn find_within(&self, term: &str, k: usize) -> Vec<(String, usize)> {
+        let automaton = LevenshteinAutomaton::new(term, k);
+        let mut found = vec![];
+        if !self.nodes.is_empty() {
+            self.walk(
+                0,
+                &mut String::new(),
+                &automaton,
+                automaton.start(),
+                &mut found,
+            );
+        }
+
+        found.sort_by(|(a, a_dist), (b, b_dist)| a_dist.cmp(b_dist).then_with(|| a.cmp(b)));
+        found
+    }
+
+    /// Collect the words below the node at `index`, spelled `word` and reached in `state`,
+    /// that `automaton` accepts.
+    fn walk(
+        &self,
+        index: usize,
+        word: &mut String,
+        automaton: &LevenshteinAutomaton,
+        state: State,
+        found: &mut Vec<(String, usize)>,
+    ) {
+        let node = &self.nodes[index];
+        if node.terminal {
+            if let Some(dist) = automaton.distance(&state) {
+                found.push((word.clone(), dist));
+            }
+        }
+
+        for (c, child) in &node.children {
+            let next = automaton.step(&state, *c);
+            if automaton.can_match(&next) {
+                word.push(*c);
+                self.walk(*child as usize, word, automaton, next, found);
+                word.pop();
+            }
+        }
+    }
+
+    /// Return the words starting with `prefix`, in alphabetical order.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The start of the words.
+    pub fn with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut index = 0;
+        for c in prefix.chars() {
+            match self.child(index, c) {
+                Some(child) => index = child,
+                None => return vec![],
+            }
+        }
+
+        let mut found = vec![];
+        let mut stack = vec![(index, prefix.to_string())];
+        while let Some((index, word)) = stack.pop() {
+            let node = &self.nodes[index];
+            if node.terminal {
+                found.push(word.clone());
+            }
+            // Push the children backwards so they are visited in order.
+            for (c, child) in node.children.iter().rev() {
+                let mut word = word.clone();
+                word.push(*c);
+                stack.push((*child as usize, word));
+            }
+        }
+        found
+    }
+
+    /// Return the index of the child of the node at `index` reached by `c`.
+    fn child(&self, index: usize, c: char) -> Option<usize> {
+        let children = &self.nodes.get(index)?.children;
+        children
+            .binary_search_by_key(&c, |(child, _)| *child)
+            .ok()
+            .map(|i| children[i].1 as usize)
+    }
+
+    /// Write the trie to `writer` in a compact binary format read by
+    /// [`Trie::read_from`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the trie.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(self.len as u64).to_le_bytes())?;
+        writer.write_all(&(self.nodes.len() as u32).to_le_bytes())?;
+        for node in &self.nodes {
+            writer.write_all(&[node.terminal as u8])?;
+            writer.write_all(&(node.children.len() as u32).to_le_bytes())?;
+            for (c, child) in &node.children {
+                writer.write_all(&(*c as u32).to_le_bytes())?;
+                writer.write_all(&child.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a trie written by [`Trie::write_to`] from `reader`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Where to read the trie from.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Trie, Error> {
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message.to_string());
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not a dym index"));
+        }
+        if read_u32(reader)? != VERSION {
+            return Err(invalid("unsupported dym index version"));
+        }
+
+        let mut len = [0; 8];
+        reader.read_exact(&mut len)?;
+        let len = u64::from_le_bytes(len) as usize;
+        let count = read_u32(reader)? as usize;
+
+        let mut nodes = Vec::with_capacity(count.min(1 << 20));
+        for _ in 0..count {
+            let mut terminal = [0];
+            reader.read_exact(&mut terminal)?;
+            let child_count = read_u32(reader)? as usize;
+            let mut children = Vec::with_capacity(child_count.min(1 << 16));
+            for _ in 0..child_count {
+                let c =
+                    char::from_u32(read_u32(reader)?).ok_or_else(|| invalid("bad character"))?;
+                let child = read_u32(reader)?;
+                if child as usize >= count {
+                    return Err(invalid("child out of range"));
+                }
+                children.push((c, child));
+            }
+            nodes.push(Node {
+                terminal: terminal[0] != 0,
+                children,
+            });
+        }
+
+        Ok(Trie { nodes, len })
+    }
+
+    /// Save the trie to the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Load a trie saved with [`Trie::save`] from the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read.
+    pub fn load(path: &Path) -> Result<Trie, Error> {
+        Trie::read_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+/// A [`Trie`] of words spelled backwards, for finding the words with a given ending.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::index::SuffixIndex;
+/// let index = SuffixIndex::new(["singing", "ring", "sing", "cat", "bring"]);
+/// assert_eq!(index.ending_with("ing"), vec!["ring", "sing", "bring", "singing"]);
+/// assert!(index.ending_with("dog").is_empty());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SuffixIndex {
+    reversed: Trie,
+}
+
+impl SuffixIndex {
+    /// Create the index of `words`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to index.
+    pub fn new<I, S>(words: I) -> SuffixIndex
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        SuffixIndex {
+            reversed: Trie::from_words(words.into_iter().map(|word| reverse(word.as_ref()))),
+        }
+    }
+
+    /// Return the words ending with `suffix`, shortest first and in alphabetical order of
+    /// their endings at equal length.
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - The end of the words.
+    pub fn ending_with(&self, suffix: &str) -> Vec<String> {
+        let mut found = self.reversed.with_prefix(&reverse(suffix));
+        // The trie gives the words in order of their reversed spelling, kept at equal length.
+        found.sort_by_key(|word| word.chars().count());
+        found.iter().map(|word| reverse(word)).collect()
+    }
+}
+
+/// Return `word` spelled backwards.
+fn reverse(word: &str) -> String {
+    word.chars().rev().collect()
+}
+
+/// Return the edges of the graph joining each pair of distinct `words` at most
+/// `max_distance` edits apart, by [`crate::edit_distance`]. Each edge is given once, with
+/// the alphabetically first word first, and edges are sorted. The neighbours of every word
+/// are found with a [`Trie`], so dictionaries of many thousands of words are practical.
+///
+/// # Arguments
+///
+/// * `words` - The words of the graph. Duplicates are ignored.
+/// * `max_distance` - The largest distance between neighbours.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::index::neighbour_graph;
+/// let edges = neighbour_graph(&["cut", "cat", "cart", "dog", "cat"], 1);
+/// assert_eq!(edges, vec![("cart", "cat", 1), ("cat", "cut", 1)]);
+/// ```
+pub fn neighbour_graph<'a>(
+    words: &[&'a str],
+    max_distance: usize,
+) -> Vec<(&'a str, &'a str, usize)> {
+    let mut words = words.to_vec();
+    words.sort_unstable();
+    words.dedup();
+    let trie = Trie::from_words(&words);
+
+    let mut edges = vec![];
+    for word in &words {
+        for (neighbour, dist) in trie.find_within(word, max_distance) {
+            if neighbour.as_str() > *word {
+                // Borrow the neighbour from `words` rather than the trie's copy.
+                let index = words.binary_search(&neighbour.as_str()).unwrap();
+                edges.push((*word, words[index], dist));
+            }
+        }
+    }
+    edges.sort_unstable();
+    edges
+}
+
+/// Read a little-endian `u32` from `reader`.
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, Error> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+/// An automaton accepting the strings within `k` edits of a term, by the distance of
+/// [`crate::edit_distance`]. It is fed one character at a time, so a search can stop
+/// following a prefix as soon as no string starting with it can be accepted.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::index::LevenshteinAutomaton;
+/// let automaton = LevenshteinAutomaton::new("test", 1);
+/// let state = "tset".chars().fold(automaton.start(), |state, c| automaton.step(&state, c));
+/// assert_eq!(automaton.distance(&state), Some(1));
+/// let state = "xx".chars().fold(automaton.start(), |state, c| automaton.step(&state, c));
+/// assert!(!automaton.can_match(&state));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LevenshteinAutomaton {
+    term: Vec<char>,
+    k: usize,
+}
+
+/// A state of a [`LevenshteinAutomaton`]: the last row of the edit distance matrix for the
+/// characters read so far, the row before it and the last character, which transpositions
+/// need.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct State {
+    row: Vec<usize>,
+    previous: Option<(Vec<usize>, char)>,
+}
+
+impl LevenshteinAutomaton {
+    /// Create an automaton accepting the strings within `k` edits of `term`.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The term to match.
+    /// * `k` - The most edits a string may be from `term`.
+    pub fn new(term: &str, k: usize) -> LevenshteinAutomaton {
+        LevenshteinAutomaton {
+            term: term.chars().collect(),
+            k,
+        }
+    }
+
+    /// Return the state before any character is read.
+    pub fn start(&self) -> State {
+        State {
+            row: (0..=self.term.len()).collect(),
+            previous: None,
+        }
+    }
+
+    /// Return the state after reading `c` in `state`.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The current state.
+    /// * `c` - The next character.
+    pub fn step(&self, state: &State, c: char) -> State {
+        let mut row = Vec::with_capacity(state.row.len());
+        row.push(state.row[0] + 1);
+        for i in 1..=self.term.len() {
+            let cost = usize::from(self.term[i - 1] != c);
+            let mut cell = (state.row[i - 1] + cost)
+                .min(state.row[i] + 1)
+                .min(row[i - 1] + 1);
+            if let Some((before, last)) = &state.previous {
+                if i > 1 && self.term[i - 1] == *last && self.term[i - 2] == c {
+                    cell = cell.min(before[i - 2] + 1);
+                }
+            }
+            row.push(cell);
+        }
+
+        State {
+            row,
+            previous: Some((state.row.clone(), c)),
+        }
+    }
+
+    /// Return the distance of the string read so far from the term, if it is within `k`.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state after reading the string.
+    pub fn distance(&self, state: &State) -> Option<usize> {
+        let dist = *state.row.last().unwrap();
+        (dist <= self.k).then_some(dist)
+    }
+
+    /// Return whether any string starting with the one read so far can be accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state after reading the string.
+    pub fn can_match(&self, state: &State) -> bool {
+        // No later row has a cell below this row's smallest, since every edit adds to it.
+        state.row.iter().min().is_some_and(|min| *min <= self.k)
+    }
+}
@@ -143,3 +143,118 @@ pub static SUPPORTED_LANGS: phf::Map<&'static str, &'static str> = phf_map! {
     "yi" => "Yiddish",
     "zu" => "Zulu",
 };
+
+/// The name of each supported language in the language itself.
+static NATIVE_NAMES: phf::Map<&'static str, &'static str> = phf_map! {
+    "af" => "Afrikaans",
+    "ar" => "العربية",
+    "az" => "Azərbaycanca",
+    "be" => "Беларуская",
+    "bg" => "Български",
+    "br" => "Brezhoneg",
+    "bs" => "Bosanski",
+    "ca" => "Català",
+    "cs" => "Čeština",
+    "cy" => "Cymraeg",
+    "da" => "Dansk",
+    "de" => "Deutsch",
+    "el" => "Ελληνικά",
+    "en" => "English",
+    "es" => "Español",
+    "et" => "Eesti",
+    "eu" => "Euskara",
+    "fo" => "Føroyskt",
+    "fr" => "Français",
+    "fy" => "Frysk",
+    "gl" => "Galego",
+    "hu" => "Magyar",
+    "id" => "Bahasa Indonesia",
+    "is" => "Íslenska",
+    "it" => "Italiano",
+    "kk" => "Қазақша",
+    "ko" => "한국어",
+    "la" => "Latina",
+    "lb" => "Lëtzebuergesch",
+    "lt" => "Lietuvių",
+    "lv" => "Latviešu",
+    "ms" => "Bahasa Melayu",
+    "nb" => "Norsk bokmål",
+    "nl" => "Nederlands",
+    "nn" => "Norsk nynorsk",
+    "oc" => "Occitan",
+    "pl" => "Polski",
+    "ro" => "Română",
+    "ru" => "Русский",
+    "se" => "Davvisámegiella",
+    "sk" => "Slovenčina",
+    "sl" => "Slovenščina",
+    "sq" => "Shqip",
+    "sr" => "Српски",
+    "st" => "Sesotho",
+    "sv" => "Svenska",
+    "sw" => "Kiswahili",
+    "tg" => "Тоҷикӣ",
+    "tk" => "Türkmençe",
+    "tl" => "Tagalog",
+    "tn" => "Setswana",
+    "tr" => "Türkçe",
+    "ts" => "Xitsonga",
+    "tt" => "Татарча",
+    "uk" => "Українська",
+    "ve" => "Tshivenḓa",
+    "vi" => "Tiếng Việt",
+    "xh" => "isiXhosa",
+    "yi" => "ייִדיש",
+    "zu" => "isiZulu",
+};
+
+/// A language with a word list available for download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Language {
+    /// The locale code, which is also the name of the word list file.
+    pub code: &'static str,
+    /// The English name of the language.
+    pub name: &'static str,
+    /// The name of the language in the language itself.
+    pub native_name: &'static str,
+}
+
+/// Return the supported language with the locale code `code`.
+///
+/// # Arguments
+///
+/// * `code` - The locale code of the language.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::langs::language;
+/// let french = language("fr").unwrap();
+/// assert_eq!(french.name, "French");
+/// assert_eq!(french.native_name, "Français");
+/// assert!(language("xx").is_none());
+/// ```
+pub fn language(code: &str) -> Option<Language> {
+    let (code, name) = SUPPORTED_LANGS.get_entry(code)?;
+
+    Some(Language {
+        code,
+        name,
+        native_name: NATIVE_NAMES.get(code).copied().unwrap_or(name),
+    })
+}
+
+/// Return every supported language, sorted by locale code.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::langs::languages;
+/// assert!(languages().any(|lang| lang.code == "en"));
+/// ```
+pub fn languages() -> impl Iterator<Item = Language> {
+    let mut codes: Vec<&str> = SUPPORTED_LANGS.keys().copied().collect();
+    codes.sort_unstable();
+
+    codes.into_iter().filter_map(language)
+}
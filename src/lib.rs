@@ -1,63 +1,78 @@
-use cli_clipboard::{ClipboardContext, ClipboardProvider};
-use colored::*;
-use std::cmp::min;
-
-#[cfg(unix)]
-use nix::unistd::{fork, ForkResult};
-
-/// Copy `string` to the system clipboard
-///
-/// # Arguments
-///
-/// * `string` - the string to be copied.
-pub fn yank(string: &str) {
-    let platform = std::env::consts::OS;
-    if vec![
-        "linux",
-        "freebsd",
-        "netbsd",
-        "dragonfly",
-        "netbsd",
-        "openbsd",
-        "solaris",
-    ]
-    .contains(&platform)
-    {
-        // The platform is linux/*bsd and is likely using X11 or Wayland.
-        // There is a fix needed for clipboard use in cases like these.
-        // The clipboard is cleared on X11/Wayland after the process that set it exist.
-        // To combat this, we will fork and keep a process aroudn until the clipboard
-        // is cleared.
-        // Ideally, this wouldn't be an issue but it was a conscious design decision
-        // on X11/Wayland
-        #[cfg(unix)]
-        match unsafe { fork() } {
-            Ok(ForkResult::Child) => {
-                let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-                ctx.set_contents(string.to_owned()).unwrap();
-
-                // Keep the process running until the clipboard changes.
-                loop {
-                    let clipboard = ctx.get_contents().unwrap();
-                    std::thread::sleep(std::time::Duration::from_secs(1));
-                    if clipboard != string {
-                        std::process::exit(0);
-                    }
-                }
-            }
-            Err(_) => {
-                println!("{}", "Error: Clipboard fork failed".red());
-                std::process::exit(1);
-            }
-            _ => {}
-        }
-    } else {
-        // The platform is NOT running X11/Wayland and thus, we don't have to handle
-        // the clipboard clearing behaviour.
-        let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-        ctx.set_contents(string.to_owned()).unwrap();
-    }
-}
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod anagram;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod bktree;
+#[cfg(feature = "std")]
+pub mod check;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+#[cfg(feature = "std")]
+pub mod dictionary;
+#[cfg(feature = "std")]
+pub mod engine;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
+pub mod evaluate;
+#[cfg(feature = "std")]
+pub mod fetcher;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod float;
+#[cfg(feature = "std")]
+pub mod git;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod index;
+#[cfg(feature = "std")]
+pub mod langs;
+#[cfg(feature = "std")]
+pub mod lsp;
+#[cfg(feature = "std")]
+pub mod manager;
+#[cfg(feature = "std")]
+pub mod metric;
+pub mod normalize;
+#[cfg(feature = "std")]
+pub mod pattern;
+pub mod phonetic;
+#[cfg(feature = "std")]
+pub mod pipeline;
+#[cfg(feature = "std")]
+pub mod plugin;
+#[cfg(feature = "std")]
+pub mod rpc;
+#[cfg(feature = "std")]
+pub mod session;
+#[cfg(feature = "std")]
+mod sha256;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+#[cfg(feature = "std")]
+pub mod source;
+#[cfg(feature = "std")]
+pub mod symspell;
+#[cfg(feature = "std")]
+pub mod thesaurus;
+pub mod typos;
+#[cfg(feature = "std")]
+mod versions;
+#[cfg(feature = "std")]
+pub mod wordlist;
+
+#[cfg(feature = "clipboard")]
+pub use clipboard::yank;
+
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+use core::cmp::{max, min};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Insert `element` at `index` preserving length.
 ///
@@ -88,12 +103,14 @@ pub fn insert_and_shift<T: Copy>(list: &mut Vec<T>, index: usize, element: T) {
 /// Return the edit distance between `search_term` and `known_term`.
 /// Currently implemented using a modified version of
 /// [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance).
+/// Terms are compared by grapheme cluster, so an accented letter written with a combining
+/// mark or an emoji made of several code points counts as a single character.
 ///
 /// # Arguments
 ///
 /// * `search_chars` - The first `Vec<char>` to compare, in most time search_term will not change, so
-/// we would like to share the same `Vec<char>` between multiple calls. you could use `search_string.chars().collect::<Vec<_>>()` to
-/// convert a string to a `Vec<char>`
+///   we would like to share the same `Vec<char>` between multiple calls. you could use `search_string.chars().collect::<Vec<_>>()` to
+///   convert a string to a `Vec<char>`
 /// * `known_term` - The second string to compare
 ///
 /// # Examples
@@ -106,13 +123,56 @@ pub fn insert_and_shift<T: Copy>(list: &mut Vec<T>, index: usize, element: T) {
 /// assert_eq!(edit_distance(&"cat".chars().collect::<Vec<_>>(), "cut"), 1);
 /// assert_eq!(edit_distance(&"sunday".chars().collect::<Vec<_>>(), "saturday"), 3);
 /// assert_eq!(edit_distance(&"tset".chars().collect::<Vec<_>>(), "test"), 1);
+/// // "cafe\u{301}" ends in an e with a combining acute accent.
+/// assert_eq!(edit_distance(&"cafe\u{301}".chars().collect::<Vec<_>>(), "cafe"), 1);
+/// assert_eq!(edit_distance(&"cafe\u{301}".chars().collect::<Vec<_>>(), "café"), 1);
+/// assert_eq!(edit_distance(&"🇫🇷".chars().collect::<Vec<_>>(), "🇩🇪"), 1);
 /// ```
-#[allow(clippy::iter_count, clippy::needless_range_loop)]
 pub fn edit_distance(search_chars: &[char], known_term: &str) -> usize {
+    // A grapheme cluster of ASCII is a single character, except for "\r\n".
+    if known_term.is_ascii() && search_chars.iter().all(|c| c.is_ascii() && *c != '\r') {
+        let known_chars: Vec<char> = known_term.chars().collect();
+        return edit_distance_seq(search_chars, &known_chars);
+    }
+
+    let search_term: String = search_chars.iter().collect();
+    let search_graphemes: Vec<&str> = search_term.graphemes(true).collect();
+    let known_graphemes: Vec<&str> = known_term.graphemes(true).collect();
+    edit_distance_seq(&search_graphemes, &known_graphemes)
+}
+
+/// Return the edit distance between the sequences `a` and `b` of any items, such as the
+/// words of two sentences, the components of two paths or two byte strings, counting
+/// insertions, deletions, substitutions and transpositions of adjacent items like
+/// [`edit_distance`] does for characters.
+///
+/// # Arguments
+///
+/// * `a` - The first sequence to compare.
+/// * `b` - The second sequence to compare.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::edit_distance_seq;
+/// let command = ["git", "comit", "-m"];
+/// assert_eq!(edit_distance_seq(&command, &["git", "commit", "-m"]), 1);
+/// assert_eq!(edit_distance_seq(&["src", "main.rs"], &["main.rs", "src"]), 1);
+/// assert_eq!(edit_distance_seq(b"kitten", b"sitting"), 3);
+/// assert_eq!(edit_distance_seq::<u8>(&[], b"abc"), 3);
+/// ```
+pub fn edit_distance_seq<T: Eq>(a: &[T], b: &[T]) -> usize {
+    // Return the bottom left corner of the matrix.
+    *osa_matrix(a, b).last().unwrap()
+}
+
+/// Return the matrix of edit distances between the prefixes of `search` and `known`, row
+/// by row, with a row for each prefix of `search` and a column for each prefix of `known`.
+#[allow(clippy::needless_range_loop)]
+fn osa_matrix<T: PartialEq>(search: &[T], known: &[T]) -> Vec<usize> {
     // Set local constants for repeated use later.
-    let known_chars: Vec<char> = known_term.chars().collect();
-    let n = search_chars.iter().count() + 1;
-    let m = known_chars.iter().count() + 1;
+    let n = search.len() + 1;
+    let m = known.len() + 1;
 
     // Setup matrix 2D vector.
     let mut mat = vec![0; m * n];
@@ -127,20 +187,277 @@ pub fn edit_distance(search_chars: &[char], known_term: &str) -> usize {
 
     // Run the algorithm.
     for i in 1..n {
-        // let search_char_i_minus_one = search_chars[i - 1];
-        // let search_char_i_minus_two = if i > 1 { search_chars[i - 2] } else { ' ' };
+        for j in 1..m {
+            let sub_cost = if search[i - 1] == known[j - 1] { 0 } else { 1 };
+
+            mat[i * m + j] = min(
+                mat[(i - 1) * m + j - 1] + sub_cost, // substitution cost
+                min(
+                    mat[(i - 1) * m + j] + 1, // deletion cost
+                    mat[i * m + j - 1] + 1,   // insertion cost
+                ),
+            );
+            if i > 1 && j > 1 && search[i - 1] == known[j - 2] && search[i - 2] == known[j - 1] {
+                mat[i * m + j] = min(
+                    mat[i * m + j],
+                    mat[(i - 2) * m + j - 2] + 1, // transposition cost
+                );
+            }
+        }
+    }
+
+    mat
+}
+
+/// One edit of those turning a word `a` into a word `b`, as returned by [`edit_ops`].
+/// Positions count grapheme clusters, like [`edit_distance`], so `a[i]` is the `i`th item
+/// of `a.graphemes(true)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditOp {
+    /// `b[j]` is inserted before `a[i]`.
+    Insert { i: usize, j: usize },
+    /// `a[i]` is deleted, before `b[j]`.
+    Delete { i: usize, j: usize },
+    /// `a[i]` is replaced with `b[j]`.
+    Substitute { i: usize, j: usize },
+    /// `a[i]` and `a[i + 1]` are swapped, becoming `b[j]` and `b[j + 1]`.
+    Transpose { i: usize, j: usize },
+}
+
+/// Return the edits turning `a` into `b`, in order from the start of the words. There are
+/// as many as the [`edit_distance`] between them, and they can be used to highlight what
+/// differs between a misspelled term and a suggestion.
+///
+/// # Arguments
+///
+/// * `a` - The word to edit, such as a misspelled term.
+/// * `b` - The word to turn it into, such as a suggestion.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{edit_ops, EditOp};
+/// assert_eq!(
+///     edit_ops("sitting", "kitten"),
+///     vec![
+///         EditOp::Substitute { i: 0, j: 0 },
+///         EditOp::Substitute { i: 4, j: 4 },
+///         EditOp::Delete { i: 6, j: 6 },
+///     ]
+/// );
+/// assert_eq!(edit_ops("tset", "test"), vec![EditOp::Transpose { i: 1, j: 1 }]);
+/// assert_eq!(edit_ops("cafe", "café!"), vec![
+///     EditOp::Substitute { i: 3, j: 3 },
+///     EditOp::Insert { i: 4, j: 4 },
+/// ]);
+/// assert!(edit_ops("same", "same").is_empty());
+/// ```
+pub fn edit_ops(a: &str, b: &str) -> Vec<EditOp> {
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    let mat = osa_matrix(&a, &b);
+    let m = b.len() + 1;
+
+    // Walk back from the bottom right corner along cells the distance could come from.
+    let mut ops = vec![];
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        let here = mat[i * m + j];
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && mat[(i - 1) * m + j - 1] == here {
+            i -= 1;
+            j -= 1;
+        } else if i > 1
+            && j > 1
+            && a[i - 1] == b[j - 2]
+            && a[i - 2] == b[j - 1]
+            && mat[(i - 2) * m + j - 2] + 1 == here
+        {
+            ops.push(EditOp::Transpose { i: i - 2, j: j - 2 });
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && mat[(i - 1) * m + j] + 1 == here {
+            ops.push(EditOp::Delete { i: i - 1, j });
+            i -= 1;
+        } else if j > 0 && mat[i * m + j - 1] + 1 == here {
+            ops.push(EditOp::Insert { i, j: j - 1 });
+            j -= 1;
+        } else {
+            ops.push(EditOp::Substitute { i: i - 1, j: j - 1 });
+            i -= 1;
+            j -= 1;
+        }
+    }
+
+    ops.reverse();
+    ops
+}
+
+/// Return the [`edit_distance`] between `search_chars` and each of `known_terms`, in
+/// order. With the `simd` feature, on CPUs with AVX2, ASCII words are compared sixteen at
+/// a time.
+///
+/// # Arguments
+///
+/// * `search_chars` - The characters of the possibly misspelled term.
+/// * `known_terms` - The words to compare with.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::edit_distances;
+/// let search_chars: Vec<char> = "sitting".chars().collect();
+/// assert_eq!(edit_distances(&search_chars, &["kitten", "sitting", "", "tisting"]), vec![3, 0, 7, 2]);
+/// ```
+pub fn edit_distances(search_chars: &[char], known_terms: &[&str]) -> Vec<usize> {
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    if simd::available()
+        && search_chars.len() <= simd::MAX_LEN
+        && search_chars.iter().all(|c| c.is_ascii() && *c != '\r')
+    {
+        let search: Vec<u8> = search_chars.iter().map(|c| *c as u8).collect();
+        let mut distances = Vec::with_capacity(known_terms.len());
+        for chunk in known_terms.chunks(simd::LANES) {
+            if chunk
+                .iter()
+                .all(|t| t.is_ascii() && t.len() <= simd::MAX_LEN)
+            {
+                let words: Vec<&[u8]> = chunk.iter().map(|t| t.as_bytes()).collect();
+                // SAFETY: simd::available checked that the CPU supports AVX2.
+                let batch = unsafe { simd::edit_distances(&search, &words) };
+                distances.extend_from_slice(&batch[..chunk.len()]);
+            } else {
+                distances.extend(chunk.iter().map(|t| edit_distance(search_chars, t)));
+            }
+        }
+        return distances;
+    }
+
+    known_terms
+        .iter()
+        .map(|known_term| edit_distance(search_chars, known_term))
+        .collect()
+}
+
+/// Return how similar `a` and `b` are, from 0 for nothing in common to 1 for equal
+/// strings: one minus their [`edit_distance`] divided by the length of the longer one.
+/// Unlike the raw distance, it means the same for short and long words, so a single
+/// threshold suits both.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::similarity;
+/// assert_eq!(similarity("kitten", "kitten"), 1.0);
+/// assert_eq!(similarity("kitten", "sitting"), 4.0 / 7.0);
+/// assert_eq!(similarity("cats", "cuts"), 0.75);
+/// assert_eq!(similarity("", ""), 1.0);
+/// assert_eq!(similarity("abc", ""), 0.0);
+/// // One edit matters less in a long word.
+/// assert!(similarity("accomodation", "accommodation") > similarity("cat", "cut"));
+/// ```
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a_chars: Vec<char> = a.chars().collect();
+    similarity_at(a, b, edit_distance(&a_chars, b))
+}
+
+/// Return the [`similarity`] of `a` and `b` given their edit distance, `distance`.
+pub(crate) fn similarity_at(a: &str, b: &str, distance: usize) -> f64 {
+    let longer = max(a.graphemes(true).count(), b.graphemes(true).count());
+    if longer == 0 {
+        return 1.0;
+    }
+
+    1.0 - distance as f64 / longer as f64
+}
+
+/// The cost of each kind of edit, for [`edit_distance_with_costs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Costs {
+    /// The cost of inserting a character into the search term.
+    pub insert: usize,
+    /// The cost of deleting a character from the search term.
+    pub delete: usize,
+    /// The cost of replacing a character of the search term.
+    pub substitute: usize,
+    /// The cost of swapping two adjacent characters of the search term.
+    pub transpose: usize,
+}
+
+impl Default for Costs {
+    fn default() -> Costs {
+        Costs {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+            transpose: 1,
+        }
+    }
+}
+
+impl Costs {
+    /// Return the cost of the most expensive kind of edit.
+    pub fn max(&self) -> usize {
+        max(
+            max(self.insert, self.delete),
+            max(self.substitute, self.transpose),
+        )
+    }
+}
+
+/// Return the edit distance between `search_chars` and `known_term` like
+/// [`edit_distance`], but with each kind of edit costing what `costs` says. Costs are
+/// relative, so to make transpositions nearly free, make every other edit cost ten and
+/// transpositions one.
+///
+/// # Arguments
+///
+/// * `search_chars` - The characters of the possibly misspelled term.
+/// * `known_term` - The known term to compare with.
+/// * `costs` - The cost of each kind of edit.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{edit_distance_with_costs, Costs};
+/// let chars = |s: &str| s.chars().collect::<Vec<_>>();
+/// let costs = Costs { insert: 10, delete: 30, substitute: 10, transpose: 1 };
+/// assert_eq!(edit_distance_with_costs(&chars("tset"), "test", costs), 1);
+/// assert_eq!(edit_distance_with_costs(&chars("tests"), "test", costs), 30);
+/// assert_eq!(edit_distance_with_costs(&chars("tst"), "test", costs), 10);
+/// assert_eq!(edit_distance_with_costs(&chars("sitting"), "kitten", Costs::default()), 3);
+/// ```
+#[allow(clippy::needless_range_loop)]
+pub fn edit_distance_with_costs(search_chars: &[char], known_term: &str, costs: Costs) -> usize {
+    let known_chars: Vec<char> = known_term.chars().collect();
+    let n = search_chars.len() + 1;
+    let m = known_chars.len() + 1;
+    let mut mat = vec![0; m * n];
+
+    for i in 1..n {
+        mat[i * m] = i * costs.delete;
+    }
+    for j in 1..m {
+        mat[j] = j * costs.insert;
+    }
+
+    for i in 1..n {
         for j in 1..m {
             let sub_cost = if search_chars[i - 1] == known_chars[j - 1] {
                 0
             } else {
-                1
+                costs.substitute
             };
 
             mat[i * m + j] = min(
                 mat[(i - 1) * m + j - 1] + sub_cost, // substitution cost
                 min(
-                    mat[(i - 1) * m + j] + 1, // deletion cost
-                    mat[i * m + j - 1] + 1,   // insertion cost
+                    mat[(i - 1) * m + j] + costs.delete, // deletion cost
+                    mat[i * m + j - 1] + costs.insert,   // insertion cost
                 ),
             );
             if i > 1
@@ -150,12 +467,799 @@ pub fn edit_distance(search_chars: &[char], known_term: &str) -> usize {
             {
                 mat[i * m + j] = min(
                     mat[i * m + j],
-                    mat[(i - 2) * m + j - 2] + 1, // transposition cost
+                    mat[(i - 2) * m + j - 2] + costs.transpose, // transposition cost
+                );
+            }
+        }
+    }
+
+    mat[m * n - 1]
+}
+
+/// Return the edit distance between `search_chars` and `known_term` like
+/// [`edit_distance`], but in tenths of an edit, with substituting a character typed on a
+/// nearby key costing less than a full edit. Neighbouring keys on a QWERTY keyboard cost
+/// about half an edit, while keys two or more apart, and characters off the letter keys,
+/// cost a whole one.
+///
+/// # Arguments
+///
+/// * `search_chars` - The characters of the possibly misspelled term.
+/// * `known_term` - The known term to compare with.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::edit_distance_weighted;
+/// let chars = |s: &str| s.chars().collect::<Vec<_>>();
+/// assert_eq!(edit_distance_weighted(&chars("cst"), "cat"), 5);
+/// assert_eq!(edit_distance_weighted(&chars("czt"), "cat"), 6);
+/// assert_eq!(edit_distance_weighted(&chars("cpt"), "cat"), 10);
+/// assert_eq!(edit_distance_weighted(&chars("tset"), "test"), 10);
+/// ```
+#[allow(clippy::needless_range_loop)]
+pub fn edit_distance_weighted(search_chars: &[char], known_term: &str) -> usize {
+    const EDIT: usize = 10;
+
+    let known_chars: Vec<char> = known_term.chars().collect();
+    let n = search_chars.len() + 1;
+    let m = known_chars.len() + 1;
+    let mut mat = vec![0; m * n];
+
+    for i in 1..n {
+        mat[i * m] = i * EDIT;
+    }
+    for j in 1..m {
+        mat[j] = j * EDIT;
+    }
+
+    for i in 1..n {
+        for j in 1..m {
+            let (a, b) = (search_chars[i - 1], known_chars[j - 1]);
+            let sub_cost = match typos::key_distance(a, b) {
+                _ if a == b => 0,
+                Some(keys) => min(EDIT, float::round(keys * EDIT as f64 / 2.0) as usize),
+                None => EDIT,
+            };
+
+            mat[i * m + j] = min(
+                mat[(i - 1) * m + j - 1] + sub_cost, // substitution cost
+                min(
+                    mat[(i - 1) * m + j] + EDIT, // deletion cost
+                    mat[i * m + j - 1] + EDIT,   // insertion cost
+                ),
+            );
+            if i > 1 && j > 1 && a == known_chars[j - 2] && search_chars[i - 2] == b {
+                mat[i * m + j] = min(
+                    mat[i * m + j],
+                    mat[(i - 2) * m + j - 2] + EDIT, // transposition cost
                 );
             }
         }
     }
 
-    // Return the bottom left corner of the matrix.
     mat[m * n - 1]
 }
+
+/// Return the Jaro-Winkler similarity between `a` and `b`, from 0 for nothing in common to
+/// 1 for equal strings. Characters shared within a window count as matches, and a common
+/// prefix of up to four characters scores extra, so typos towards the end of a word cost
+/// less than typos at its start.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::jaro_winkler;
+/// assert_eq!(jaro_winkler("martha", "martha"), 1.0);
+/// assert!((jaro_winkler("martha", "marhta") - 0.961).abs() < 0.001);
+/// assert!((jaro_winkler("dwayne", "duane") - 0.84).abs() < 0.001);
+/// assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    jaro_winkler_chars(&a, &b)
+}
+
+/// Return the Jaro-Winkler similarity between the character sequences `a` and `b`.
+fn jaro_winkler_chars(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    // Characters match if they are equal and no further apart than the window.
+    let window = (max(a.len(), b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+    for (i, c) in a.iter().enumerate() {
+        let end = min(i + window + 1, b.len());
+        for j in i.saturating_sub(window)..end {
+            if !b_matched[j] && b[j] == *c {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // Matched characters that appear in a different order are half a transposition each.
+    let mut b_matches = b.iter().zip(&b_matched).filter(|(_, m)| **m);
+    let out_of_order = a
+        .iter()
+        .zip(&a_matched)
+        .filter(|(_, m)| **m)
+        .filter(|(c, _)| b_matches.next().map(|(d, _)| d) != Some(c))
+        .count();
+
+    let m = matches as f64;
+    let jaro =
+        (m / a.len() as f64 + m / b.len() as f64 + (m - out_of_order as f64 / 2.0) / m) / 3.0;
+    let prefix = a.iter().zip(b).take(4).take_while(|(x, y)| x == y).count();
+
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// Return the similarity of `a` and `b` by the character n-grams they share, from 0 for
+/// none in common to 1 for equal strings. Each string is padded with a space at both ends,
+/// so n-grams at its start and end count too, and the score is the Dice coefficient of the
+/// two collections of n-grams. Unlike edit distances, it copes with long words mangled in
+/// several places, since it only asks which pieces of the term survive.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+/// * `n` - The length of the n-grams, at least 1. Trigrams, with 3, suit most words.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::ngram_similarity;
+/// assert_eq!(ngram_similarity("night", "night", 3), 1.0);
+/// // Only "ht " is shared among five trigrams each.
+/// assert_eq!(ngram_similarity("night", "nacht", 3), 0.2);
+/// assert_eq!(ngram_similarity("abc", "xyz", 2), 0.0);
+/// ```
+pub fn ngram_similarity(a: &str, b: &str, n: usize) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    ngram_similarity_chars(&a, &b, n)
+}
+
+/// Return the n-gram similarity between the character sequences `a` and `b`.
+fn ngram_similarity_chars(a: &[char], b: &[char], n: usize) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    // The sorted n-grams of a sequence padded with a space at both ends.
+    let ngrams = |chars: &[char]| -> Vec<Vec<char>> {
+        let mut padded = Vec::with_capacity(chars.len() + 2);
+        padded.push(' ');
+        padded.extend_from_slice(chars);
+        padded.push(' ');
+        let mut ngrams: Vec<Vec<char>> = padded.windows(n.max(1)).map(<[char]>::to_vec).collect();
+        ngrams.sort_unstable();
+        ngrams
+    };
+    let (a, b) = (ngrams(a), ngrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Count the n-grams in common by walking both sorted lists at once.
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            core::cmp::Ordering::Less => i += 1,
+            core::cmp::Ordering::Greater => j += 1,
+            core::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    2.0 * shared as f64 / (a.len() + b.len()) as f64
+}
+
+/// Return the number of characters to delete from and insert into `a` to turn it into
+/// `b`, without substitutions or transpositions. It is the length of both strings less
+/// twice that of their longest common subsequence, so a truncated or expanded word is as
+/// close as its missing or extra characters, but a wrong character costs two.
+///
+/// # Arguments
+///
+/// * `a` - The first string to compare.
+/// * `b` - The second string to compare.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::lcs_distance;
+/// assert_eq!(lcs_distance("kitten", "sitting"), 5);
+/// assert_eq!(lcs_distance("cat", "cart"), 1);
+/// assert_eq!(lcs_distance("cat", "cut"), 2);
+/// assert_eq!(lcs_distance("", "abc"), 3);
+/// ```
+pub fn lcs_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    lcs_distance_chars(&a, &b)
+}
+
+/// Return the [`lcs_distance`] between the character sequences `a` and `b`.
+fn lcs_distance_chars(a: &[char], b: &[char]) -> usize {
+    // Lengths of the longest common subsequences of a's prefix so far and each of b's.
+    let mut last = vec![0; b.len() + 1];
+    let mut row = vec![0; b.len() + 1];
+    for x in a {
+        for (j, y) in b.iter().enumerate() {
+            row[j + 1] = match x == y {
+                true => last[j] + 1,
+                false => max(last[j + 1], row[j]),
+            };
+        }
+        core::mem::swap(&mut last, &mut row);
+    }
+
+    a.len() + b.len() - 2 * last[b.len()]
+}
+
+/// The length of the n-grams compared under [`Ranking::Ngram`].
+pub const NGRAM_LENGTH: usize = 3;
+
+/// How candidates are ranked against the search term.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Ranking {
+    /// By [`edit_distance`], the number of edits between them.
+    #[default]
+    EditDistance,
+    /// By [`jaro_winkler`] similarity, which favours candidates sharing a prefix with the
+    /// term. Distances are the dissimilarity in thousandths.
+    JaroWinkler,
+    /// By [`edit_distance`], among only the candidates that
+    /// [sound like](phonetic::sounds_alike) the term.
+    Soundex,
+    /// By [`edit_distance`], with candidates that do not
+    /// [sound like](phonetic::metaphones_match) the term by Double Metaphone
+    /// [`METAPHONE_PENALTY`] edits further away, so that misspellings such as "nite" for
+    /// "knight" are still suggested.
+    Metaphone,
+    /// By [`edit_distance_weighted`], which makes slips onto nearby keys cheaper.
+    /// Distances are in tenths of an edit.
+    Keyboard,
+    /// By [`edit_distance_with_costs`] with these costs.
+    Costs(Costs),
+    /// By [`ngram_similarity`] of [`NGRAM_LENGTH`]-grams, which scales to long words
+    /// mangled in several places. Distances are the dissimilarity in thousandths.
+    Ngram,
+    /// By [`lcs_distance`], counting only insertions and deletions, which ranks truncated
+    /// and expanded words closer than ones with wrong characters.
+    Lcs,
+}
+
+/// The edits added to the distance of candidates that do not sound like the term under
+/// [`Ranking::Metaphone`].
+pub const METAPHONE_PENALTY: usize = 3;
+
+impl Ranking {
+    /// Return the distance between the term `term_chars` and `candidate`, lower being
+    /// closer.
+    ///
+    /// # Arguments
+    ///
+    /// * `term_chars` - The characters of the possibly misspelled term.
+    /// * `candidate` - The known term to compare with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::Ranking;
+    /// let term: Vec<char> = "marhta".chars().collect();
+    /// assert_eq!(Ranking::EditDistance.distance(&term, "martha"), 1);
+    /// assert_eq!(Ranking::JaroWinkler.distance(&term, "martha"), 39);
+    ///
+    /// // Candidates that do not sound like the term are infinitely far away.
+    /// let term: Vec<char> = "fone".chars().collect();
+    /// assert_eq!(Ranking::Soundex.distance(&term, "phone"), 2);
+    /// assert_eq!(Ranking::Soundex.distance(&term, "phase"), usize::MAX);
+    ///
+    /// let term: Vec<char> = "nite".chars().collect();
+    /// assert_eq!(Ranking::Metaphone.distance(&term, "knight"), 4);
+    /// assert_eq!(Ranking::Metaphone.distance(&term, "nine"), 4);
+    ///
+    /// let term: Vec<char> = "night".chars().collect();
+    /// assert_eq!(Ranking::Ngram.distance(&term, "nacht"), 800);
+    /// assert_eq!(Ranking::Lcs.distance(&term, "nigh"), 1);
+    /// ```
+    pub fn distance(self, term_chars: &[char], candidate: &str) -> usize {
+        match self {
+            Ranking::EditDistance => edit_distance(term_chars, candidate),
+            Ranking::JaroWinkler => {
+                let candidate: Vec<char> = candidate.chars().collect();
+                let similarity = jaro_winkler_chars(term_chars, &candidate);
+                float::round((1.0 - similarity) * 1000.0) as usize
+            }
+            Ranking::Soundex => {
+                let term: String = term_chars.iter().collect();
+                match phonetic::sounds_alike(&term, candidate) {
+                    true => edit_distance(term_chars, candidate),
+                    false => UNRANKED,
+                }
+            }
+            Ranking::Metaphone => {
+                let term: String = term_chars.iter().collect();
+                let penalty = match phonetic::metaphones_match(&term, candidate) {
+                    true => 0,
+                    false => METAPHONE_PENALTY,
+                };
+                edit_distance(term_chars, candidate) + penalty
+            }
+            Ranking::Keyboard => edit_distance_weighted(term_chars, candidate),
+            Ranking::Costs(costs) => edit_distance_with_costs(term_chars, candidate, costs),
+            Ranking::Ngram => {
+                let candidate: Vec<char> = candidate.chars().collect();
+                let similarity = ngram_similarity_chars(term_chars, &candidate, NGRAM_LENGTH);
+                float::round((1.0 - similarity) * 1000.0) as usize
+            }
+            Ranking::Lcs => {
+                let candidate: Vec<char> = candidate.chars().collect();
+                lcs_distance_chars(term_chars, &candidate)
+            }
+        }
+    }
+
+    /// Return the largest distance from `term` that [`Threshold::Auto`] accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    pub fn auto_threshold(self, term: &str) -> usize {
+        match self {
+            Ranking::EditDistance => Threshold::Auto.max_distance(term),
+            // A similarity of 0.8, a common cut-off for Jaro-Winkler.
+            Ranking::JaroWinkler => 200,
+            // Sounding alike is threshold enough.
+            Ranking::Soundex => usize::MAX,
+            // Candidates that sound alike get the penalty back.
+            Ranking::Metaphone => Threshold::Auto.max_distance(term) + METAPHONE_PENALTY,
+            Ranking::Keyboard => Threshold::Auto.max_distance(term) * 10,
+            Ranking::Costs(costs) => Threshold::Auto.max_distance(term) * costs.max(),
+            // Sharing half of the trigrams, as a word of seven letters with a typo in the
+            // middle still does.
+            Ranking::Ngram => 500,
+            // A wrong character is a deletion and an insertion.
+            Ranking::Lcs => Threshold::Auto.max_distance(term) * 2,
+        }
+    }
+}
+
+/// The distance of candidates that are never ranked, such as words that do not sound
+/// alike under [`Ranking::Soundex`].
+const UNRANKED: usize = usize::MAX;
+
+/// Return `word` case folded, so that words differing only in case fold to the same
+/// string. This is lowercasing, except that the German sharp s becomes "ss", the Greek
+/// final sigma and the long s become their usual forms and the Latin ligatures ﬀ, ﬁ, ﬂ,
+/// ﬃ, ﬄ, ﬅ and ﬆ are spelled out. It is not the full Unicode case folding, which folds
+/// more characters this way.
+///
+/// # Arguments
+///
+/// * `word` - The word to fold.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::fold_case;
+/// assert_eq!(fold_case("Paris"), "paris");
+/// assert_eq!(fold_case("STRAẞE"), fold_case("straße"));
+/// assert_eq!(fold_case("ΣΟΦΟΣ"), fold_case("σοφος"));
+/// ```
+pub fn fold_case(word: &str) -> String {
+    word.chars().flat_map(char::to_lowercase).fold(
+        String::with_capacity(word.len()),
+        |mut folded, c| {
+            match c {
+                'ß' => folded.push_str("ss"),
+                'ς' => folded.push('σ'),
+                'ſ' => folded.push('s'),
+                'ﬀ' => folded.push_str("ff"),
+                'ﬁ' => folded.push_str("fi"),
+                'ﬂ' => folded.push_str("fl"),
+                'ﬃ' => folded.push_str("ffi"),
+                'ﬄ' => folded.push_str("ffl"),
+                'ﬅ' | 'ﬆ' => folded.push_str("st"),
+                _ => folded.push(c),
+            }
+            folded
+        },
+    )
+}
+
+/// Options changing which differences between words count towards their distance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Compare words by their [case folding](fold_case), so that differences in case are
+    /// free.
+    pub ignore_case: bool,
+    /// Compare words [without their accents](normalize::strip_accents), so that
+    /// "resume" matches "résumé".
+    pub ignore_accents: bool,
+}
+
+impl MatchOptions {
+    /// Return `word` as it is compared under these options.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to normalize.
+    pub fn normalize<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        let mut word = Cow::Borrowed(word);
+        // ASCII has no accents, so most words can skip decomposing.
+        if self.ignore_accents && !word.is_ascii() {
+            word = Cow::Owned(normalize::strip_accents(&word));
+        }
+        if self.ignore_case {
+            word = Cow::Owned(fold_case(&word));
+        }
+        word
+    }
+
+    /// Return the [`edit_distance`] between `a` and `b` under these options.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first word to compare.
+    /// * `b` - The second word to compare.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::MatchOptions;
+    /// let options = MatchOptions { ignore_case: true, ..MatchOptions::default() };
+    /// assert_eq!(options.distance("Paris", "paris"), 0);
+    /// assert_eq!(MatchOptions::default().distance("Paris", "paris"), 1);
+    /// let options = MatchOptions { ignore_accents: true, ..MatchOptions::default() };
+    /// assert_eq!(options.distance("resume", "résumé"), 0);
+    /// assert_eq!(MatchOptions::default().distance("resume", "résumé"), 2);
+    /// ```
+    pub fn distance(&self, a: &str, b: &str) -> usize {
+        let a: Vec<char> = self.normalize(a).chars().collect();
+        edit_distance(&a, &self.normalize(b))
+    }
+}
+
+/// How far a candidate may be from the term for it to be suggested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Threshold {
+    /// At most this many edits.
+    Fixed(usize),
+    /// At most a third of the term's length in edits, and at least one, so that short
+    /// terms are not matched with unrelated words.
+    Auto,
+}
+
+impl Threshold {
+    /// Return the largest edit distance a suggestion for `term` may have.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::Threshold;
+    /// assert_eq!(Threshold::Auto.max_distance("cat"), 1);
+    /// assert_eq!(Threshold::Auto.max_distance("receive"), 2);
+    /// assert_eq!(Threshold::Fixed(4).max_distance("cat"), 4);
+    /// ```
+    pub fn max_distance(self, term: &str) -> usize {
+        match self {
+            Threshold::Fixed(max_distance) => max_distance,
+            Threshold::Auto => max(1, term.chars().count() / 3),
+        }
+    }
+}
+
+impl From<usize> for Threshold {
+    fn from(max_distance: usize) -> Threshold {
+        Threshold::Fixed(max_distance)
+    }
+}
+
+/// Return the candidate closest to `term` by [`edit_distance`], or `None` if even the
+/// closest one is further than `threshold` allows. Ties go to the earliest candidate.
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `candidates` - The known terms to choose from.
+/// * `threshold` - The largest edit distance a suggestion may have, or
+///   [`Threshold::Auto`] to scale it with the length of `term`.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{did_you_mean, Threshold};
+/// let flags = ["verbose", "version", "yank"];
+/// assert_eq!(did_you_mean("verbos", flags, 2), Some("verbose"));
+/// assert_eq!(did_you_mean("colour", flags, 2), None);
+/// assert_eq!(did_you_mean("yak", flags, Threshold::Auto), Some("yank"));
+/// assert_eq!(did_you_mean("vrbse", flags, Threshold::Auto), None);
+/// ```
+pub fn did_you_mean<'a, I, T>(term: &str, candidates: I, threshold: T) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+    T: Into<Threshold>,
+{
+    let term_chars: Vec<char> = term.chars().collect();
+    let max_distance = threshold.into().max_distance(term);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(&term_chars, candidate), candidate))
+        .filter(|(dist, _)| *dist <= max_distance)
+        .min_by_key(|(dist, _)| *dist)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Return the `n` candidates closest to `term` by [`edit_distance`] with their distances,
+/// closest first. Ties keep the order of `candidates`.
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `candidates` - The known terms to rank.
+/// * `n` - The number of candidates to return.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::top_n;
+/// let words = ["world", "hello", "help", "yellow"];
+/// assert_eq!(top_n("helo", words, 2), vec![("hello", 1), ("help", 1)]);
+/// ```
+pub fn top_n<'a, I>(term: &str, candidates: I, n: usize) -> Vec<(&'a str, usize)>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    top_n_by(
+        term,
+        candidates.into_iter().map(|word| (word, word)),
+        n,
+        Ranking::EditDistance,
+        MatchOptions::default(),
+    )
+}
+
+/// Rank `candidates` by their distance to `term` under each of `rankings` in a single pass,
+/// returning the `n` closest candidates and their distances for each ranking, in the order
+/// of `rankings`. Ties keep the order of `candidates`, and candidates a ranking cannot rank,
+/// such as words that do not sound alike under [`Ranking::Soundex`], are left out of it.
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `candidates` - The known terms to rank.
+/// * `rankings` - How to measure the distance between `term` and each candidate.
+/// * `n` - The number of candidates to return for each ranking.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{top_n_multi, Ranking};
+/// let words = ["tets", "test", "text"];
+/// let rankings = [Ranking::EditDistance, Ranking::Lcs, Ranking::JaroWinkler];
+/// let ranked = top_n_multi("tset", words, &rankings, 2);
+/// assert_eq!(ranked[0], vec![("test", 1), ("tets", 2)]);
+/// assert_eq!(ranked[1], vec![("tets", 2), ("test", 2)]);
+/// assert_eq!(ranked[2], vec![("test", 75), ("tets", 150)]);
+/// ```
+pub fn top_n_multi<'a, I>(
+    term: &str,
+    candidates: I,
+    rankings: &[Ranking],
+    n: usize,
+) -> Vec<Vec<(&'a str, usize)>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let term_chars: Vec<char> = term.chars().collect();
+    let mut ranked: Vec<Vec<(&'a str, usize)>> = vec![Vec::with_capacity(n + 1); rankings.len()];
+
+    for candidate in candidates {
+        for (ranking, ranked) in rankings.iter().zip(ranked.iter_mut()) {
+            let dist = ranking.distance(&term_chars, candidate);
+            if dist == UNRANKED {
+                continue;
+            }
+            let index = ranked.partition_point(|(_, d)| *d <= dist);
+            if index < n {
+                ranked.insert(index, (candidate, dist));
+                ranked.truncate(n);
+            }
+        }
+    }
+
+    ranked
+}
+
+/// A word suggested by [`suggest`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion<'a> {
+    /// The suggested word.
+    pub word: &'a str,
+    /// The [`edit_distance`] between the search term and the word.
+    pub distance: usize,
+    /// The [`similarity`] of the search term and the word, from 0 to 1.
+    pub score: f64,
+}
+
+/// Return the `n` candidates closest to `term` by [`edit_distance`], closest first, with
+/// their distances and similarity scores. Ties keep the order of `candidates`, as with
+/// [`top_n`].
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `candidates` - The known terms to rank.
+/// * `n` - The number of suggestions to return.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{suggest, Suggestion};
+/// let words = ["world", "hello", "help", "yellow"];
+/// let suggestions = suggest("helo", words, 2);
+/// assert_eq!(
+///     suggestions[0],
+///     Suggestion { word: "hello", distance: 1, score: 0.8 }
+/// );
+/// assert_eq!(suggestions[1].word, "help");
+/// assert_eq!(suggestions[1].score, 0.75);
+/// ```
+pub fn suggest<'a, I>(term: &str, candidates: I, n: usize) -> Vec<Suggestion<'a>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    top_n(term, candidates, n)
+        .into_iter()
+        .map(|(word, distance)| Suggestion {
+            word,
+            distance,
+            score: similarity_at(term, word, distance),
+        })
+        .collect()
+}
+
+/// The number of candidates [`top_n_by`] hands to [`edit_distances`] at once.
+const SIMD_BATCH: usize = 1024;
+
+/// Like [`top_n`], but ranking `(item, word)` pairs by the distance between `term` and
+/// `word` under `ranking` and `options`, and returning the items.
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `candidates` - The items to rank, each with the word it is ranked by.
+/// * `n` - The number of items to return.
+/// * `ranking` - How to measure the distance between `term` and each word.
+/// * `options` - Which differences between `term` and each word to ignore.
+pub(crate) fn top_n_by<'a, T, I>(
+    term: &str,
+    candidates: I,
+    n: usize,
+    ranking: Ranking,
+    options: MatchOptions,
+) -> Vec<(T, usize)>
+where
+    T: Copy + Default,
+    I: IntoIterator<Item = (T, &'a str)>,
+{
+    if n == 0 {
+        return vec![];
+    }
+
+    // Create mutable vecs for storing the top n items.
+    let term = options.normalize(term);
+    let mut top_n_items = vec![T::default(); n];
+    let mut top_n_dists = vec![UNRANKED; n];
+    let mut consider = |item: T, word: &str, dist: usize| {
+        if dist < top_n_dists[n - 1] {
+            for i in 0..n {
+                if dist < top_n_dists[i] {
+                    tracing::trace!(word, dist, rank = i + 1, "ranked");
+                    insert_and_shift(&mut top_n_dists, i, dist);
+                    insert_and_shift(&mut top_n_items, i, item);
+                    break;
+                }
+            }
+        }
+    };
+
+    // Loop over the candidates, run the algorithm, and add to the list if appropriate.
+    let search_chars = term.chars().collect::<Vec<_>>();
+    if cfg!(feature = "simd") && ranking == Ranking::EditDistance {
+        // Compare the candidates in batches, so that edit_distances can compare several at
+        // once.
+        let mut candidates = candidates.into_iter().peekable();
+        while candidates.peek().is_some() {
+            let batch: Vec<(T, Cow<str>)> = candidates
+                .by_ref()
+                .take(SIMD_BATCH)
+                .map(|(item, word)| (item, options.normalize(word)))
+                .collect();
+            let words: Vec<&str> = batch.iter().map(|(_, word)| word.as_ref()).collect();
+            let dists = edit_distances(&search_chars, &words);
+            for ((item, word), dist) in batch.iter().zip(dists) {
+                consider(*item, word, dist);
+            }
+        }
+    } else {
+        for (item, word) in candidates {
+            let word = options.normalize(word);
+            consider(item, &word, ranking.distance(&search_chars, &word));
+        }
+    }
+
+    top_n_items
+        .into_iter()
+        .zip(top_n_dists)
+        .filter(|(_, dist)| *dist < UNRANKED)
+        .collect()
+}
+
+/// Return every item of `candidates` whose word is at most `max_distance` from `term`
+/// under `ranking` and `options`, with its distance, closest first. Ties keep the order of
+/// `candidates`.
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `candidates` - The items to rank, each with the word it is ranked by.
+/// * `max_distance` - The largest distance to return items at.
+/// * `ranking` - How to measure the distance between `term` and each word.
+/// * `options` - Which differences between `term` and each word to ignore.
+#[cfg(feature = "std")]
+pub(crate) fn within_by<'a, T, I>(
+    term: &str,
+    candidates: I,
+    max_distance: usize,
+    ranking: Ranking,
+    options: MatchOptions,
+) -> Vec<(T, usize)>
+where
+    I: IntoIterator<Item = (T, &'a str)>,
+{
+    let term = options.normalize(term);
+    let search_chars = term.chars().collect::<Vec<_>>();
+
+    let mut items: Vec<(T, usize)> = candidates
+        .into_iter()
+        .map(|(item, word)| {
+            (
+                item,
+                ranking.distance(&search_chars, &options.normalize(word)),
+            )
+        })
+        // Unrankable candidates are left out even when any distance is close enough.
+        .filter(|(_, dist)| *dist != UNRANKED && *dist <= max_distance)
+        .collect();
+    items.sort_by_key(|(_, dist)| *dist);
+    items
+}
@@ -0,0 +1,255 @@
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{BufRead, Error, ErrorKind, Write},
+};
+
+use crate::check::Finding;
+
+/// The JSON-RPC error code for a method the server does not implement.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Read one Language Server Protocol message from `reader`, or `None` if it is at its
+/// end.
+///
+/// # Arguments
+///
+/// * `reader` - Where to read the message from.
+fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>, Error> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let length =
+        length.ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing Content-Length"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
+/// Write `message` to `writer` as a Language Server Protocol message.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the message.
+/// * `message` - The JSON-RPC message to write.
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<(), Error> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+/// Return the LSP position of the byte `offset` in `text`: its line and its column in
+/// UTF-16 code units, as clients count them by default.
+///
+/// # Arguments
+///
+/// * `text` - The text of the document.
+/// * `offset` - The byte offset into `text`.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::lsp::position;
+/// assert_eq!(position("one\ntwo", 5), (1, 1));
+/// assert_eq!(position("😀 smile", 5), (0, 3));
+/// ```
+pub fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    (
+        before.matches('\n').count(),
+        before[line_start..].encode_utf16().count(),
+    )
+}
+
+/// Return the LSP range of the byte range `span` in `text`.
+fn range(text: &str, span: &std::ops::Range<usize>) -> Value {
+    let (start_line, start_char) = position(text, span.start);
+    let (end_line, end_char) = position(text, span.end);
+    json!({
+        "start": { "line": start_line, "character": start_char },
+        "end": { "line": end_line, "character": end_char },
+    })
+}
+
+/// Return the `(line, character)` of an LSP position.
+fn line_and_character(position: &Value) -> (u64, u64) {
+    (
+        position["line"].as_u64().unwrap_or(0),
+        position["character"].as_u64().unwrap_or(0),
+    )
+}
+
+/// Return the diagnostic reporting `finding` in `text`.
+fn diagnostic(text: &str, finding: &Finding) -> Value {
+    let mut message = format!("Unknown word \"{}\"", finding.word);
+    if !finding.suggestions.is_empty() {
+        message.push_str(&format!(
+            ", did you mean {}?",
+            finding.suggestions.join(", ")
+        ));
+    }
+
+    json!({
+        "range": range(text, &finding.span),
+        // Information, since unknown words are often names rather than mistakes.
+        "severity": 3,
+        "source": "dym",
+        "message": message,
+    })
+}
+
+/// Run a Language Server Protocol server reading requests from `input` and writing
+/// responses and notifications to `output`, until the client asks it to exit or `input`
+/// ends. Documents are synchronised in full, every change is checked with `check`, and
+/// each finding is published as a diagnostic with a quick fix per suggestion.
+///
+/// # Arguments
+///
+/// * `input` - Where to read messages from, such as standard input.
+/// * `output` - Where to write messages to, such as standard output.
+/// * `check` - Returns the unknown words of a document's text.
+pub fn serve<R, W, F>(mut input: R, mut output: W, mut check: F) -> Result<(), Error>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&str) -> Vec<Finding>,
+{
+    // The text and findings of every open document, by URI.
+    let mut documents: HashMap<String, (String, Vec<Finding>)> = HashMap::new();
+
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let id = message.get("id").cloned();
+
+        // The URI and new text of the document a notification changed, if any.
+        let mut changed = None;
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    // Full document synchronisation.
+                    "textDocumentSync": 1,
+                    "codeActionProvider": true,
+                },
+                "serverInfo": { "name": "dym" },
+            }),
+            "shutdown" => Value::Null,
+            "exit" => return Ok(()),
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                changed = Some((&document["uri"], &document["text"]));
+                Value::Null
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                let text = changes.and_then(|changes| changes.last());
+                changed = text.map(|change| (&params["textDocument"]["uri"], &change["text"]));
+                Value::Null
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                documents.remove(uri);
+                let notification = json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": uri, "diagnostics": [] },
+                });
+                write_message(&mut output, &notification)?;
+                Value::Null
+            }
+            "textDocument/codeAction" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let start = line_and_character(&params["range"]["start"]);
+                let end = line_and_character(&params["range"]["end"]);
+
+                let mut actions = vec![];
+                if let Some((text, findings)) = documents.get(uri) {
+                    for finding in findings {
+                        let diagnostic = diagnostic(text, finding);
+                        let range = &diagnostic["range"];
+                        let overlaps = line_and_character(&range["start"]) <= end
+                            && start <= line_and_character(&range["end"]);
+                        if !overlaps {
+                            continue;
+                        }
+
+                        for suggestion in &finding.suggestions {
+                            actions.push(json!({
+                                "title": format!("Replace with \"{}\"", suggestion),
+                                "kind": "quickfix",
+                                "diagnostics": [diagnostic],
+                                "edit": {
+                                    "changes": {
+                                        uri: [{ "range": range, "newText": suggestion }],
+                                    },
+                                },
+                            }));
+                        }
+                    }
+                }
+                Value::Array(actions)
+            }
+            _ => {
+                // Requests must be answered, while unknown notifications are ignored.
+                if let Some(id) = id {
+                    let response = json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("{} is not supported", method),
+                        },
+                    });
+                    write_message(&mut output, &response)?;
+                }
+                continue;
+            }
+        };
+
+        if let Some(id) = id {
+            let response = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+            write_message(&mut output, &response)?;
+        }
+
+        if let Some((uri, text)) = changed {
+            let (uri, text) = match (uri.as_str(), text.as_str()) {
+                (Some(uri), Some(text)) => (uri.to_string(), text.to_string()),
+                _ => continue,
+            };
+            let findings = check(&text);
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": {
+                    "uri": uri,
+                    "diagnostics": findings
+                        .iter()
+                        .map(|finding| diagnostic(&text, finding))
+                        .collect::<Vec<_>>(),
+                },
+            });
+            write_message(&mut output, &notification)?;
+            documents.insert(uri, (text, findings));
+        }
+    }
+
+    Ok(())
+}
@@ -1,58 +1,443 @@
 pub mod cli;
-pub mod langs;
-pub mod lib;
+pub mod config;
+pub mod presets;
+pub mod prompt;
 
-use clap::{Command, Parser};
+use clap::{
+    error::{ContextKind, ContextValue},
+    Command, CommandFactory, Parser,
+};
 use colored::*;
-use dialoguer::{theme::ColorfulTheme, Select};
-use dirs::data_dir;
-use futures_util::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
-use reqwest::get;
+use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect, Select};
+use indicatif::{HumanBytes, ProgressBar, ProgressStyle};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde_json::{json, Value};
 use std::{
-    cmp::min,
+    cmp::max,
+    collections::{HashMap, HashSet},
     fmt::Write as _,
-    fs::{create_dir, read_dir, read_to_string, remove_file, File},
-    io::{self, BufRead, Error, Write},
+    fs::{create_dir_all, File},
+    io::{self, BufRead, BufWriter, Error, Read, Write},
+    time::Instant,
 };
+use tracing::{debug, debug_span};
+use tracing_subscriber::{filter::LevelFilter, EnvFilter};
 
-use cli::Cli;
-use langs::{LOCALES, SUPPORTED_LANGS};
-use lib::{edit_distance, insert_and_shift, yank};
+use cli::{Cli, Commands, ConfigAction, IndexAction, LangsAction, Metric, Progress};
+use config::Config;
+use didyoumean::{
+    anagram::AnagramIndex,
+    check::{self, Checker, Filter, Skip},
+    dictionary::Dictionary,
+    did_you_mean,
+    error::DymError,
+    evaluate::{self, Evaluation},
+    fetcher::{self, ProcessFetcher, ReqwestFetcher},
+    git, history,
+    index::{neighbour_graph, SuffixIndex},
+    langs::{languages, LOCALES, SUPPORTED_LANGS},
+    lsp,
+    manager::{DownloadEvent, WordlistManager},
+    pattern::{LengthIndex, Pattern},
+    phonetic::rhyme_ending,
+    pipeline::{Boost, MaxLengthDifference, Pipeline, Within},
+    plugin, rpc,
+    session::{RankedWord, SearchMode, Session},
+    similarity,
+    source::{
+        distance_histogram, find_project_wordlist, sort_by_priority, top_n_merged, two_word_splits,
+        within_merged, Source, SourceKind, Suggestion,
+    },
+    symspell::{self, SymSpell},
+    typos, wordlist, yank, Costs, MatchOptions, Ranking,
+};
+use prompt::Menu;
 
 fn main() {
     std::process::exit(match run_app() {
         Ok(_) => 0,
         Err(error) => {
-            eprintln!("Error: {:?}", error);
-            1
+            eprintln!("{} {}", "Error:".red(), error);
+            error.exit_code()
         }
     });
 }
 
-/// Main function to run the application. Return `std::result::Result<(), std::io::Error>`.
-fn run_app() -> std::result::Result<(), Error> {
+/// Main function to run the application. Return `std::result::Result<(), DymError>`.
+fn run_app() -> std::result::Result<(), DymError> {
     // Correctly output ANSI escape codes on Windows.
     #[cfg(windows)]
     colored::control::set_virtual_terminal(true).ok();
 
-    // Parse args using clap.
-    let args = Cli::parse();
+    // Parse args using clap, suggesting the closest option for unknown ones.
+    let mut args = Cli::try_parse().unwrap_or_else(|error| exit_with_suggestion(error));
 
-    // Print all supported languages.
-    if args.print_langs {
-        println!("Supported Languages:");
-        let mut langs: Vec<String> = vec![];
+    // Log to stderr as requested by RUST_LOG, for example RUST_LOG=didyoumean=debug.
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::OFF.into())
+                .from_env_lossy(),
+        )
+        .with_writer(io::stderr)
+        .init();
+
+    // Answer a download request from a sandboxed parent, before touching any files.
+    if let Some(Commands::FetchProcess { url, offset, head }) = &args.command {
+        restrict_fetch_process()?;
+        fetcher::serve(
+            &ReqwestFetcher::new(),
+            url,
+            *offset,
+            *head,
+            &mut io::stdout(),
+        )?;
+        std::process::exit(0);
+    }
+
+    // Load the configuration file.
+    let mut config = Config::load()?;
 
-        // Add words to vector.
-        for key in SUPPORTED_LANGS.keys() {
-            langs.push(format!(" - {}: {}", key, SUPPORTED_LANGS.get(key).unwrap()));
+    // Manage the word lists in the data directory.
+    let mut manager = WordlistManager::with_default_dir()
+        .ok_or_else(|| {
+            Error::new(
+                io::ErrorKind::NotFound,
+                "There is no data directory to keep word lists in",
+            )
+        })?
+        .system_dirs(config.system_dirs.clone())
+        .keep_versions(config.keep_versions);
+
+    // Download in a separate process if asked to.
+    if config.sandbox_downloads {
+        let fetcher =
+            ProcessFetcher::new(std::env::current_exe()?, vec!["fetch-worker".to_string()]);
+        manager = manager.fetcher(Box::new(fetcher));
+    }
+
+    // Run subcommands. The language server runs once the word lists are loaded.
+    let mut lsp = false;
+    match args.command.take() {
+        Some(Commands::Config { action }) => {
+            run_config(&mut config, action)?;
+            std::process::exit(0);
         }
+        Some(Commands::Fetch { langs, dry_run }) => {
+            for lang in langs {
+                if !SUPPORTED_LANGS.contains_key(lang.as_str()) {
+                    Command::new("dym fetch")
+                        .error(
+                            clap::ErrorKind::InvalidValue,
+                            format!(
+                                "There is currently no word list for {}{}",
+                                lang,
+                                suggest_lang(&lang)
+                            ),
+                        )
+                        .exit();
+                }
 
-        // Sort and print vector.
-        langs.sort();
-        for lang in langs {
-            println!("{}", lang);
+                if dry_run {
+                    report_word_list(&manager, &lang, false);
+                } else {
+                    fetch_word_list(&manager, &lang, args.quiet, args.progress, false)?;
+                }
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Langs {
+            action: LangsAction::Rollback { lang },
+        }) => {
+            if manager.rollback(&lang)? {
+                eprintln!(
+                    "{}",
+                    format!("Restored the previous {} word list", lang).green()
+                );
+            } else {
+                Command::new("dym langs rollback")
+                    .error(
+                        clap::ErrorKind::InvalidValue,
+                        format!("There is no previous version of the {} word list", lang),
+                    )
+                    .exit();
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Langs {
+            action: LangsAction::Log,
+        }) => {
+            for entry in manager.audit_log()? {
+                println!(
+                    "{} {} {} sha256:{} {}",
+                    entry.time_utc(),
+                    entry.lang,
+                    HumanBytes(entry.bytes),
+                    entry.sha256,
+                    entry.url
+                );
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Index {
+            action: IndexAction::Build { lang, max_distance },
+        }) => {
+            let words = language_words(&manager, &lang, "dym index build", &args)?;
+            let path = manager.index_path(&lang);
+            let mut writer = BufWriter::new(File::create(&path)?);
+            symspell::write_index(&words, max_distance, &mut writer)?;
+            writer.flush()?;
+            eprintln!(
+                "{}",
+                format!("Built the {} index at {}", lang, path.display()).green()
+            );
+            std::process::exit(0);
+        }
+        Some(Commands::FetchProcess { .. }) => unreachable!(),
+        Some(Commands::GenTypos { lang, count, seed }) => {
+            let lang = lang.unwrap_or_else(|| config.lang.clone());
+            let words = language_words(&manager, &lang, "dym gen-typos", &args)?;
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+
+            for (typo, word) in typos::corpus(&words, count, &mut rng) {
+                println!("{}\t{}", typo, word);
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Evaluate { corpus, lang, json }) => {
+            let lang = lang.unwrap_or_else(|| config.lang.clone());
+            let words = language_words(&manager, &lang, "dym evaluate", &args)?;
+            let corpus = evaluate::parse_corpus(&std::fs::read_to_string(corpus)?);
+            let evaluation = evaluate::evaluate(&corpus, &words);
+
+            if json {
+                println!("{}", evaluation.report());
+            } else {
+                print_evaluation(&evaluation);
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Graph {
+            lang,
+            max_distance,
+            output,
+            sample,
+            seed,
+        }) => {
+            let lang = lang.unwrap_or_else(|| config.lang.clone());
+            let words = language_words(&manager, &lang, "dym graph", &args)?;
+            let mut words: Vec<&str> = words.iter().map(String::as_str).collect();
+            if let Some(sample) = sample {
+                let mut rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(seed),
+                    None => StdRng::from_entropy(),
+                };
+                words = words.choose_multiple(&mut rng, sample).copied().collect();
+            }
+
+            let mut writer: Box<dyn Write> = match output {
+                Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+                None => Box::new(BufWriter::new(io::stdout().lock())),
+            };
+            for (word, neighbour, dist) in neighbour_graph(&words, max_distance) {
+                writeln!(writer, "{}\t{}\t{}", word, neighbour, dist)?;
+            }
+            writer.flush()?;
+            std::process::exit(0);
+        }
+        Some(Commands::Random {
+            lang,
+            len,
+            prefix,
+            count,
+            seed,
+            daily,
+        }) => {
+            let lang = lang.unwrap_or_else(|| config.lang.clone());
+            let words = language_words(&manager, &lang, "dym random", &args)?;
+            let words: Vec<&String> = words
+                .iter()
+                .filter(|word| len.is_none_or(|len| word.chars().count() == len))
+                .filter(|word| {
+                    prefix
+                        .as_ref()
+                        .is_none_or(|prefix| word.starts_with(prefix))
+                })
+                .collect();
+            if words.is_empty() {
+                Command::new("dym random")
+                    .error(
+                        clap::ErrorKind::InvalidValue,
+                        format!(
+                            "No word in the {} word list has the given length and prefix",
+                            lang
+                        ),
+                    )
+                    .exit();
+            }
+
+            let seed = match seed {
+                Some(seed) => Some(seed),
+                // Days since the epoch, so the seed changes at midnight UTC.
+                None if daily => Some(
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs()
+                        / 86400,
+                ),
+                None => None,
+            };
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            for word in words.choose_multiple(&mut rng, count) {
+                println!("{}", word);
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Pattern { pattern, lang }) => {
+            let pattern: Pattern = pattern.parse().unwrap_or_else(|error| {
+                Command::new("dym pattern")
+                    .error(clap::ErrorKind::InvalidValue, error)
+                    .exit()
+            });
+            let lang = lang.unwrap_or_else(|| config.lang.clone());
+            let words = language_words(&manager, &lang, "dym pattern", &args)?;
+            for word in LengthIndex::new(&words).find(&pattern) {
+                println!("{}", word);
+            }
+            std::process::exit(0);
+        }
+        Some(Commands::Lsp { lang }) => {
+            if !lang.is_empty() {
+                args.lang = lang;
+            }
+            lsp = true;
+        }
+        None => {}
+    }
+
+    // Offer to set things up the first time dym is used interactively.
+    if !args.print_langs
+        && !args.update_langs
+        && is_first_run(&manager)
+        && atty::is(atty::Stream::Stdin)
+        && atty::is(atty::Stream::Stdout)
+    {
+        first_run_setup(&manager, &mut config)?;
+    }
+
+    // Disable colour if the configuration asks for it.
+    if !config.colour {
+        colored::control::set_override(false);
+    }
+
+    // Fill in options that were not given on the command line from the configuration.
+    let dictionary_cmd = match &args.preset {
+        Some(name) => Some(config.preset(name).unwrap_or_else(|| {
+            Command::new("dym --preset")
+                .error(
+                    clap::ErrorKind::InvalidValue,
+                    format!(
+                        "{} is not a preset. Available presets: {}",
+                        name,
+                        config.preset_names().join(", ")
+                    ),
+                )
+                .exit()
+        })),
+        None => args.dictionary_cmd,
+    };
+    let number = args.number.unwrap_or(config.number);
+    let langs = if args.lang.is_empty() {
+        vec![config.lang.clone()]
+    } else {
+        args.lang.clone()
+    };
+    let clean_output = args.clean_output || config.clean_output;
+    let verbose = args.verbose || config.verbose;
+    // --exec and --tmux-insert replace yanking unless --yank is given too.
+    let yank_word =
+        args.yank || (args.exec.is_none() && !args.tmux_insert && (args.multiple || config.yank));
+    let auto_threshold = args.auto_threshold || config.auto_threshold;
+    let costs = [
+        args.insert_cost,
+        args.delete_cost,
+        args.substitute_cost,
+        args.transpose_cost,
+    ];
+    let ranking = match args.metric {
+        _ if costs.iter().any(Option::is_some) => Ranking::Costs(Costs {
+            insert: args.insert_cost.unwrap_or(1),
+            delete: args.delete_cost.unwrap_or(1),
+            substitute: args.substitute_cost.unwrap_or(1),
+            transpose: args.transpose_cost.unwrap_or(1),
+        }),
+        _ if args.phonetic => Ranking::Soundex,
+        _ if args.keyboard => Ranking::Keyboard,
+        Metric::EditDistance => Ranking::EditDistance,
+        Metric::JaroWinkler => Ranking::JaroWinkler,
+        Metric::Metaphone => Ranking::Metaphone,
+        Metric::Ngram => Ranking::Ngram,
+        Metric::Lcs => Ranking::Lcs,
+    };
+    let options = MatchOptions {
+        ignore_case: args.ignore_case || config.ignore_case,
+        ignore_accents: args.ignore_accents || config.ignore_accents,
+    };
+    // A replayed search uses the recorded options instead.
+    let replay = args.replay.as_deref().map(Session::load).transpose()?;
+    let (number, ranking, options, auto_threshold) = match &replay {
+        Some(session) => (
+            session.number,
+            session.ranking().unwrap_or_else(|error| {
+                Command::new("dym --replay")
+                    .error(clap::ErrorKind::InvalidValue, error)
+                    .exit()
+            }),
+            session.options(),
+            session.auto_threshold,
+        ),
+        None => (number, ranking, options, auto_threshold),
+    };
+    // As does the way it searched for words.
+    if let Some(mode) = replay.as_ref().map(|session| session.mode.clone()) {
+        args.suffix = mode.suffix;
+        args.rhyme = mode.rhyme;
+        args.anagrams = mode.anagrams.is_some();
+        args.max_differences = mode.anagrams;
+        args.complete = mode.complete;
+        args.split = mode.split;
+        args.plugin = mode.plugin;
+        config.boost_words = mode.boost_words;
+        config.max_length_difference = mode.max_length_difference;
+    }
+    // The words to leave alone when checking text. Possible values are checked by clap, so
+    // every name parses.
+    let mut skip = config.skip();
+    skip.extend(
+        args.skip
+            .iter()
+            .filter_map(|name| name.parse::<Skip>().ok()),
+    );
+    skip.retain(|kind| !args.no_skip.contains(&kind.to_string()));
+    let filter = Filter {
+        skip,
+        min_len: args.min_check_len.unwrap_or(config.min_check_len),
+    };
+
+    // Print all supported languages.
+    if args.print_langs {
+        eprintln!("Supported Languages:");
+
+        // Print the languages, sorted by locale code.
+        for lang in languages() {
+            println!(" - {}: {}", lang.code, lang.name);
         }
 
         std::process::exit(0);
@@ -60,14 +445,23 @@ fn run_app() -> std::result::Result<(), Error> {
 
     // Update all downloaded languages.
     if args.update_langs {
-        update_langs();
+        update_langs(&manager, args.quiet, args.progress, args.dry_run)?;
         std::process::exit(0);
     }
 
-    // Unwrap Option<String> or check if something was piped in as the search term.
-    let search_term = args.search_term.unwrap_or_else(|| {
+    // Unwrap Option<String> or check if something was piped in as the search term. In RPC,
+    // language server and correction modes the terms come from the input instead.
+    let serving = args.nvim_rpc || lsp || args.correct;
+    let search_term = args
+        .search_term
+        .take()
+        .or_else(|| args.suffix.clone())
+        .or_else(|| replay.as_ref().map(|session| session.term.clone()));
+    let search_term = match search_term.filter(|_| !serving) {
+        Some(search_term) => search_term,
+        None if serving => String::new(),
         // Check if stdin is empty, produce error if so.
-        if atty::is(atty::Stream::Stdin) {
+        None if atty::is(atty::Stream::Stdin) => {
             Command::new("dym [OPTIONS] <SEARCH_TERM>")
                 .error(
                     clap::ErrorKind::MissingRequiredArgument,
@@ -77,119 +471,608 @@ fn run_app() -> std::result::Result<(), Error> {
                     )
                 )
                 .exit();
-        } else {
-            // Read search_term from standard input if stdin is not empty.
+        }
+        None if args.split_input => {
+            // Every word of standard input is searched for, not only those on the first
+            // line.
             let mut search_term = String::new();
-            io::stdin().lock().read_line(&mut search_term).unwrap();
+            io::stdin().lock().read_to_string(&mut search_term)?;
             search_term
         }
-    });
+        None => {
+            // Read search_term from the first line of standard input if stdin is not
+            // empty.
+            let mut search_term = String::new();
+            io::stdin().lock().read_line(&mut search_term)?;
+            search_term
+        }
+    };
+    // Surrounding whitespace, such as the newline ending standard input, is not part of
+    // the term.
+    let search_term = search_term.trim().to_string();
+    let max_query_len = args.max_query_len.unwrap_or(config.max_query_len);
+    let terms: Vec<&str> = match args.split_input {
+        true => search_term.split_whitespace().collect(),
+        false => vec![&search_term],
+    };
+    if !serving {
+        if terms.is_empty() {
+            check_search_term(&search_term, max_query_len);
+        }
+        for term in &terms {
+            check_search_term(term, max_query_len);
+        }
+    }
 
-    if SUPPORTED_LANGS.contains_key(args.lang.as_str()) {
-        fetch_word_list(args.lang.to_owned());
+    // Get the word lists to search, downloading languages first if they are not installed.
+    let load_start = Instant::now();
+    let mut sources = vec![];
+    // The files of the word lists, read again by --translate for their translations.
+    let mut glossaries = vec![];
+    if let Some(command) = &dictionary_cmd {
+        sources.push(Source::new(
+            SourceKind::Other,
+            command,
+            wordlist::from_command(command)?,
+        ));
+    } else if args.history {
+        let path = history::history_path().unwrap_or_else(|| {
+            Command::new("dym --history")
+                .error(
+                    clap::ErrorKind::InvalidValue,
+                    "Could not find a shell history file, set $HISTFILE to point at one",
+                )
+                .exit()
+        });
+        sources.push(Source::new(
+            SourceKind::Other,
+            "history",
+            history::load(&path)?,
+        ));
+    } else if args.env {
+        let names = std::env::vars_os()
+            .map(|(name, _)| name.to_string_lossy().to_string())
+            .collect();
+        sources.push(Source::new(SourceKind::Other, "env", names));
+    } else if args.git {
+        let git_dir = git::find_git_dir(&std::env::current_dir()?).unwrap_or_else(|| {
+            Command::new("dym --git")
+                .error(
+                    clap::ErrorKind::InvalidValue,
+                    "The current directory is not inside a git repository",
+                )
+                .exit()
+        });
+        sources.push(Source::new(
+            SourceKind::Other,
+            "git",
+            git::ref_names(&git_dir)?,
+        ));
     } else {
-        // Not supported.
-        // Whether or not locale code is valid.
-        let error_string = if LOCALES.contains_key(args.lang.as_str()) {
+        // The personal and project word lists are searched alongside the languages and
+        // custom lists.
+        let personal = manager.personal_path();
+        if personal.is_file() {
+            let words = wordlist::load(&personal)?;
+            sources.push(Source::new(SourceKind::Personal, "personal", words));
+            glossaries.push(personal);
+        }
+        if let Some(path) = find_project_wordlist(&std::env::current_dir()?) {
+            let words = wordlist::load(&path)?;
+            sources.push(Source::new(SourceKind::Project, "project", words));
+            glossaries.push(path);
+        }
+        for path in &args.wordlist {
+            let name = path.display().to_string();
+            sources.push(Source::new(
+                SourceKind::Custom,
+                &name,
+                wordlist::load(path)?,
+            ));
+            glossaries.push(path.clone());
+        }
+        if args.wordlist.is_empty() {
+            for lang in &langs {
+                check_lang(lang);
+                fetch_word_list(&manager, lang, args.quiet, args.progress, false)?;
+                sources.push(Source::new(SourceKind::Language, lang, manager.load(lang)?));
+                glossaries.extend(manager.locate(lang));
+            }
+        }
+    }
+    sort_by_priority(&mut sources, &config.source_priority());
+    for source in &sources {
+        debug!(source = %source.name, words = source.words.len(), "loaded");
+    }
+    debug!(elapsed = ?load_start.elapsed(), "loaded the word lists");
+
+    if args.correct {
+        return Ok(correct_stream(&sources, options, &filter)?);
+    }
+    if args.nvim_rpc || lsp {
+        let checker = Checker::new(&sources)
+            .filter(filter)
+            .number(number)
+            .ranking(ranking, options);
+        if lsp {
+            lsp::serve(io::stdin().lock(), io::stdout().lock(), |text| {
+                checker.check_text(text)
+            })?;
+            return Ok(());
+        }
+        return Ok(serve_rpc(&checker, &sources, number, ranking, options)?);
+    }
+
+    if args.histogram {
+        let max_distance = search_term.chars().count();
+        let histogram = distance_histogram(&search_term, &sources, max_distance, options);
+        print_histogram(&histogram, args.json, clean_output);
+        return Ok(());
+    }
+
+    if args.split_input {
+        let groups: Vec<(&str, Vec<Suggestion>)> = terms
+            .iter()
+            .map(|term| {
+                let mut suggestions = match number {
+                    0 => {
+                        let max_distance = ranking.auto_threshold(term);
+                        within_merged(term, &sources, max_distance, ranking, options)
+                    }
+                    _ => top_n_merged(term, &sources, number, ranking, options),
+                };
+                if auto_threshold {
+                    let max_distance = ranking.auto_threshold(term);
+                    suggestions.retain(|suggestion| suggestion.distance <= max_distance);
+                }
+                (*term, suggestions)
+            })
+            .collect();
+        print_groups(&groups, args.json, clean_output);
+        return Ok(());
+    }
+
+    if args.progress == Progress::Json {
+        emit_progress(json!({ "event": "search-started", "term": search_term }));
+    }
+    let search_start = Instant::now();
+
+    // A plugin reranks more candidates than are shown, so it can bring up words the
+    // search alone would have left out.
+    let pool = match &args.plugin {
+        Some(_) => number.saturating_mul(PLUGIN_POOL),
+        None => number,
+    };
+
+    // Words to boost or a length limit from the configuration file put the plain search
+    // through a pipeline with those stages.
+    let custom_pipeline = !config.boost_words.is_empty() || config.max_length_difference.is_some();
+
+    // Words with the given ending, or rhyming with the search term.
+    let endings = if let Some(suffix) = &args.suffix {
+        words_ending_with(&sources, suffix, |word| {
+            word.chars().count() - suffix.chars().count()
+        })
+    } else if args.rhyme {
+        let term = search_term.to_lowercase();
+        words_ending_with(&sources, rhyme_ending(&term), |word| {
+            // Words sharing more of the term's ending rhyme better.
+            let shared = term
+                .chars()
+                .rev()
+                .zip(word.to_lowercase().chars().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            term.chars().count() - shared.min(term.chars().count())
+        })
+        .into_iter()
+        .filter(|(word, _, _)| word.to_lowercase() != term)
+        .collect()
+    } else {
+        vec![]
+    };
+
+    // The words of the full word lists the search term starts, used instead of the
+    // spelling suggestions when the term is not a word itself but starts enough of them to
+    // look abbreviated.
+    let completions = match args.complete {
+        true => words_starting_with(&sources, &search_term),
+        false => vec![],
+    };
+    let completing = completions.len() >= MIN_COMPLETIONS
+        && !sources.iter().any(|source| source.contains(&search_term));
+    if completing && !clean_output {
+        eprintln!(
+            "{}",
             format!(
-                "There is currently no word list for {}",
-                LOCALES.get(args.lang.as_str()).cloned().unwrap()
+                "\"{}\" starts {} words, showing completions",
+                search_term,
+                completions.len()
             )
-        } else {
-            format!("{} is not a recognized localed code", args.lang)
+            .yellow()
+        );
+    }
+
+    // Ways of splitting the search term into two words, ranked alongside the words.
+    let splits = match args.split {
+        true => two_word_splits(&search_term, &sources, options),
+        false => vec![],
+    };
+
+    // The word lists with the languages narrowed down to the words their indexes find,
+    // when that cannot change the suggestions of the plain ranking. Every other search
+    // needs the full word lists, which are left as they are.
+    let narrowing = ranking == Ranking::EditDistance
+        && options == MatchOptions::default()
+        && !custom_pipeline
+        && !args.anagrams
+        && args.suffix.is_none()
+        && !args.rhyme
+        && !args.complete
+        && !args.split
+        && args.record.is_none();
+    let narrowed = match narrowing {
+        true => {
+            let max_distance = ranking.auto_threshold(&search_term);
+            narrow_with_indexes(&manager, &sources, &search_term, pool, max_distance)?
+        }
+        false => None,
+    };
+    let ranked_sources = narrowed.as_deref().unwrap_or(&sources);
+
+    // The search from the word lists onwards, run twice by --check-determinism.
+    let search = || -> Result<Vec<Suggestion>, DymError> {
+        let _span = debug_span!("search", term = %search_term).entered();
+        let mut suggestions = match number {
+            _ if completing => {
+                let mut suggestions: Vec<Suggestion> = completions
+                    .iter()
+                    .map(|(word, distance, source)| Suggestion {
+                        word,
+                        distance: *distance,
+                        source,
+                    })
+                    .collect();
+                if number > 0 {
+                    suggestions.truncate(number);
+                }
+                suggestions
+            }
+            _ if args.suffix.is_some() || args.rhyme => {
+                let mut suggestions: Vec<Suggestion> = endings
+                    .iter()
+                    .map(|(word, distance, source)| Suggestion {
+                        word,
+                        distance: *distance,
+                        source,
+                    })
+                    .collect();
+                suggestions.sort_by_key(|suggestion| suggestion.distance);
+                if number > 0 {
+                    suggestions.truncate(number);
+                }
+                suggestions
+            }
+            _ if args.anagrams => {
+                let max_differences = args.max_differences.unwrap_or(0);
+                let mut suggestions =
+                    find_anagrams(&manager, &sources, &search_term, max_differences)?;
+                if number > 0 {
+                    suggestions.truncate(number);
+                }
+                suggestions
+            }
+            _ if custom_pipeline => {
+                let mut pipeline = Pipeline::new().normalize(options).scorer(ranking).n(pool);
+                if let Some(max_difference) = config.max_length_difference {
+                    pipeline = pipeline.prefilter(MaxLengthDifference(max_difference));
+                }
+                if !config.boost_words.is_empty() {
+                    pipeline = pipeline.post_process(Boost::new(&config.boost_words, 1));
+                }
+                if number == 0 {
+                    pipeline = pipeline.post_process(Within(ranking.auto_threshold(&search_term)));
+                }
+                pipeline.run(&search_term, &sources)
+            }
+            // Rank the words in the word lists against the search term. Zero asks for every
+            // word close enough to be what was meant.
+            0 => {
+                let max_distance = ranking.auto_threshold(&search_term);
+                within_merged(&search_term, ranked_sources, max_distance, ranking, options)
+            }
+            _ => top_n_merged(&search_term, ranked_sources, pool, ranking, options),
         };
 
-        // Exit with error.
-        Command::new("dym [OPTIONS] <SEARCH_TERM>")
-            .error(clap::ErrorKind::MissingRequiredArgument, error_string)
-            .exit();
+        if !splits.is_empty() && !completing {
+            let term_chars: Vec<char> = options.normalize(&search_term).chars().collect();
+            for split in &splits {
+                let distance = ranking.distance(&term_chars, &options.normalize(split));
+                if number > 0 || distance <= ranking.auto_threshold(&search_term) {
+                    suggestions.push(Suggestion {
+                        word: split,
+                        distance,
+                        source: "split",
+                    });
+                }
+            }
+            suggestions.sort_by_key(|suggestion| suggestion.distance);
+            if number > 0 {
+                suggestions.truncate(number);
+            }
+        }
+
+        if let Some(command) = &args.plugin {
+            plugin::rescore(shell(command), &search_term, &mut suggestions)?;
+            if number > 0 {
+                suggestions.truncate(number);
+            }
+        }
+
+        // Drop the suggestions too far from the search term to be what was meant.
+        if auto_threshold && !args.anagrams && args.suffix.is_none() && !args.rhyme && !completing {
+            let max_distance = ranking.auto_threshold(&search_term);
+            suggestions.retain(|suggestion| suggestion.distance <= max_distance);
+        }
+
+        Ok(suggestions)
+    };
+    let mut suggestions = search()?;
+    if args.check_determinism {
+        check_determinism(&suggestions, &search()?, clean_output);
     }
 
-    // Get word list. The program will only get here if/when this is a valid word list.
-    let word_list = read_to_string(dirs::data_dir().unwrap().join("didyoumean").join(args.lang))
-        .expect("Error reading file");
+    if args.progress == Progress::Json {
+        emit_progress(json!({
+            "event": "search-finished",
+            "term": search_term,
+            "elapsed_ms": search_start.elapsed().as_millis() as u64,
+        }));
+    }
 
-    // Get dictionary of words from words.txt.
-    let dictionary = word_list.split('\n');
+    if let Some(session) = &replay {
+        return replay_session(session, &sources, &suggestions, clean_output);
+    }
+    if let Some(path) = &args.record {
+        let session = Session::new(
+            &search_term,
+            number,
+            ranking,
+            options,
+            auto_threshold,
+            &sources,
+            &suggestions,
+        )
+        .with_mode(SearchMode {
+            suffix: args.suffix.clone(),
+            rhyme: args.rhyme,
+            anagrams: args.anagrams.then(|| args.max_differences.unwrap_or(0)),
+            complete: args.complete,
+            split: args.split,
+            plugin: args.plugin.clone(),
+            boost_words: config.boost_words.clone(),
+            max_length_difference: config.max_length_difference,
+        });
+        session.save(path)?;
+    }
 
-    // Create mutable vecs for storing the top n words.
-    let mut top_n_words = vec![""; args.number];
-    let mut top_n_dists = vec![search_term.len() * 10; args.number];
+    // Show the translations from bilingual word lists in place of the terms they pair
+    // with, the first word list to translate a term winning.
+    let mut translations = HashMap::new();
+    if args.translate {
+        for path in &glossaries {
+            for (term, translation) in wordlist::load_translations(path)? {
+                translations.entry(term).or_insert(translation);
+            }
+        }
+        if translations.is_empty() && !clean_output {
+            eprintln!(
+                "{}",
+                "None of the word lists searched has translations".yellow()
+            );
+        }
+    }
+    for suggestion in &mut suggestions {
+        if let Some(translation) = translations.get(suggestion.word) {
+            suggestion.word = translation;
+        }
+    }
 
-    // Loop over the words in the dictionary, run the algorithm, and
-    // add to the list if appropriate.
-    let search_chars = search_term.chars().collect::<Vec<_>>();
-    for word in dictionary {
-        // Get edit distance.
-        let dist = edit_distance(&search_chars, word);
+    if args.synonyms {
+        return print_synonyms(
+            &manager,
+            &langs,
+            suggestions.first(),
+            args.json,
+            clean_output,
+        );
+    }
 
-        // Add to the list if appropriate.
-        if dist < top_n_dists[args.number - 1] {
-            for i in 0..args.number {
-                if dist < top_n_dists[i] {
-                    insert_and_shift(&mut top_n_dists, i, dist);
-                    insert_and_shift(&mut top_n_words, i, word);
-                    break;
-                }
+    // Keep only the requested page, remembering where it starts for numbering.
+    let mut first = 0;
+    if let Some(page) = args.page {
+        if page == 0 || args.page_size == 0 {
+            Command::new("dym --page")
+                .error(
+                    clap::ErrorKind::InvalidValue,
+                    "Pages and page sizes count from one",
+                )
+                .exit();
+        }
+        first = (page - 1).saturating_mul(args.page_size);
+        suggestions = suggestions
+            .into_iter()
+            .skip(first)
+            .take(args.page_size)
+            .collect();
+    }
+
+    if args.json {
+        // Write the array a suggestion at a time, so long lists start arriving at once.
+        use io::Write;
+        let mut stdout = io::stdout().lock();
+        write!(stdout, "[")?;
+        for (i, suggestion) in suggestions.iter().enumerate() {
+            if i > 0 {
+                write!(stdout, ",")?;
             }
+            serde_json::to_writer(&mut stdout, suggestion).map_err(Error::from)?;
         }
+        writeln!(stdout, "]")?;
+        return Ok(());
+    }
+
+    if suggestions.is_empty() && (auto_threshold || number == 0) {
+        if !clean_output {
+            eprintln!("{}", "No close matches".red());
+        }
+        std::process::exit(1);
     }
 
     // Print out results.
-    if !args.clean_output {
-        println!("{}", "Did you mean?".blue().bold());
+    if !clean_output {
+        eprintln!("{}", "Did you mean?".blue().bold());
     }
-    let mut items = vec!["".to_string(); args.number];
-    for i in 0..args.number {
+    let mut items = vec![];
+    for (i, suggestion) in suggestions.iter().enumerate() {
         let mut output = String::new();
-        let indent = args.number.to_string().len();
+        let indent = max(number, first + suggestions.len()).to_string().len();
 
         // Add numbers if not clean.
-        if !args.clean_output {
+        if !clean_output {
             write!(
                 output,
                 "{:>indent$}{} ",
-                (i + 1).to_string().purple(),
+                (first + i + 1).to_string().purple(),
                 ".".purple()
             )
             .unwrap();
         }
 
         // Add words in order of edit distance.
-        output.push_str(top_n_words[i]);
+        output.push_str(suggestion.word);
+
+        // Add the value of the environment variable if asked to.
+        if args.show_values {
+            if let Some(value) = std::env::var_os(suggestion.word) {
+                write!(output, "={}", value.to_string_lossy()).unwrap();
+            }
+        }
 
         // Add edit distance if verbose.
-        if args.verbose {
-            write!(output, " (edit distance: {})", top_n_dists[i]).unwrap();
+        if verbose && args.anagrams {
+            write!(
+                output,
+                " (letters off: {}, from {})",
+                suggestion.distance, suggestion.source
+            )
+            .unwrap();
+        } else if verbose && (args.suffix.is_some() || args.rhyme || completing) {
+            write!(output, " (from {})", suggestion.source).unwrap();
+        } else if verbose {
+            match ranking {
+                Ranking::EditDistance | Ranking::Soundex => {
+                    write!(output, " (edit distance: {}", suggestion.distance)
+                }
+                Ranking::Metaphone | Ranking::Costs(_) | Ranking::Lcs => {
+                    write!(output, " (distance: {}", suggestion.distance)
+                }
+                Ranking::Keyboard => write!(
+                    output,
+                    " (edit distance: {:.1}",
+                    suggestion.distance as f64 / 10.0
+                ),
+                Ranking::JaroWinkler | Ranking::Ngram => write!(
+                    output,
+                    " (similarity: {:.3}",
+                    1.0 - suggestion.distance as f64 / 1000.0
+                ),
+            }
+            .unwrap();
+            // Distances mean more next to the length of the words, so give the edit
+            // distance as a share of it too.
+            if !matches!(ranking, Ranking::JaroWinkler | Ranking::Ngram) {
+                let similarity = similarity(
+                    &options.normalize(&search_term),
+                    &options.normalize(suggestion.word),
+                );
+                write!(output, ", {:.0}% similar", similarity * 100.0).unwrap();
+            }
+            write!(output, ", from {})", suggestion.source).unwrap();
         }
 
         // Print concatenated string.
-        items[i] = output;
+        items.push(output);
     }
 
-    // If the yank argument is set, copy the item to the clipboard.
-    if args.yank {
-        // Get the chosen argument with prompt.
-        let chosen = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("[↑↓ to move, ↵ to select, esc/q to cancel]")
-            .items(&items)
-            .default(0)
-            .report(false)
-            .clear(false)
-            .interact_opt()?;
-
-        match chosen {
-            // If the chosen arguemnt is valid.
-            Some(index) => {
-                yank(top_n_words[index]);
-                println!(
-                    "{}",
-                    format!("\"{}\" copied to clipboard", top_n_words[index]).green()
-                );
+    // If the yank, exec, tmux-insert or rofi argument is set, let the user choose from the
+    // items.
+    if yank_word || args.exec.is_some() || args.tmux_insert || args.rofi {
+        // Get the chosen items with prompt.
+        let prompt = match args.multiple {
+            true => &config.multiple_prompt,
+            false => &config.prompt,
+        };
+        let mut menu = Menu::new(prompt, &items)
+            .default_index(config.default_selection - 1)
+            .clear(config.clear_prompt)
+            .vim_keys(config.vim_keys);
+        if args.preview {
+            menu = menu.preview(|i| preview(&config.preview_command, suggestions[i].word));
+        }
+        let chosen = if args.rofi {
+            let words: Vec<&str> = suggestions.iter().map(|s| s.word).collect();
+            let lines = launcher_select(&config.launcher_command, &words)?;
+            Some(
+                lines
+                    .iter()
+                    .filter_map(|line| words.iter().position(|word| word == line))
+                    .collect(),
+            )
+        } else if args.multiple {
+            menu.multi_select()?
+        } else {
+            menu.select()?.map(|index| vec![index])
+        };
+
+        match chosen.filter(|indices| !indices.is_empty()) {
+            // If the chosen arguments are valid, copy them one per line and run the command
+            // on each.
+            Some(indices) => {
+                let words: Vec<&str> = indices.iter().map(|i| suggestions[*i].word).collect();
+                if yank_word {
+                    yank(&words.join("\n"))?;
+
+                    let message = match words.as_slice() {
+                        [word] => format!("\"{}\" copied to clipboard", word),
+                        _ => format!("{} words copied to clipboard", words.len()),
+                    };
+                    eprintln!("{}", message.green());
+                }
+                if args.tmux_insert {
+                    tmux_insert(&words.join(" "))?;
+                }
+                // A launcher on its own prints the choice, for scripts to use.
+                if args.rofi && !yank_word && !args.tmux_insert && args.exec.is_none() {
+                    for word in &words {
+                        println!("{}", word);
+                    }
+                }
+                if let Some(command) = &args.exec {
+                    for word in words {
+                        let command = substitute_word(command, word);
+                        let status = shell(&command).status()?;
+                        if !status.success() {
+                            return Err(
+                                Error::other(format!("`{}` failed ({})", command, status)).into()
+                            );
+                        }
+                    }
+                }
             }
             // If no argument is chosen.
             None => {
-                println!("{}", "No selection made".red());
+                eprintln!("{}", "No selection made".red());
                 std::process::exit(1);
             }
         }
@@ -203,88 +1086,1144 @@ fn run_app() -> std::result::Result<(), Error> {
     Ok(())
 }
 
-/// Fetch the word list specified by `lang` from https://github.com/hisbaan/wordlists
+/// Print the synonyms of `intended`, the word most likely meant, from the thesaurus of
+/// the first of `langs` to list any, exiting with an error if there are none.
 ///
 /// # Arguments
 ///
-/// * `lang` - A locale code string to define the word list file to fetch.
-#[tokio::main]
-async fn fetch_word_list(lang: String) {
-    // Get data directory.
-    let data_dir = dirs::data_dir().unwrap().join("didyoumean");
+/// * `manager` - The word list manager the thesauri are installed with.
+/// * `langs` - The languages searched.
+/// * `intended` - The closest suggestion, if there is one.
+/// * `json` - Whether to print JSON instead of a list.
+/// * `clean_output` - Whether to leave out the messages.
+fn print_synonyms(
+    manager: &WordlistManager,
+    langs: &[String],
+    intended: Option<&Suggestion>,
+    json: bool,
+    clean_output: bool,
+) -> Result<(), DymError> {
+    let word = match intended {
+        Some(suggestion) => suggestion.word,
+        None => {
+            if !clean_output {
+                eprintln!("{}", "No close matches".red());
+            }
+            std::process::exit(1);
+        }
+    };
+
+    let mut thesauri = vec![];
+    for lang in langs {
+        thesauri.extend(manager.load_thesaurus(lang)?);
+    }
+    if thesauri.is_empty() {
+        Command::new("dym --synonyms")
+            .error(
+                clap::ErrorKind::InvalidValue,
+                format!(
+                    "There is no thesaurus for {}, install one at {}",
+                    langs.join(", "),
+                    manager.thesaurus_path(&langs[0]).display()
+                ),
+            )
+            .exit();
+    }
+    let synonyms = thesauri
+        .iter()
+        .map(|thesaurus| thesaurus.synonyms(word))
+        .find(|synonyms| !synonyms.is_empty())
+        .unwrap_or_default();
+
+    if json {
+        println!("{}", json!({ "word": word, "synonyms": synonyms }));
+        return Ok(());
+    }
+    if synonyms.is_empty() {
+        if !clean_output {
+            eprintln!("{}", format!("No synonyms of \"{}\" found", word).red());
+        }
+        std::process::exit(1);
+    }
+
+    if !clean_output {
+        eprintln!("{}", format!("Synonyms of \"{}\":", word).blue().bold());
+    }
+    for synonym in synonyms {
+        println!("{}", synonym);
+    }
+    Ok(())
+}
+
+/// Exit with an error if `search_term` is empty or longer than `max_len` characters.
+///
+/// # Arguments
+///
+/// * `search_term` - The term to search for.
+/// * `max_len` - The longest search term allowed, or zero for no limit.
+fn check_search_term(search_term: &str, max_len: usize) {
+    let len = search_term.chars().count();
+    if len == 0 {
+        Command::new("dym [OPTIONS] <SEARCH_TERM>")
+            .error(clap::ErrorKind::InvalidValue, "The search term is empty")
+            .exit();
+    }
+    if max_len > 0 && len > max_len {
+        Command::new("dym [OPTIONS] <SEARCH_TERM>")
+            .error(
+                clap::ErrorKind::InvalidValue,
+                format!(
+                    "The search term is {} characters long, more than the limit of {}. Raise it with --max-query-len or `max-query-len` in the configuration file.",
+                    len, max_len
+                ),
+            )
+            .exit();
+    }
+}
+
+/// How many candidates a plugin is sent for each suggestion shown.
+const PLUGIN_POOL: usize = 4;
+
+/// The fewest words the search term must start for --complete to show completions.
+const MIN_COMPLETIONS: usize = 3;
+
+/// Return the words of `sources` starting with `prefix`, with how many characters they
+/// add to it and the names of their sources, shortest first. A word in several sources is
+/// returned once, from the earliest of them.
+///
+/// # Arguments
+///
+/// * `sources` - The word lists to search.
+/// * `prefix` - The start to look for.
+fn words_starting_with<'a>(sources: &'a [Source], prefix: &str) -> Vec<(&'a str, usize, &'a str)> {
+    let prefix_len = prefix.chars().count();
+    let mut seen = HashSet::new();
+    let mut found = vec![];
+    for source in sources {
+        for word in source.words().filter(|word| word.starts_with(prefix)) {
+            if seen.insert(word) {
+                let distance = word.chars().count() - prefix_len;
+                found.push((word, distance, source.name.as_str()));
+            }
+        }
+    }
+    found.sort_by_key(|(_, distance, _)| *distance);
+    found
+}
+
+/// Return the words of `sources` ending with `suffix`, with how far `rank` places them
+/// and the names of their sources, shortest first. A word in several sources is returned
+/// once, from the earliest of them.
+///
+/// # Arguments
+///
+/// * `sources` - The word lists to search.
+/// * `suffix` - The ending to look for.
+/// * `rank` - Return the distance of a word, lower ranking first.
+fn words_ending_with<'a>(
+    sources: &'a [Source],
+    suffix: &str,
+    rank: impl Fn(&str) -> usize,
+) -> Vec<(String, usize, &'a str)> {
+    let mut seen = HashSet::new();
+    let mut found = vec![];
+    for source in sources {
+        for word in SuffixIndex::new(&source.words).ending_with(suffix) {
+            if seen.insert(word.clone()) {
+                let distance = rank(&word);
+                found.push((word, distance, source.name.as_str()));
+            }
+        }
+    }
+    found
+}
+
+/// Return the words of `sources` made of the letters of `search_term` with at most
+/// `max_differences` letters added or left out, fewest differences first. Languages are
+/// searched with an anagram index in the data directory, which is built the first time and
+/// again whenever the word list changes, and other sources with one built on the spot. A
+/// word in several sources is suggested once, from the earliest of them.
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+/// * `sources` - The word lists to search.
+/// * `search_term` - The letters to find words of.
+/// * `max_differences` - The most letters a word may add or leave out.
+fn find_anagrams<'a>(
+    manager: &WordlistManager,
+    sources: &'a [Source],
+    search_term: &str,
+    max_differences: usize,
+) -> std::result::Result<Vec<Suggestion<'a>>, Error> {
+    let mut found = vec![];
+    for source in sources {
+        let index = if source.kind == SourceKind::Language {
+            let path = manager.anagram_path(&source.name);
+            match AnagramIndex::load(&path) {
+                Ok(index) if index.fingerprint() == symspell::fingerprint(&source.words) => index,
+                _ => {
+                    debug!(source = %source.name, "indexing the letters");
+                    let index = AnagramIndex::build(&source.words);
+                    // The index only saves time, so a language read from a system directory
+                    // is still searched when the data directory cannot be written.
+                    if let Err(error) =
+                        create_dir_all(manager.data_dir()).and_then(|()| index.save(&path))
+                    {
+                        debug!(source = %source.name, %error, "not saving the letter index");
+                    }
+                    index
+                }
+            }
+        } else {
+            AnagramIndex::build(&source.words)
+        };
+
+        for (i, difference) in index.find(search_term, max_differences) {
+            found.push(Suggestion {
+                word: &source.words[i],
+                distance: difference,
+                source: &source.name,
+            });
+        }
+    }
+
+    // Sources are in priority order, so the first copy of a word is the one to keep.
+    found.sort_by_key(|suggestion| suggestion.distance);
+    let mut seen = HashSet::new();
+    found.retain(|suggestion| seen.insert(suggestion.word));
+    Ok(found)
+}
+
+/// Return `sources` with each language that has a SymSpell index narrowed down by
+/// [`narrow_with_index`], or `None` if no language could be narrowed down. `sources` are
+/// left as they are.
+///
+/// # Arguments
+///
+/// * `manager` - The word list manager, which knows where the indexes are.
+/// * `sources` - The word lists to narrow down.
+/// * `search_term` - The term being searched for.
+/// * `number` - The number of suggestions asked for, zero for every close word.
+/// * `max_distance` - The distance words must be within when `number` is zero.
+fn narrow_with_indexes(
+    manager: &WordlistManager,
+    sources: &[Source],
+    search_term: &str,
+    number: usize,
+    max_distance: usize,
+) -> std::result::Result<Option<Vec<Source>>, Error> {
+    let mut narrowed = vec![];
+    let mut any = false;
+    for source in sources {
+        let path = manager.index_path(&source.name);
+        let words = match source.kind == SourceKind::Language && path.is_file() {
+            true => narrow_with_index(source, &path, search_term, number, max_distance)?,
+            false => None,
+        };
+        any |= words.is_some();
+        narrowed.push(match words {
+            Some(words) => Source::new(source.kind, &source.name, words),
+            None => source.clone(),
+        });
+    }
+    Ok(any.then_some(narrowed))
+}
 
-    // Create data directory if it doesn't exist.
-    if !data_dir.is_dir() {
-        create_dir(data_dir).expect("Failed to create data directory");
+/// Return the words of `source` that its SymSpell index at `path` finds close to
+/// `search_term`, if the search gives the same suggestions over those words as over all of
+/// them. That is when `number` words are within the index's distance, or when every word
+/// within `max_distance` is asked for and the index reaches that far. An index built from
+/// another version of the word list is ignored.
+///
+/// # Arguments
+///
+/// * `source` - The language to narrow down.
+/// * `path` - The path of the index of the language.
+/// * `search_term` - The term being searched for.
+/// * `number` - The number of suggestions asked for, zero for every close word.
+/// * `max_distance` - The distance words must be within when `number` is zero.
+fn narrow_with_index(
+    source: &Source,
+    path: &std::path::Path,
+    search_term: &str,
+    number: usize,
+    max_distance: usize,
+) -> std::result::Result<Option<Vec<String>>, Error> {
+    let start = Instant::now();
+    let mut index = SymSpell::open(File::open(path)?)?;
+    if index.word_count() != source.words.len()
+        || index.fingerprint() != symspell::fingerprint(&source.words)
+    {
+        debug!(source = %source.name, "ignoring the stale index");
+        return Ok(None);
+    }
+
+    let k = match number {
+        0 if max_distance <= index.max_distance() => max_distance,
+        0 => return Ok(None),
+        _ => index.max_distance(),
+    };
+    let found = index.find_within(search_term, &source.words, k)?;
+    if number > 0 && found.len() < number {
+        return Ok(None);
     }
 
-    // Get file path.
-    let file_path = dirs::data_dir().unwrap().join("didyoumean").join(&lang);
+    let words: Vec<String> = found
+        .into_iter()
+        .map(|(i, _)| source.words[i].clone())
+        .collect();
+    debug!(
+        source = %source.name,
+        words = words.len(),
+        elapsed = ?start.elapsed(),
+        "narrowed down with the index"
+    );
+    Ok(Some(words))
+}
+
+/// Return what `command` prints with `{}` replaced by `word`, or why it failed.
+///
+/// # Arguments
+///
+/// * `command` - The shell command to run.
+/// * `word` - The word to substitute into the command.
+fn preview(command: &str, word: &str) -> String {
+    let command = substitute_word(command, word);
+    match shell(&command).output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).into(),
+        Ok(output) => String::from_utf8_lossy(&output.stderr).into(),
+        Err(error) => format!("`{}` failed: {}", command, error),
+    }
+}
 
-    // If the file does not exist, fetch it from the server.
-    if !file_path.is_file() {
+/// Print `histogram`, the number of words at each edit distance followed by the number
+/// further away, as a bar chart or as JSON.
+///
+/// # Arguments
+///
+/// * `histogram` - The counts from [`distance_histogram`].
+/// * `json` - Whether to print JSON instead of a chart.
+/// * `clean_output` - Whether to leave out the title.
+fn print_histogram(histogram: &[usize], json: bool, clean_output: bool) {
+    let (further, counts) = histogram.split_last().unwrap();
+    if json {
+        println!("{}", json!({ "counts": counts, "further": further }));
+        return;
+    }
+
+    // Scale the bars so the longest fills forty columns.
+    let most = histogram.iter().copied().max().unwrap_or(0).max(1);
+    let labels: Vec<String> = (0..counts.len())
+        .map(|dist| dist.to_string())
+        .chain([format!(">{}", counts.len() - 1)])
+        .collect();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let count_width = most.to_string().len();
+
+    if !clean_output {
+        eprintln!("{}", "Words at each edit distance:".blue().bold());
+    }
+    for (label, count) in labels.iter().zip(histogram) {
+        let bar = "█".repeat((count * 40).div_ceil(most));
         println!(
-            "Downloading {} word list...",
-            LOCALES.get(&lang).unwrap().to_string().blue()
+            "{:>label_width$} {:>count_width$} {}",
+            label.purple(),
+            count,
+            bar.green()
         );
+    }
+}
 
-        let url = format!(
-            "https://raw.githubusercontent.com/hisbaan/wordlists/main/{}",
-            &lang
+/// Compare the ranking of a replayed search with the one recorded in `session`, naming the
+/// word lists that changed since, and exit with an error if they differ.
+///
+/// # Arguments
+///
+/// * `session` - The recorded search.
+/// * `sources` - The word lists searched now.
+/// * `suggestions` - The suggestions found now.
+/// * `clean_output` - Whether to leave out the headings.
+fn replay_session(
+    session: &Session,
+    sources: &[Source],
+    suggestions: &[Suggestion],
+    clean_output: bool,
+) -> Result<(), DymError> {
+    for name in session.changed_sources(sources) {
+        eprintln!(
+            "{}",
+            format!("The {} word list differs from the recording", name).yellow()
         );
+    }
+    if session.dym_version != env!("CARGO_PKG_VERSION") {
+        eprintln!(
+            "{}",
+            format!(
+                "Recorded with dym {}, replaying with {}",
+                session.dym_version,
+                env!("CARGO_PKG_VERSION")
+            )
+            .yellow()
+        );
+    }
 
-        // Setup reqwest.
-        let response = get(&url).await.expect("Request failed");
-        let total_size = response.content_length().unwrap();
-        let mut file = File::create(file_path).expect("Failed to create file");
-        let mut downloaded: u64 = 0;
-        let mut stream = response.bytes_stream();
-
-        // Setup indicatif.
-        let pb = ProgressBar::new(total_size);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template(
-                    "[{elapsed_precise}] [{wide_bar:.blue/cyan}] {bytes}/{total_bytes} ({eta})",
-                )
-                .progress_chars("#>-"),
+    let now: Vec<RankedWord> = suggestions.iter().map(RankedWord::from).collect();
+    if now == session.suggestions {
+        if !clean_output {
+            eprintln!("{}", "The ranking is unchanged".green());
+        }
+        return Ok(());
+    }
+
+    if !clean_output {
+        eprintln!("{}", "The ranking changed (recorded | now):".red().bold());
+    }
+    print_side_by_side(&session.suggestions, &now);
+    std::process::exit(1);
+}
+
+/// Compare the suggestions of two runs of the same search, and exit with an error showing
+/// both if they differ.
+///
+/// # Arguments
+///
+/// * `first` - The suggestions of the first run.
+/// * `second` - The suggestions of the second run.
+/// * `clean_output` - Whether to leave out the messages.
+fn check_determinism(first: &[Suggestion], second: &[Suggestion], clean_output: bool) {
+    let first: Vec<RankedWord> = first.iter().map(RankedWord::from).collect();
+    let second: Vec<RankedWord> = second.iter().map(RankedWord::from).collect();
+    if first == second {
+        if !clean_output {
+            eprintln!("{}", "The ranking is the same on both runs".green());
+        }
+        return;
+    }
+
+    if !clean_output {
+        eprintln!(
+            "{}",
+            "The ranking differs between runs (first | second):"
+                .red()
+                .bold()
+        );
+    }
+    print_side_by_side(&first, &second);
+    std::process::exit(1);
+}
+
+/// Print two rankings side by side, marking the rows that differ.
+///
+/// # Arguments
+///
+/// * `left` - The ranking on the left.
+/// * `right` - The ranking on the right.
+fn print_side_by_side(left: &[RankedWord], right: &[RankedWord]) {
+    let describe = |ranked: Option<&RankedWord>| {
+        ranked.map_or("-".to_string(), |ranked| {
+            format!("{} ({}, {})", ranked.word, ranked.distance, ranked.source)
+        })
+    };
+    let rows = max(left.len(), right.len());
+    let width = (0..rows)
+        .map(|i| describe(left.get(i)).chars().count())
+        .max()
+        .unwrap_or(0);
+    for i in 0..rows {
+        let (left, right) = (left.get(i), right.get(i));
+        let marker = if left == right { " " } else { "*" };
+        println!(
+            "{} {:>2}. {:<width$} | {}",
+            marker,
+            i + 1,
+            describe(left),
+            describe(right)
         );
+    }
+}
+
+/// Print the suggestions for each of several search terms, under a line naming the term.
+///
+/// # Arguments
+///
+/// * `groups` - Each search term with its suggestions.
+/// * `json` - Whether to print JSON instead of lists.
+/// * `clean_output` - Whether to leave out the numbering and messages.
+fn print_groups(groups: &[(&str, Vec<Suggestion>)], json: bool, clean_output: bool) {
+    if json {
+        let groups: Vec<Value> = groups
+            .iter()
+            .map(|(term, suggestions)| json!({ "term": term, "suggestions": suggestions }))
+            .collect();
+        println!("{}", Value::Array(groups));
+        return;
+    }
+
+    for (i, (term, suggestions)) in groups.iter().enumerate() {
+        if i > 0 && !clean_output {
+            println!();
+        }
+        match clean_output {
+            true => println!("{}:", term),
+            false => println!("{}", format!("{}:", term).blue().bold()),
+        }
+        if suggestions.is_empty() && !clean_output {
+            println!("  {}", "No close matches".red());
+        }
+        let indent = suggestions.len().to_string().len();
+        for (j, suggestion) in suggestions.iter().enumerate() {
+            match clean_output {
+                true => println!("  {}", suggestion.word),
+                false => println!(
+                    "  {:>indent$}{} {}",
+                    (j + 1).to_string().purple(),
+                    ".".purple(),
+                    suggestion.word
+                ),
+            }
+        }
+    }
+}
+
+/// Answer MessagePack-RPC requests on standard input with suggestions from `sources`,
+/// until standard input closes.
+///
+/// # Arguments
+///
+/// * `checker` - Checks the lines of check_buffer requests.
+/// * `sources` - The word lists to suggest from.
+/// * `number` - The number of suggestions to return by default.
+/// * `ranking` - How to rank the words.
+/// * `options` - Which differences between words to ignore.
+fn serve_rpc(
+    checker: &Checker,
+    sources: &[Source],
+    number: usize,
+    ranking: Ranking,
+    options: MatchOptions,
+) -> std::result::Result<(), Error> {
+    let suggest = |term: &str, n: usize| -> rpc::Value {
+        let suggestions = top_n_merged(term, sources, n, ranking, options);
+        rpc::Value::Array(suggestions.iter().map(|s| s.word.into()).collect())
+    };
 
-        // Read from stream into file.
-        while let Some(item) = stream.next().await {
-            let chunk = item.expect("Error downloading file");
-            file.write_all(&chunk).expect("Error while writing to file");
-            let new = min(downloaded + (chunk.len() as u64), total_size);
-            downloaded = new;
-            pb.set_position(new);
+    rpc::serve(
+        io::stdin().lock(),
+        io::stdout().lock(),
+        |method, params| match method {
+            "suggest" => {
+                let term = params.first().and_then(rpc::Value::as_str);
+                let term = term.ok_or("suggest takes a term")?;
+                let n = params.get(1).and_then(rpc::Value::as_int);
+                Ok(suggest(term, n.map_or(number, |n| n.max(0) as usize)))
+            }
+            "check_buffer" => {
+                let lines = params.first().and_then(rpc::Value::as_array);
+                let lines = lines.ok_or("check_buffer takes a list of lines")?;
+
+                let mut findings = vec![];
+                for (lnum, line) in lines.iter().enumerate() {
+                    let line = line.as_str().unwrap_or_default();
+                    for finding in checker.check_text(line) {
+                        let suggestions = finding.suggestions.iter().map(|s| s.as_str().into());
+                        findings.push(rpc::Value::Map(vec![
+                            ("lnum".into(), lnum.into()),
+                            ("col".into(), finding.span.start.into()),
+                            ("end_col".into(), finding.span.end.into()),
+                            ("word".into(), finding.word.as_str().into()),
+                            (
+                                "suggestions".into(),
+                                rpc::Value::Array(suggestions.collect()),
+                            ),
+                        ]));
+                    }
+                }
+                Ok(rpc::Value::Array(findings))
+            }
+            _ => Err(format!("{} is not a method", method)),
+        },
+    )
+}
+
+/// Copy standard input to standard output, replacing every unknown word that has exactly
+/// one word in `sources` one edit away with that word.
+///
+/// # Arguments
+///
+/// * `sources` - The word lists to correct with.
+/// * `options` - Which differences between words to ignore.
+/// * `filter` - Which words to correct.
+fn correct_stream(
+    sources: &[Source],
+    options: MatchOptions,
+    filter: &Filter,
+) -> std::result::Result<(), Error> {
+    let checker = Checker::new(sources);
+    // Text repeats words, so remember what each one was corrected to.
+    let mut corrections: HashMap<String, Option<String>> = HashMap::new();
+    let mut correct = |word: &str| -> Option<String> {
+        if checker.is_known(word) {
+            return None;
         }
+        corrections
+            .entry(word.to_string())
+            .or_insert_with(|| {
+                // Each word is suggested once, so a second close one is a different word.
+                let close: Vec<&str> =
+                    top_n_merged(word, sources, 2, Ranking::EditDistance, options)
+                        .iter()
+                        .filter(|suggestion| suggestion.distance <= 1)
+                        .map(|suggestion| suggestion.word)
+                        .collect();
+                match close[..] {
+                    [word] => Some(word.to_string()),
+                    _ => None,
+                }
+            })
+            .clone()
+    };
 
-        // Print completed bar.
-        pb.finish_at_current_pos();
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+    let mut line = String::new();
+    while stdin.read_line(&mut line)? > 0 {
+        io::Write::write_all(
+            &mut stdout,
+            check::correct(&line, filter, &mut correct).as_bytes(),
+        )?;
+        // Flush each line, so dym can sit in a pipeline that is still being written to.
+        io::Write::flush(&mut stdout)?;
+        line.clear();
     }
+
+    Ok(())
 }
 
-/// Update the word list files by deleting and downloading the files from the repository.
-fn update_langs() {
-    let data = data_dir().unwrap().join("didyoumean");
+/// Let the user choose from `words` with the launcher `command`, such as `rofi -dmenu`,
+/// returning the lines it printed. Launchers exit with an error when cancelled, so that
+/// chooses nothing.
+///
+/// # Arguments
+///
+/// * `command` - The shell command running the launcher.
+/// * `words` - The words to choose from.
+fn launcher_select(command: &str, words: &[&str]) -> std::result::Result<Vec<String>, Error> {
+    let mut launcher = shell(command)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
 
-    // Create data directory if it doesn't exist.
-    if !data.is_dir() {
-        create_dir(&data).expect("Failed to create data directory");
+    // Dropping stdin closes it, so the launcher knows the list is complete.
+    if let Some(mut stdin) = launcher.stdin.take() {
+        io::Write::write_all(&mut stdin, words.join("\n").as_bytes())?;
+    }
+    let output = launcher.wait_with_output()?;
+    if !output.status.success() {
+        return Ok(vec![]);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Type `text` into the current tmux pane, as if the user had typed it.
+///
+/// # Arguments
+///
+/// * `text` - The text to type.
+fn tmux_insert(text: &str) -> std::result::Result<(), Error> {
+    if std::env::var_os("TMUX").is_none() {
+        return Err(Error::other("--tmux-insert only works inside tmux"));
+    }
+
+    // -l sends the text literally rather than as key names.
+    let status = std::process::Command::new("tmux")
+        .args(["send-keys", "-l", "--", text])
+        .status()?;
+    if !status.success() {
+        return Err(Error::other(format!("tmux send-keys failed ({})", status)));
+    }
+
+    Ok(())
+}
+
+/// Return a process running `command` through the system shell.
+///
+/// # Arguments
+///
+/// * `command` - The shell command to run.
+fn shell(command: &str) -> std::process::Command {
+    #[cfg(windows)]
+    let (shell, flag) = ("cmd", "/C");
+    #[cfg(not(windows))]
+    let (shell, flag) = ("sh", "-c");
+
+    let mut process = std::process::Command::new(shell);
+    process.args([flag, command]);
+    process
+}
+
+/// Replace every `{}` in `command` with `word`, quoted for the system shell.
+///
+/// # Arguments
+///
+/// * `command` - The shell command to substitute into.
+/// * `word` - The word to substitute.
+fn substitute_word(command: &str, word: &str) -> String {
+    #[cfg(windows)]
+    let quoted = format!("\"{}\"", word.replace('"', "\"\""));
+    #[cfg(not(windows))]
+    let quoted = format!("'{}'", word.replace('\'', "'\\''"));
+
+    command.replace("{}", &quoted)
+}
+
+/// Reduce what the download process can do before it touches the network: it leaves the
+/// user's directories and, on Linux, gives up gaining privileges through setuid binaries.
+fn restrict_fetch_process() -> std::result::Result<(), Error> {
+    std::env::set_current_dir(std::path::MAIN_SEPARATOR.to_string())?;
+
+    #[cfg(target_os = "linux")]
+    if unsafe { nix::libc::prctl(nix::libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+        return Err(Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Exit with `error`, replacing clap's message for unknown options with one that suggests
+/// the closest known option.
+///
+/// # Arguments
+///
+/// * `error` - The error clap returned while parsing the arguments.
+fn exit_with_suggestion(error: clap::Error) -> ! {
+    if error.kind() != clap::ErrorKind::UnknownArgument {
+        error.exit();
     }
 
-    // Get files in data directory.
-    let data_dir_files = read_dir(&data).unwrap();
+    // Get the unknown option without any value attached to it.
+    let arg = match error
+        .context()
+        .find(|(kind, _)| *kind == ContextKind::InvalidArg)
+    {
+        Some((_, ContextValue::String(arg))) => arg.to_owned(),
+        _ => error.exit(),
+    };
+    let flag = arg.split('=').next().unwrap().trim_start_matches('-');
 
-    // Delete and update all files.
-    for file in data_dir_files {
-        let file_name = file.unwrap().file_name();
-        let string: &str = file_name.to_str().unwrap();
+    let mut cmd = Cli::command();
+    cmd.set_bin_name("dym");
+    let longs: Vec<&str> = cmd.get_arguments().filter_map(|a| a.get_long()).collect();
+    let suggestion = did_you_mean(flag, longs, max(2, flag.len() / 3));
 
-        // Only delete and download if the language is supported.
-        if SUPPORTED_LANGS.contains_key(string) {
-            remove_file(data.join(&string)).expect("Failed to update file (deletion failed)");
-            fetch_word_list(string.to_string());
+    let mut message = format!(
+        "Found argument '{}' which wasn't expected, or isn't valid in this context",
+        arg.yellow()
+    );
+    if let Some(long) = suggestion {
+        write!(
+            message,
+            "\n\n\tDid you mean {}?",
+            format!("--{}", long).green()
+        )
+        .unwrap();
+    }
+
+    cmd.error(clap::ErrorKind::UnknownArgument, message).exit()
+}
+
+/// Print a human readable summary of `evaluation`.
+///
+/// # Arguments
+///
+/// * `evaluation` - The result of evaluating a corpus.
+fn print_evaluation(evaluation: &Evaluation) {
+    let overall = &evaluation.overall;
+    println!("cases: {}", overall.cases);
+    println!("top-1 accuracy: {:.1}%", overall.top_1_accuracy() * 100.0);
+    println!("top-5 accuracy: {:.1}%", overall.top_5_accuracy() * 100.0);
+    println!(
+        "mean reciprocal rank: {:.3}",
+        evaluation.mean_reciprocal_rank()
+    );
+    println!(
+        "latency: {:?} mean, {:?} p95",
+        evaluation.mean_latency(),
+        evaluation.latency_percentile(95)
+    );
+
+    for (dist, bucket) in &evaluation.buckets {
+        println!(
+            " - edit distance {}: {} cases, {:.1}% top-1, {:.1}% top-5",
+            dist,
+            bucket.cases,
+            bucket.top_1_accuracy() * 100.0,
+            bucket.top_5_accuracy() * 100.0
+        );
+    }
+}
+
+/// Exit with an error if there is no word list for `lang`, suggesting the closest one.
+///
+/// # Arguments
+///
+/// * `lang` - The locale code given with --lang.
+fn check_lang(lang: &str) {
+    if SUPPORTED_LANGS.contains_key(lang) {
+        return;
+    }
+
+    // Whether or not locale code is valid.
+    let error_string = if LOCALES.contains_key(lang) {
+        format!(
+            "There is currently no word list for {}",
+            LOCALES.get(lang).cloned().unwrap()
+        )
+    } else {
+        format!(
+            "{} is not a recognized localed code{}",
+            lang,
+            suggest_lang(lang)
+        )
+    };
+
+    // Exit with error.
+    Command::new("dym [OPTIONS] <SEARCH_TERM>")
+        .error(clap::ErrorKind::MissingRequiredArgument, error_string)
+        .exit();
+}
+
+/// Return the words of the word list for `lang`, downloading it first if needed, or exit
+/// with an error naming `command` if there is no word list for `lang`.
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+/// * `lang` - The locale code of the word list.
+/// * `command` - The command to name in the error message.
+/// * `args` - The command line arguments, for the progress reporting options.
+fn language_words(
+    manager: &WordlistManager,
+    lang: &str,
+    command: &str,
+    args: &Cli,
+) -> Result<Vec<String>, DymError> {
+    if !SUPPORTED_LANGS.contains_key(lang) {
+        Command::new(command)
+            .error(
+                clap::ErrorKind::InvalidValue,
+                format!(
+                    "There is currently no word list for {}{}",
+                    lang,
+                    suggest_lang(lang)
+                ),
+            )
+            .exit();
+    }
+
+    fetch_word_list(manager, lang, args.quiet, args.progress, false)?;
+    manager.load(lang)
+}
+
+/// Return a sentence suggesting the supported language closest to `lang`, matching either
+/// locale codes or language names, or an empty string if none is close.
+///
+/// # Arguments
+///
+/// * `lang` - The locale code or language name that was not recognized.
+fn suggest_lang(lang: &str) -> String {
+    let lowercase = lang.to_lowercase();
+
+    // Match language names first, so `--lang french` suggests `fr`.
+    let names: Vec<(String, &str)> = languages()
+        .map(|lang| (lang.name.to_lowercase(), lang.code))
+        .collect();
+    let by_name = did_you_mean(
+        &lowercase,
+        names.iter().map(|(name, _)| name.as_str()),
+        lowercase.len() / 3,
+    )
+    .and_then(|name| names.iter().find(|(n, _)| n == name))
+    .map(|(_, code)| *code);
+
+    let codes = languages().map(|lang| lang.code);
+
+    match by_name.or_else(|| did_you_mean(&lowercase, codes, 1)) {
+        Some(code) => format!(". Did you mean {}?", code.green()),
+        None => String::new(),
+    }
+}
+
+/// Run the config subcommand `action` against `config`.
+///
+/// # Arguments
+///
+/// * `config` - The loaded configuration.
+/// * `action` - The config subcommand to run.
+fn run_config(config: &mut Config, action: ConfigAction) -> std::result::Result<(), Error> {
+    match action {
+        ConfigAction::Get { key } => match config.get(&key) {
+            Ok(value) => println!("{}", value),
+            Err(error) => Command::new("dym config get")
+                .error(clap::ErrorKind::InvalidValue, error)
+                .exit(),
+        },
+        ConfigAction::Set { key, value } => {
+            if let Err(error) = config.set(&key, &value) {
+                Command::new("dym config set")
+                    .error(clap::ErrorKind::InvalidValue, error)
+                    .exit();
+            }
+            config.save()?;
+        }
+        ConfigAction::List => {
+            for (key, value) in config.entries() {
+                println!("{} = {}", key, value);
+            }
         }
+        ConfigAction::Path => println!("{}", Config::path().display()),
     }
+
+    Ok(())
+}
+
+/// Return whether dym has neither a configuration file nor a data directory yet.
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+fn is_first_run(manager: &WordlistManager) -> bool {
+    !Config::exists() && !manager.data_dir().is_dir()
+}
+
+/// Interactively choose the languages to download and the defaults to use, then
+/// write them to the configuration file.
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+/// * `config` - The configuration to fill in.
+fn first_run_setup(
+    manager: &WordlistManager,
+    config: &mut Config,
+) -> std::result::Result<(), DymError> {
+    eprintln!(
+        "{}",
+        "Welcome to Did You Mean! Let's get you set up."
+            .blue()
+            .bold()
+    );
+
+    // Choose the word lists to download.
+    let codes: Vec<&str> = languages().map(|lang| lang.code).collect();
+    let items: Vec<String> = languages()
+        .map(|lang| format!("{} - {} ({})", lang.name, lang.native_name, lang.code))
+        .collect();
+    let defaults: Vec<bool> = codes.iter().map(|code| *code == config.lang).collect();
+    let mut chosen = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Word lists to download [space to toggle, ↵ to confirm]")
+        .items(&items)
+        .defaults(&defaults)
+        .interact()?;
+    if chosen.is_empty() {
+        chosen.push(codes.iter().position(|code| *code == config.lang).unwrap());
+    }
+
+    // Choose the default language among the downloaded ones.
+    let default = if chosen.len() == 1 {
+        chosen[0]
+    } else {
+        let chosen_items: Vec<&String> = chosen.iter().map(|i| &items[*i]).collect();
+        chosen[Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Default language")
+            .items(&chosen_items)
+            .default(0)
+            .interact()?]
+    };
+    config.lang = codes[default].to_string();
+
+    // Choose the yank and colour behaviour.
+    config.yank = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Always choose a word to copy to the clipboard?")
+        .default(config.yank)
+        .interact()?;
+    config.colour = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Use colour in the output?")
+        .default(config.colour)
+        .interact()?;
+
+    config.save()?;
+    eprintln!("Configuration written to {}", Config::path().display());
+
+    // Download the chosen word lists.
+    for i in chosen {
+        fetch_word_list(manager, codes[i], false, Progress::Bar, false)?;
+    }
+
+    Ok(())
+}
+
+/// Fetch the word list specified by `lang` from https://github.com/hisbaan/wordlists
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+/// * `lang` - A locale code string to define the word list file to fetch.
+/// * `quiet` - Whether to hide the download message and progress bar.
+/// * `progress` - How to report the progress of the download.
+/// * `update` - Whether to download the word list again if it is already installed.
+fn fetch_word_list(
+    manager: &WordlistManager,
+    lang: &str,
+    quiet: bool,
+    progress: Progress,
+    update: bool,
+) -> std::result::Result<(), DymError> {
+    if !update && manager.is_installed(lang) {
+        return Ok(());
+    }
+
+    let mut pb = ProgressBar::hidden();
+    let mut on_progress = |event: DownloadEvent| match event {
+        DownloadEvent::Started { lang, total_bytes } => match progress {
+            Progress::Json => emit_progress(json!({
+                "event": "download-started",
+                "lang": lang,
+                "total_bytes": total_bytes,
+            })),
+            Progress::Bar if !quiet => {
+                eprintln!(
+                    "Downloading {} word list...",
+                    LOCALES.get(lang).unwrap().to_string().blue()
+                );
+
+                // Setup indicatif.
+                pb = ProgressBar::new(total_bytes.unwrap_or(0));
+                pb.set_style(
+                    ProgressStyle::default_bar()
+                        .template(
+                            "[{elapsed_precise}] [{wide_bar:.blue/cyan}] {bytes}/{total_bytes} ({eta})",
+                        )
+                        .progress_chars("#>-"),
+                );
+            }
+            Progress::Bar => {}
+        },
+        DownloadEvent::Progress {
+            lang,
+            bytes,
+            total_bytes,
+        } => {
+            pb.set_position(bytes);
+
+            if progress == Progress::Json {
+                emit_progress(json!({
+                    "event": "download-progress",
+                    "lang": lang,
+                    "bytes": bytes,
+                    "total_bytes": total_bytes,
+                }));
+            }
+        }
+        DownloadEvent::Finished { lang, bytes } => {
+            // Print completed bar.
+            pb.finish_at_current_pos();
+
+            if progress == Progress::Json {
+                emit_progress(json!({
+                    "event": "download-finished",
+                    "lang": lang,
+                    "bytes": bytes,
+                }));
+            }
+        }
+    };
+
+    if update {
+        manager.update(lang, &mut on_progress)
+    } else {
+        manager.fetch(lang, &mut on_progress)
+    }
+}
+
+/// Print what fetching the word list specified by `lang` would do, without writing anything.
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+/// * `lang` - A locale code string to define the word list file to report on.
+/// * `replace` - Whether an installed copy would be replaced by a new download.
+fn report_word_list(manager: &WordlistManager, lang: &str, replace: bool) {
+    if let Some(file_path) = manager.locate(lang) {
+        if !replace {
+            println!("{} is already installed at {}", lang, file_path.display());
+            return;
+        }
+
+        let local_size = file_path.metadata().map(|m| m.len()).unwrap_or(0);
+        println!(
+            "Would replace {} ({})",
+            file_path.display(),
+            HumanBytes(local_size)
+        );
+    }
+
+    // Only ask the server for the size, the body is never downloaded.
+    let remote_size = manager
+        .remote_size(lang)
+        .map_or("unknown size".to_string(), |size| {
+            HumanBytes(size).to_string()
+        });
+    println!("Would download {} ({})", manager.url(lang), remote_size);
+}
+
+/// Update the word list files by downloading them again from the repository.
+///
+/// # Arguments
+///
+/// * `manager` - The manager of the installed word lists.
+/// * `quiet` - Whether to hide the download messages and progress bars.
+/// * `progress` - How to report the progress of the downloads.
+/// * `dry_run` - Whether to only print what would be replaced and downloaded.
+fn update_langs(
+    manager: &WordlistManager,
+    quiet: bool,
+    progress: Progress,
+    dry_run: bool,
+) -> std::result::Result<(), DymError> {
+    for lang in manager.installed()? {
+        // Word lists provided by the system are updated by its package manager.
+        if manager.is_read_only(&lang) {
+            if !quiet {
+                eprintln!(
+                    "Skipping {}, provided read-only by {}",
+                    lang,
+                    manager.locate(&lang).unwrap().display()
+                );
+            }
+            continue;
+        }
+
+        if dry_run {
+            report_word_list(manager, &lang, true);
+        } else {
+            fetch_word_list(manager, &lang, quiet, progress, true)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print a progress event as a single line of JSON on stderr.
+///
+/// # Arguments
+///
+/// * `event` - The event to print.
+fn emit_progress(event: Value) {
+    eprintln!("{}", event);
 }
@@ -0,0 +1,469 @@
+use std::{
+    fs::{create_dir_all, read, read_dir, remove_file, rename, File, OpenOptions},
+    io::{Error, ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    audit::{self, AuditEntry},
+    error::DymError,
+    fetcher::Fetcher,
+    langs::SUPPORTED_LANGS,
+    sha256,
+    thesaurus::Thesaurus,
+    versions, wordlist,
+};
+
+/// The base URL word lists are downloaded from.
+const WORDLISTS_URL: &str = "https://raw.githubusercontent.com/hisbaan/wordlists/main";
+
+/// The directories distribution packages install word lists in, searched read-only
+/// before the per-user data directory.
+#[cfg(unix)]
+pub const SYSTEM_DIRS: &[&str] = &["/usr/local/share/didyoumean", "/usr/share/didyoumean"];
+/// The directories distribution packages install word lists in, searched read-only
+/// before the per-user data directory.
+#[cfg(not(unix))]
+pub const SYSTEM_DIRS: &[&str] = &[];
+
+/// Progress of a word list download, reported to the callback given to
+/// [`WordlistManager::fetch`] and [`WordlistManager::update`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadEvent<'a> {
+    /// The download of `lang` started. `total_bytes` is `None` if the server did not say.
+    Started {
+        lang: &'a str,
+        total_bytes: Option<u64>,
+    },
+    /// `bytes` of the word list for `lang` have been downloaded so far.
+    Progress {
+        lang: &'a str,
+        bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    /// The word list for `lang` was downloaded and installed.
+    Finished { lang: &'a str, bytes: u64 },
+}
+
+/// Manages the word lists installed in a data directory: downloading, updating, rolling
+/// back and loading them. Word lists can also be provided by read-only system
+/// directories, which are searched first and never written to.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::manager::WordlistManager;
+/// let manager = WordlistManager::new(std::env::temp_dir().join("didyoumean_doc"));
+/// assert!(manager.path("en").ends_with("en"));
+/// ```
+pub struct WordlistManager {
+    data_dir: PathBuf,
+    system_dirs: Vec<PathBuf>,
+    keep_versions: usize,
+    fetcher: Box<dyn Fetcher>,
+}
+
+impl WordlistManager {
+    /// Create a manager for the word lists stored in `data_dir`, keeping one previous
+    /// version of each word list on update and downloading with a
+    /// [`ReqwestFetcher`](crate::fetcher::ReqwestFetcher), or an
+    /// [`OfflineFetcher`](crate::fetcher::OfflineFetcher) without the `network` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_dir` - The directory to store word lists in.
+    pub fn new(data_dir: PathBuf) -> WordlistManager {
+        WordlistManager {
+            data_dir,
+            system_dirs: vec![],
+            keep_versions: 1,
+            #[cfg(feature = "network")]
+            fetcher: Box::new(crate::fetcher::ReqwestFetcher::new()),
+            #[cfg(not(feature = "network"))]
+            fetcher: Box::new(crate::fetcher::OfflineFetcher),
+        }
+    }
+
+    /// Create a manager for the per-user didyoumean data directory, also searching the
+    /// [`SYSTEM_DIRS`], or `None` if the platform has no data directory.
+    pub fn with_default_dir() -> Option<WordlistManager> {
+        dirs::data_dir().map(|dir| {
+            WordlistManager::new(dir.join("didyoumean"))
+                .system_dirs(SYSTEM_DIRS.iter().map(PathBuf::from).collect())
+        })
+    }
+
+    /// Set the read-only directories searched for word lists before the data directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `system_dirs` - The directories to search, in order.
+    pub fn system_dirs(mut self, system_dirs: Vec<PathBuf>) -> WordlistManager {
+        self.system_dirs = system_dirs;
+        self
+    }
+
+    /// Set the number of previous versions of each word list kept on update.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep_versions` - The number of previous versions to keep, zero to keep none.
+    pub fn keep_versions(mut self, keep_versions: usize) -> WordlistManager {
+        self.keep_versions = keep_versions;
+        self
+    }
+
+    /// Set the fetcher word lists are downloaded with.
+    ///
+    /// # Arguments
+    ///
+    /// * `fetcher` - The fetcher to download with.
+    pub fn fetcher(mut self, fetcher: Box<dyn Fetcher>) -> WordlistManager {
+        self.fetcher = fetcher;
+        self
+    }
+
+    /// Return the directory the word lists are stored in.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Return the path the word list for `lang` is downloaded to.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn path(&self, lang: &str) -> PathBuf {
+        self.data_dir.join(lang)
+    }
+
+    /// Return the path the SymSpell index of the word list for `lang` is stored at.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn index_path(&self, lang: &str) -> PathBuf {
+        self.data_dir.join(format!("{}.symspell", lang))
+    }
+
+    /// Return the path the anagram index of the word list for `lang` is stored at.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn anagram_path(&self, lang: &str) -> PathBuf {
+        self.data_dir.join(format!("{}.anagrams", lang))
+    }
+
+    /// Return the path the thesaurus of `lang` is installed at in the data directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the language.
+    pub fn thesaurus_path(&self, lang: &str) -> PathBuf {
+        self.data_dir.join(format!("{}.thesaurus", lang))
+    }
+
+    /// Load the thesaurus of `lang`, looking in the system directories first like
+    /// [`WordlistManager::locate`], or return `None` if there is none installed.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the language.
+    pub fn load_thesaurus(&self, lang: &str) -> Result<Option<Thesaurus>, DymError> {
+        let path = self
+            .system_dirs
+            .iter()
+            .chain([&self.data_dir])
+            .map(|dir| dir.join(format!("{}.thesaurus", lang)))
+            .find(|path| path.is_file());
+        match path {
+            Some(path) => Ok(Some(Thesaurus::load(&path)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the path of the personal word list, searched alongside the languages.
+    pub fn personal_path(&self) -> PathBuf {
+        self.data_dir.join("personal")
+    }
+
+    /// Return the path of the log recording every download.
+    pub fn audit_log_path(&self) -> PathBuf {
+        self.data_dir.join("downloads.log")
+    }
+
+    /// Return the downloads recorded in the audit log, oldest first.
+    pub fn audit_log(&self) -> Result<Vec<AuditEntry>, Error> {
+        audit::read(&self.audit_log_path())
+    }
+
+    /// Return the URL the word list for `lang` is downloaded from.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn url(&self, lang: &str) -> String {
+        format!("{}/{}", WORDLISTS_URL, lang)
+    }
+
+    /// Return the path of the installed word list for `lang`, looking in the system
+    /// directories first, or `None` if it is not installed.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn locate(&self, lang: &str) -> Option<PathBuf> {
+        self.system_dirs
+            .iter()
+            .chain([&self.data_dir])
+            .map(|dir| dir.join(lang))
+            .find(|path| path.is_file())
+    }
+
+    /// Return whether the word list for `lang` is installed.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn is_installed(&self, lang: &str) -> bool {
+        self.locate(lang).is_some()
+    }
+
+    /// Return whether the word list for `lang` is provided by a read-only system
+    /// directory, so it cannot be updated or rolled back.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn is_read_only(&self, lang: &str) -> bool {
+        self.locate(lang)
+            .is_some_and(|path| !path.starts_with(&self.data_dir))
+    }
+
+    /// Return the locale codes of the installed word lists, sorted.
+    pub fn installed(&self) -> Result<Vec<String>, Error> {
+        let mut langs = vec![];
+        for dir in self.system_dirs.iter().chain([&self.data_dir]) {
+            if !dir.is_dir() {
+                continue;
+            }
+
+            for entry in read_dir(dir)? {
+                let name = entry?.file_name().to_string_lossy().to_string();
+                if SUPPORTED_LANGS.contains_key(name.as_str()) && dir.join(&name).is_file() {
+                    langs.push(name);
+                }
+            }
+        }
+        langs.sort();
+        langs.dedup();
+
+        Ok(langs)
+    }
+
+    /// Load the words of the installed word list for `lang`, failing with
+    /// [`DymError::MissingLanguage`] if it is not installed.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn load(&self, lang: &str) -> Result<Vec<String>, DymError> {
+        let path = self
+            .locate(lang)
+            .ok_or_else(|| DymError::MissingLanguage(lang.to_string()))?;
+        Ok(wordlist::load(&path)?)
+    }
+
+    /// Download the word list for `lang` if it is not installed yet. Fails with
+    /// [`DymError::MissingLanguage`] if there is no word list for `lang` to download.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    /// * `on_progress` - Called as the download starts, progresses and finishes.
+    pub fn fetch(
+        &self,
+        lang: &str,
+        on_progress: &mut dyn FnMut(DownloadEvent),
+    ) -> Result<(), DymError> {
+        if self.is_installed(lang) {
+            return Ok(());
+        }
+        if !SUPPORTED_LANGS.contains_key(lang) {
+            return Err(DymError::MissingLanguage(lang.to_string()));
+        }
+
+        self.download(lang, on_progress)
+    }
+
+    /// Download the word list for `lang` again, keeping the installed copy as a previous
+    /// version. Fails if the word list is provided by a read-only system directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    /// * `on_progress` - Called as the download starts, progresses and finishes.
+    pub fn update(
+        &self,
+        lang: &str,
+        on_progress: &mut dyn FnMut(DownloadEvent),
+    ) -> Result<(), DymError> {
+        if self.is_read_only(lang) {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!(
+                    "The {} word list is provided read-only by {}",
+                    lang,
+                    self.locate(lang).unwrap().display()
+                ),
+            )
+            .into());
+        }
+        if !SUPPORTED_LANGS.contains_key(lang) {
+            return Err(DymError::MissingLanguage(lang.to_string()));
+        }
+
+        // A partial download left from an earlier update may be of an older upstream list,
+        // so start afresh rather than resume it.
+        let part_path = self.part_path(lang);
+        if part_path.is_file() {
+            remove_file(&part_path)?;
+        }
+
+        // Only retire the installed list once its replacement is complete, so a failed
+        // update leaves it in place.
+        let bytes = self.download_part(lang, on_progress)?;
+        if self.path(lang).is_file() {
+            versions::retire(&self.data_dir, lang, self.keep_versions)?;
+        }
+        self.install_part(lang, bytes, on_progress)
+    }
+
+    /// Replace the installed word list for `lang` with its most recent previous version.
+    /// Return `false` if there is no previous version to restore.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn rollback(&self, lang: &str) -> Result<bool, Error> {
+        versions::rollback(&self.data_dir, lang)
+    }
+
+    /// Return the size of the word list for `lang` on the server, without downloading it.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn remote_size(&self, lang: &str) -> Option<u64> {
+        self.fetcher.content_length(&self.url(lang)).ok()?
+    }
+
+    /// Return the path an unfinished download of the word list for `lang` is kept at.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The locale code of the word list.
+    pub fn part_path(&self, lang: &str) -> PathBuf {
+        self.data_dir.join(format!("{}.part", lang))
+    }
+
+    /// Download the word list for `lang`, replacing any installed copy once the download
+    /// is complete. An interrupted download is resumed on the next attempt.
+    fn download(
+        &self,
+        lang: &str,
+        on_progress: &mut dyn FnMut(DownloadEvent),
+    ) -> Result<(), DymError> {
+        let bytes = self.download_part(lang, on_progress)?;
+        self.install_part(lang, bytes, on_progress)
+    }
+
+    /// Download the word list for `lang` to [`WordlistManager::part_path`] and check it is
+    /// complete, returning its size. An interrupted download is resumed on the next attempt.
+    #[tracing::instrument(level = "debug", skip(self, on_progress))]
+    fn download_part(
+        &self,
+        lang: &str,
+        on_progress: &mut dyn FnMut(DownloadEvent),
+    ) -> Result<u64, DymError> {
+        create_dir_all(&self.data_dir)?;
+
+        // Download next to the final path, so a failed download never leaves a partial list.
+        let part_path = self.part_path(lang);
+        let offset = part_path.metadata().map(|m| m.len()).unwrap_or(0);
+        let url = self.url(lang);
+        let network = |source| DymError::Network {
+            url: url.clone(),
+            source,
+        };
+        let download = self.fetcher.stream(&url, offset).map_err(network)?;
+
+        // Start over if the server ignored the request to skip what we already have.
+        let (mut file, mut bytes) = if offset > 0 && download.resumed {
+            tracing::debug!(offset, "resuming the download");
+            (OpenOptions::new().append(true).open(&part_path)?, offset)
+        } else {
+            (File::create(&part_path)?, 0)
+        };
+        let total_bytes = download.content_length.map(|len| len + bytes);
+        on_progress(DownloadEvent::Started { lang, total_bytes });
+
+        // On error the partial download is kept, to be resumed next time.
+        for chunk in download.chunks {
+            let chunk = chunk.map_err(network)?;
+            file.write_all(&chunk)?;
+            bytes += chunk.len() as u64;
+            on_progress(DownloadEvent::Progress {
+                lang,
+                bytes,
+                total_bytes,
+            });
+        }
+
+        // A size mismatch means a corrupt download: discard it rather than resume it.
+        if let Some(total_bytes) = total_bytes {
+            if bytes != total_bytes {
+                remove_file(&part_path)?;
+                return Err(network(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Downloaded {} bytes of the {} word list, expected {}",
+                        bytes, lang, total_bytes
+                    ),
+                )));
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// Move the complete download of the word list for `lang` into place and record it in
+    /// the audit log.
+    fn install_part(
+        &self,
+        lang: &str,
+        bytes: u64,
+        on_progress: &mut dyn FnMut(DownloadEvent),
+    ) -> Result<(), DymError> {
+        rename(self.part_path(lang), self.path(lang))?;
+
+        // Record what was installed, so downloads can be audited later.
+        let entry = AuditEntry {
+            time: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            lang: lang.to_string(),
+            url: self.url(lang),
+            bytes,
+            sha256: sha256::hex_digest(&read(self.path(lang))?),
+        };
+        tracing::debug!(?entry, "recording the download");
+        audit::record(&self.audit_log_path(), &entry)?;
+
+        on_progress(DownloadEvent::Finished { lang, bytes });
+
+        Ok(())
+    }
+}
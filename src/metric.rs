@@ -0,0 +1,204 @@
+use std::{cmp::min, collections::HashMap, fmt};
+
+/// The distance functions that are true metrics, so they can back structures relying on
+/// metric properties such as BK-trees. For any strings `a`, `b` and `c`:
+///
+/// * `distance(a, b) == 0` if and only if `a == b`,
+/// * `distance(a, b) == distance(b, a)`,
+/// * `distance(a, c) <= distance(a, b) + distance(b, c)`.
+///
+/// [`crate::edit_distance`] is not among them: it never edits a substring twice, which
+/// breaks the triangle inequality.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{edit_distance, metric::Metric};
+/// let chars = |s: &str| s.chars().collect::<Vec<_>>();
+///
+/// // "ca" -> "ac" -> "abc" takes two edits, but edit_distance says "ca" -> "abc" takes three.
+/// assert_eq!(edit_distance(&chars("ca"), "ac") + edit_distance(&chars("ac"), "abc"), 2);
+/// assert_eq!(edit_distance(&chars("ca"), "abc"), 3);
+/// assert_eq!(Metric::DamerauLevenshtein.distance("ca", "abc"), 2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Counts insertions, deletions and substitutions.
+    Levenshtein,
+    /// Counts insertions, deletions, substitutions and transpositions of adjacent
+    /// characters.
+    DamerauLevenshtein,
+}
+
+/// A metric property that a distance function failed to hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// The distance between two strings was zero although they differ, or non-zero
+    /// although they are equal.
+    Identity,
+    /// The distance from `a` to `b` differed from the distance from `b` to `a`.
+    Symmetry,
+    /// Going from `a` to `c` through `b` was shorter than going there directly.
+    TriangleInequality,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let property = match self {
+            Violation::Identity => "identity of indiscernibles",
+            Violation::Symmetry => "symmetry",
+            Violation::TriangleInequality => "triangle inequality",
+        };
+        write!(f, "distance violates {}", property)
+    }
+}
+
+impl Metric {
+    /// Return the distance between `a` and `b`. Builds with debug assertions enabled check
+    /// the identity and symmetry properties on every call. The triangle inequality needs a
+    /// third string, so it cannot be checked from a single pair: [`check_invariants`]
+    /// checks it over three strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first string to compare.
+    /// * `b` - The second string to compare.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use didyoumean::metric::Metric;
+    /// assert_eq!(Metric::Levenshtein.distance("tset", "test"), 2);
+    /// assert_eq!(Metric::DamerauLevenshtein.distance("tset", "test"), 1);
+    /// assert_eq!(Metric::Levenshtein.distance("kitten", "sitting"), 3);
+    /// ```
+    pub fn distance(self, a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let dist = self.chars_distance(&a, &b);
+
+        debug_assert_eq!(dist == 0, a == b, "{}", Violation::Identity);
+        debug_assert_eq!(self.chars_distance(&b, &a), dist, "{}", Violation::Symmetry);
+
+        dist
+    }
+
+    /// Return the distance between the character sequences `a` and `b`.
+    pub(crate) fn chars_distance(self, a: &[char], b: &[char]) -> usize {
+        match self {
+            Metric::Levenshtein => levenshtein(a, b),
+            Metric::DamerauLevenshtein => damerau_levenshtein(a, b),
+        }
+    }
+}
+
+/// Check that `metric` holds the metric properties over the strings `a`, `b` and `c`,
+/// returning the first property it violates.
+///
+/// # Arguments
+///
+/// * `metric` - The metric to check.
+/// * `a` - The first string.
+/// * `b` - The second string.
+/// * `c` - The third string.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::metric::{check_invariants, Metric};
+/// assert_eq!(check_invariants(Metric::Levenshtein, "cat", "cut", "cute"), Ok(()));
+/// ```
+pub fn check_invariants(metric: Metric, a: &str, b: &str, c: &str) -> Result<(), Violation> {
+    let [a, b, c] = [a, b, c].map(|s| s.chars().collect::<Vec<_>>());
+    let dist = |x: &[char], y: &[char]| metric.chars_distance(x, y);
+
+    for (x, y) in [(&a, &b), (&b, &c), (&a, &c)] {
+        if (dist(x, y) == 0) != (x == y) || dist(x, x) != 0 {
+            return Err(Violation::Identity);
+        }
+        if dist(x, y) != dist(y, x) {
+            return Err(Violation::Symmetry);
+        }
+    }
+
+    // Every ordering, so each string gets to be the intermediate one.
+    for (x, y, z) in [(&a, &b, &c), (&b, &c, &a), (&c, &a, &b)] {
+        if dist(x, z) > dist(x, y) + dist(y, z) {
+            return Err(Violation::TriangleInequality);
+        }
+    }
+
+    Ok(())
+}
+
+/// Return the Levenshtein distance between `a` and `b`, keeping two rows of the matrix.
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let sub_cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = min(
+                previous[j - 1] + sub_cost,
+                min(previous[j] + 1, current[j - 1] + 1),
+            );
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Return the unrestricted Damerau-Levenshtein distance between `a` and `b`, using the
+/// Lowrance-Wagner algorithm.
+fn damerau_levenshtein(a: &[char], b: &[char]) -> usize {
+    let (n, m) = (a.len(), b.len());
+    let max = n + m;
+    // The matrix has an extra leading row and column holding `max`.
+    let width = m + 2;
+    let mut mat = vec![0; (n + 2) * width];
+
+    mat[0] = max;
+    for i in 0..=n {
+        mat[(i + 1) * width] = max;
+        mat[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=m {
+        mat[j + 1] = max;
+        mat[width + j + 1] = j;
+    }
+
+    // The last row each character of `a` was seen in.
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=n {
+        // The last column in this row where the characters matched.
+        let mut last_match_col = 0;
+        for j in 1..=m {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_col;
+            let sub_cost = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+
+            mat[(i + 1) * width + j + 1] = min(
+                min(
+                    mat[i * width + j] + sub_cost, // substitution cost
+                    mat[(i + 1) * width + j] + 1,  // insertion cost
+                ),
+                min(
+                    mat[i * width + j + 1] + 1, // deletion cost
+                    // Transposition cost, editing whatever lies between the swapped pair.
+                    mat[k * width + l] + (i - k - 1) + 1 + (j - l - 1),
+                ),
+            );
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    mat[(n + 1) * width + m + 1]
+}
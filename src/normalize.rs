@@ -0,0 +1,25 @@
+use alloc::string::String;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Return `word` without its accents and other diacritics, so that words differing only
+/// in them are the same. Letters are decomposed into a base letter and combining marks,
+/// and the marks are dropped. Letters that are not decomposed, such as "ø" or "ł", are
+/// kept.
+///
+/// # Arguments
+///
+/// * `word` - The word to strip.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::normalize::strip_accents;
+/// assert_eq!(strip_accents("résumé"), "resume");
+/// assert_eq!(strip_accents("mañana"), "manana");
+/// // Already decomposed, with a combining acute accent.
+/// assert_eq!(strip_accents("cafe\u{301}"), "cafe");
+/// assert_eq!(strip_accents("København"), "København");
+/// ```
+pub fn strip_accents(word: &str) -> String {
+    word.nfd().filter(|c| !is_combining_mark(*c)).collect()
+}
@@ -0,0 +1,202 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// One element of a [`Pattern`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    /// This character.
+    Char(char),
+    /// Any one character, written `_` or `?`.
+    One,
+    /// Any run of characters, possibly empty, written `*`.
+    Run,
+}
+
+/// A crossword-style pattern, where `_` or `?` stands for any one character and `*` for
+/// any run of characters, possibly empty. Every other character stands for itself.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::pattern::Pattern;
+/// let pattern: Pattern = "c_t__n".parse().unwrap();
+/// assert!(pattern.matches("cotton"));
+/// assert!(!pattern.matches("cotto"));
+///
+/// let pattern: Pattern = "un*able".parse().unwrap();
+/// assert!(pattern.matches("unable"));
+/// assert!(pattern.matches("unbelievable"));
+/// assert_eq!(pattern.to_string(), "un*able");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+impl Pattern {
+    /// Return the length of the shortest word the pattern matches, in characters.
+    pub fn min_len(&self) -> usize {
+        self.tokens
+            .iter()
+            .filter(|token| **token != Token::Run)
+            .count()
+    }
+
+    /// Return whether the pattern only matches words of one length, having no `*`.
+    pub fn is_fixed_len(&self) -> bool {
+        !self.tokens.contains(&Token::Run)
+    }
+
+    /// Return whether `word` matches the pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to match.
+    pub fn matches(&self, word: &str) -> bool {
+        let word: Vec<char> = word.chars().collect();
+        let (mut t, mut w) = (0, 0);
+        // The last `*` seen and the position in the word it was tried from, to backtrack
+        // to when the rest of the pattern fails.
+        let mut backtrack = None;
+
+        while w < word.len() {
+            match self.tokens.get(t) {
+                Some(Token::Run) => {
+                    backtrack = Some((t, w));
+                    t += 1;
+                }
+                Some(Token::One) => {
+                    t += 1;
+                    w += 1;
+                }
+                Some(Token::Char(c)) if *c == word[w] => {
+                    t += 1;
+                    w += 1;
+                }
+                _ => match backtrack {
+                    // Let the `*` take one more character and try again.
+                    Some((run, from)) => {
+                        backtrack = Some((run, from + 1));
+                        t = run + 1;
+                        w = from + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        self.tokens[t..].iter().all(|token| *token == Token::Run)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        if pattern.is_empty() {
+            return Err("The pattern is empty".to_string());
+        }
+
+        Ok(Pattern {
+            tokens: pattern
+                .chars()
+                .map(|c| match c {
+                    '_' | '?' => Token::One,
+                    '*' => Token::Run,
+                    c => Token::Char(c),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for token in &self.tokens {
+            match token {
+                Token::Char(c) => write!(f, "{}", c)?,
+                Token::One => write!(f, "_")?,
+                Token::Run => write!(f, "*")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Words bucketed by their length in characters, so that a search for words of some
+/// lengths only looks at the words of those lengths.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::pattern::LengthIndex;
+/// let words = ["cat", "cotton", "dog", "cut"].map(String::from).to_vec();
+/// let index = LengthIndex::new(&words);
+/// assert_eq!(index.with_len(3).collect::<Vec<_>>(), vec!["cat", "dog", "cut"]);
+/// assert_eq!(index.find(&"c_t".parse().unwrap()), vec!["cat", "cut"]);
+/// assert_eq!(index.find(&"c*".parse().unwrap()), vec!["cat", "cotton", "cut"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LengthIndex<'a> {
+    buckets: BTreeMap<usize, Vec<(usize, &'a str)>>,
+}
+
+impl<'a> LengthIndex<'a> {
+    /// Create the index of `words`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to index.
+    pub fn new(words: &'a [String]) -> LengthIndex<'a> {
+        let mut buckets: BTreeMap<usize, Vec<(usize, &str)>> = BTreeMap::new();
+        for (i, word) in words.iter().enumerate() {
+            buckets
+                .entry(word.chars().count())
+                .or_default()
+                .push((i, word));
+        }
+        LengthIndex { buckets }
+    }
+
+    /// Return every word, shortest first and in the order of the word list at equal
+    /// length.
+    pub fn words(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.buckets.values().flatten().map(|(_, word)| *word)
+    }
+
+    /// Return the words of `len` characters, in the order of the word list.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The length of the words.
+    pub fn with_len(&self, len: usize) -> impl Iterator<Item = &'a str> + '_ {
+        self.buckets
+            .get(&len)
+            .into_iter()
+            .flatten()
+            .map(|(_, word)| *word)
+    }
+
+    /// Return the words matching `pattern`, in the order of the word list. Only the words
+    /// of the lengths `pattern` can match are looked at.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to match.
+    pub fn find(&self, pattern: &Pattern) -> Vec<&'a str> {
+        let min_len = pattern.min_len();
+        let lens = match pattern.is_fixed_len() {
+            true => min_len..=min_len,
+            false => min_len..=usize::MAX,
+        };
+
+        let mut found: Vec<(usize, &str)> = self
+            .buckets
+            .range(lens)
+            .flat_map(|(_, words)| words)
+            .filter(|(_, word)| pattern.matches(word))
+            .copied()
+            .collect();
+        found.sort_unstable();
+        found.into_iter().map(|(_, word)| word).collect()
+    }
+}
@@ -0,0 +1,735 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Return the Soundex digit of `c`, or `None` for vowels, `h`, `w`, `y` and anything that
+/// is not an ASCII letter.
+///
+/// # Arguments
+///
+/// * `c` - The letter to encode.
+fn soundex_digit(c: char) -> Option<char> {
+    match c.to_ascii_lowercase() {
+        'b' | 'f' | 'p' | 'v' => Some('1'),
+        'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+        'd' | 't' => Some('3'),
+        'l' => Some('4'),
+        'm' | 'n' => Some('5'),
+        'r' => Some('6'),
+        _ => None,
+    }
+}
+
+/// Return the American Soundex code of `word`: its first letter followed by three digits
+/// standing for the consonants that follow. Words that sound alike share a code.
+/// Characters other than ASCII letters are ignored, and words without any letters have
+/// no code.
+///
+/// # Arguments
+///
+/// * `word` - The word to encode.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::phonetic::soundex;
+/// assert_eq!(soundex("Robert").as_deref(), Some("R163"));
+/// assert_eq!(soundex("Rupert").as_deref(), Some("R163"));
+/// assert_eq!(soundex("Ashcraft").as_deref(), Some("A261"));
+/// assert_eq!(soundex("Tymczak").as_deref(), Some("T522"));
+/// assert_eq!(soundex("Pfister").as_deref(), Some("P236"));
+/// assert_eq!(soundex("42"), None);
+/// ```
+pub fn soundex(word: &str) -> Option<String> {
+    let mut letters = word.chars().filter(char::is_ascii_alphabetic);
+    let first = letters.next()?;
+
+    let mut code = first.to_ascii_uppercase().to_string();
+    let mut last = soundex_digit(first);
+    for c in letters {
+        let digit = soundex_digit(c);
+        if let Some(digit) = digit.filter(|_| digit != last) {
+            code.push(digit);
+            if code.len() == 4 {
+                break;
+            }
+        }
+        // Letters with the same digit on either side of h or w are coded once, while a
+        // vowel between them has both coded.
+        if !matches!(c.to_ascii_lowercase(), 'h' | 'w') {
+            last = digit;
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    Some(code)
+}
+
+/// Return whether `a` and `b` sound alike by Soundex. Unlike comparing the codes, the
+/// first letters only need to sound alike themselves, so "fone" sounds like "phone".
+///
+/// # Arguments
+///
+/// * `a` - The first word to compare.
+/// * `b` - The second word to compare.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::phonetic::sounds_alike;
+/// assert!(sounds_alike("fone", "phone"));
+/// assert!(sounds_alike("Robert", "Rupert"));
+/// assert!(!sounds_alike("fone", "phase"));
+/// ```
+pub fn sounds_alike(a: &str, b: &str) -> bool {
+    match (soundex(a), soundex(b)) {
+        (Some(a), Some(b)) => {
+            // Compare first letters by their digit, falling back to the letter for vowels.
+            let first = |code: &str| {
+                let letter = code.chars().next().unwrap();
+                soundex_digit(letter).unwrap_or(letter)
+            };
+            first(&a) == first(&b) && a[1..] == b[1..]
+        }
+        _ => false,
+    }
+}
+
+/// The longest Double Metaphone code [`metaphone`] returns.
+const METAPHONE_LENGTH: usize = 4;
+
+/// A word being encoded by Double Metaphone, with the two codes built so far.
+struct Encoder {
+    chars: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl Encoder {
+    /// Return the character at `i`, or `'\0'` outside the word.
+    fn at(&self, i: isize) -> char {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| self.chars.get(i))
+            .copied()
+            .unwrap_or('\0')
+    }
+
+    /// Return whether one of `options` starts at `start`.
+    fn has(&self, start: isize, options: &[&str]) -> bool {
+        options.iter().any(|option| {
+            option
+                .chars()
+                .enumerate()
+                .all(|(i, c)| start >= 0 && self.at(start + i as isize) == c)
+        })
+    }
+
+    /// Return whether the character at `i` is a vowel.
+    fn is_vowel(&self, i: isize) -> bool {
+        matches!(self.at(i), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    /// Return the index of the last character.
+    fn last(&self) -> isize {
+        self.chars.len() as isize - 1
+    }
+
+    /// Return whether the word looks Germanic, from the start of its codes.
+    fn germanic(&self) -> bool {
+        self.has(0, &["VAN ", "VON ", "SCH"])
+    }
+
+    /// Append `primary` to the primary code and `alternate` to the alternate one.
+    fn add(&mut self, primary: &str, alternate: &str) {
+        self.primary.push_str(primary);
+        self.alternate.push_str(alternate);
+    }
+
+    /// Append `code` to both codes.
+    fn add_both(&mut self, code: &str) {
+        self.add(code, code);
+    }
+
+    /// Encode the consonant or vowel at `i`, returning the index to continue from.
+    fn encode_at(&mut self, i: isize) -> isize {
+        let next = self.at(i + 1);
+        match self.at(i) {
+            'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                // Only initial vowels are kept, all as A.
+                if i == 0 {
+                    self.add_both("A");
+                }
+                i + 1
+            }
+            'B' => {
+                self.add_both("P");
+                i + if next == 'B' { 2 } else { 1 }
+            }
+            'Ç' => {
+                self.add_both("S");
+                i + 1
+            }
+            'C' => self.encode_c(i),
+            'D' => {
+                if self.has(i, &["DG"]) {
+                    if self.has(i + 2, &["I", "E", "Y"]) {
+                        // "edge"
+                        self.add_both("J");
+                        i + 3
+                    } else {
+                        // "edgar"
+                        self.add_both("TK");
+                        i + 2
+                    }
+                } else {
+                    self.add_both("T");
+                    i + if self.has(i, &["DT", "DD"]) { 2 } else { 1 }
+                }
+            }
+            'F' => {
+                self.add_both("F");
+                i + if next == 'F' { 2 } else { 1 }
+            }
+            'G' => self.encode_g(i),
+            'H' => {
+                // Only kept when first or between vowels.
+                if (i == 0 || self.is_vowel(i - 1)) && self.is_vowel(i + 1) {
+                    self.add_both("H");
+                    i + 2
+                } else {
+                    i + 1
+                }
+            }
+            'J' => self.encode_j(i),
+            'K' => {
+                self.add_both("K");
+                i + if next == 'K' { 2 } else { 1 }
+            }
+            'L' => {
+                if next == 'L' {
+                    // Spanish "cabrillo", "gallegos"
+                    let last = self.last();
+                    let spanish = (i == last - 2 && self.has(i - 1, &["ILLO", "ILLA", "ALLE"]))
+                        || ((self.has(last - 1, &["AS", "OS"]) || self.has(last, &["A", "O"]))
+                            && self.has(i - 1, &["ALLE"]));
+                    if spanish {
+                        self.add("L", "");
+                    } else {
+                        self.add_both("L");
+                    }
+                    i + 2
+                } else {
+                    self.add_both("L");
+                    i + 1
+                }
+            }
+            'M' => {
+                self.add_both("M");
+                // "dumb", "thumb"
+                let umb =
+                    self.has(i - 1, &["UMB"]) && (i + 1 == self.last() || self.has(i + 2, &["ER"]));
+                i + if next == 'M' || umb { 2 } else { 1 }
+            }
+            'N' => {
+                self.add_both("N");
+                i + if next == 'N' { 2 } else { 1 }
+            }
+            'Ñ' => {
+                self.add_both("N");
+                i + 1
+            }
+            'P' => {
+                if next == 'H' {
+                    self.add_both("F");
+                    i + 2
+                } else {
+                    self.add_both("P");
+                    i + if self.has(i + 1, &["P", "B"]) { 2 } else { 1 }
+                }
+            }
+            'Q' => {
+                self.add_both("K");
+                i + if next == 'Q' { 2 } else { 1 }
+            }
+            'R' => {
+                // French "rogier", but not "hochmeier"
+                if i == self.last()
+                    && !self.slavo_germanic
+                    && self.has(i - 2, &["IE"])
+                    && !self.has(i - 4, &["ME", "MA"])
+                {
+                    self.add("", "R");
+                } else {
+                    self.add_both("R");
+                }
+                i + if next == 'R' { 2 } else { 1 }
+            }
+            'S' => self.encode_s(i),
+            'T' => self.encode_t(i),
+            'V' => {
+                self.add_both("F");
+                i + if next == 'V' { 2 } else { 1 }
+            }
+            'W' => self.encode_w(i),
+            'X' => {
+                if i == 0 {
+                    self.add_both("S");
+                    return i + 1;
+                }
+                // French "breaux"
+                let french = i == self.last()
+                    && (self.has(i - 3, &["IAU", "EAU"]) || self.has(i - 2, &["AU", "OU"]));
+                if !french {
+                    self.add_both("KS");
+                }
+                i + if self.has(i + 1, &["C", "X"]) { 2 } else { 1 }
+            }
+            'Z' => {
+                if next == 'H' {
+                    // Chinese "zhao"
+                    self.add_both("J");
+                    return i + 2;
+                }
+                if self.has(i + 1, &["ZO", "ZI", "ZA"])
+                    || (self.slavo_germanic && i > 0 && self.at(i - 1) != 'T')
+                {
+                    self.add("S", "TS");
+                } else {
+                    self.add_both("S");
+                }
+                i + if next == 'Z' { 2 } else { 1 }
+            }
+            _ => i + 1,
+        }
+    }
+
+    /// Encode the C at `i`, returning the index to continue from.
+    fn encode_c(&mut self, i: isize) -> isize {
+        // Various Germanic, such as "bacher" and "macher"
+        let germanic = self.has(i, &["CHIA"])
+            || (i > 1
+                && !self.is_vowel(i - 2)
+                && self.has(i - 1, &["ACH"])
+                && (!matches!(self.at(i + 2), 'I' | 'E')
+                    || self.has(i - 2, &["BACHER", "MACHER"])));
+
+        if germanic {
+            self.add_both("K");
+            i + 2
+        } else if i == 0 && self.has(i, &["CAESAR"]) {
+            self.add_both("S");
+            i + 2
+        } else if self.has(i, &["CH"]) {
+            self.encode_ch(i)
+        } else if self.has(i, &["CZ"]) && !self.has(i - 2, &["WICZ"]) {
+            // "czerny"
+            self.add("S", "X");
+            i + 2
+        } else if self.has(i + 1, &["CIA"]) {
+            // "focaccia"
+            self.add_both("X");
+            i + 3
+        } else if self.has(i, &["CC"]) && !(i == 1 && self.at(0) == 'M') {
+            // Double C, but not "mcclelland"
+            if self.has(i + 2, &["I", "E", "H"]) && !self.has(i + 2, &["HU"]) {
+                if (i == 1 && self.at(i - 1) == 'A') || self.has(i - 1, &["UCCEE", "UCCES"]) {
+                    // "accident", "accede", "succeed"
+                    self.add_both("KS");
+                } else {
+                    // "bacci", "bertucci"
+                    self.add_both("X");
+                }
+                i + 3
+            } else {
+                self.add_both("K");
+                i + 2
+            }
+        } else if self.has(i, &["CK", "CG", "CQ"]) {
+            self.add_both("K");
+            i + 2
+        } else if self.has(i, &["CI", "CE", "CY"]) {
+            // Italian against English
+            if self.has(i, &["CIO", "CIE", "CIA"]) {
+                self.add("S", "X");
+            } else {
+                self.add_both("S");
+            }
+            i + 2
+        } else {
+            self.add_both("K");
+            if self.has(i + 1, &[" C", " Q", " G"]) {
+                // "mac caffrey", "mac gregor"
+                i + 3
+            } else if self.has(i + 1, &["C", "K", "Q"]) && !self.has(i + 1, &["CE", "CI"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    /// Encode the CH at `i`, returning the index to continue from.
+    fn encode_ch(&mut self, i: isize) -> isize {
+        // Greek roots, such as "chemistry" and "chorus"
+        let greek = i == 0
+            && (self.has(i + 1, &["HARAC", "HARIS"])
+                || self.has(i + 1, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.has(0, &["CHORE"]);
+        // Germanic, Greek or otherwise a KH sound
+        let kh = self.germanic()
+            || self.has(i - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.has(i + 2, &["T", "S"])
+            || ((self.has(i - 1, &["A", "O", "U", "E"]) || i == 0)
+                && (self.has(i + 2, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "])
+                    || i + 1 == self.last()));
+
+        if i > 0 && self.has(i, &["CHAE"]) {
+            // "michael"
+            self.add("K", "X");
+        } else if greek || kh {
+            self.add_both("K");
+        } else if i == 0 {
+            self.add_both("X");
+        } else if self.has(0, &["MC"]) {
+            // "mchugh"
+            self.add_both("K");
+        } else {
+            self.add("X", "K");
+        }
+        i + 2
+    }
+
+    /// Encode the G at `i`, returning the index to continue from.
+    fn encode_g(&mut self, i: isize) -> isize {
+        let next = self.at(i + 1);
+        if next == 'H' {
+            return self.encode_gh(i);
+        }
+
+        if next == 'N' {
+            if i == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.add("KN", "N");
+            } else if !self.has(i + 2, &["EY"]) && !self.slavo_germanic {
+                self.add("N", "KN");
+            } else {
+                self.add_both("KN");
+            }
+            i + 2
+        } else if self.has(i + 1, &["LI"]) && !self.slavo_germanic {
+            // "tagliaro"
+            self.add("KL", "L");
+            i + 2
+        } else if i == 0
+            && (next == 'Y'
+                || self.has(
+                    i + 1,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ))
+        {
+            // -ges-, -gep-, -gel- and -gie- at the start
+            self.add("K", "J");
+            i + 2
+        } else if (self.has(i + 1, &["ER"]) || next == 'Y')
+            && !self.has(0, &["DANGER", "RANGER", "MANGER"])
+            && !self.has(i - 1, &["E", "I"])
+            && !self.has(i - 1, &["RGY", "OGY"])
+        {
+            // -ger- and -gy-
+            self.add("K", "J");
+            i + 2
+        } else if self.has(i + 1, &["E", "I", "Y"]) || self.has(i - 1, &["AGGI", "OGGI"]) {
+            // Italian "biaggi"
+            if self.germanic() || self.has(i + 1, &["ET"]) {
+                self.add_both("K");
+            } else if self.has(i + 1, &["IER"]) {
+                self.add_both("J");
+            } else {
+                self.add("J", "K");
+            }
+            i + 2
+        } else {
+            self.add_both("K");
+            i + if next == 'G' { 2 } else { 1 }
+        }
+    }
+
+    /// Encode the GH at `i`, returning the index to continue from.
+    fn encode_gh(&mut self, i: isize) -> isize {
+        if i > 0 && !self.is_vowel(i - 1) {
+            self.add_both("K");
+        } else if i == 0 {
+            // "ghislane", "ghiradelli"
+            self.add_both(if self.at(i + 2) == 'I' { "J" } else { "K" });
+        } else if (i > 1 && self.has(i - 2, &["B", "H", "D"]))
+            || (i > 2 && self.has(i - 3, &["B", "H", "D"]))
+            || (i > 3 && self.has(i - 4, &["B", "H"]))
+        {
+            // Parker's rule, as in "hugh" and "bough"
+        } else if i > 2 && self.at(i - 1) == 'U' && self.has(i - 3, &["C", "G", "L", "R", "T"]) {
+            // "laugh", "cough", "rough", "tough"
+            self.add_both("F");
+        } else if i > 0 && self.at(i - 1) != 'I' {
+            self.add_both("K");
+        }
+        i + 2
+    }
+
+    /// Encode the J at `i`, returning the index to continue from.
+    fn encode_j(&mut self, i: isize) -> isize {
+        if self.has(i, &["JOSE"]) || self.has(0, &["SAN "]) {
+            // Spanish "jose", "san jacinto"
+            if (i == 0 && self.at(i + 4) == ' ') || self.chars.len() == 4 || self.has(0, &["SAN "])
+            {
+                self.add_both("H");
+            } else {
+                self.add("J", "H");
+            }
+            return i + 1;
+        }
+
+        let next = self.at(i + 1);
+        if i == 0 {
+            // "jankelowicz"
+            self.add("J", "A");
+        } else if self.is_vowel(i - 1) && !self.slavo_germanic && matches!(next, 'A' | 'O') {
+            // Spanish "bajador"
+            self.add("J", "H");
+        } else if i == self.last() {
+            self.add("J", "");
+        } else if !self.has(i + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.has(i - 1, &["S", "K", "L"])
+        {
+            self.add_both("J");
+        }
+        i + if next == 'J' { 2 } else { 1 }
+    }
+
+    /// Encode the S at `i`, returning the index to continue from.
+    fn encode_s(&mut self, i: isize) -> isize {
+        if self.has(i - 1, &["ISL", "YSL"]) {
+            // Silent in "island", "isle", "carlisle"
+            i + 1
+        } else if i == 0 && self.has(i, &["SUGAR"]) {
+            self.add("X", "S");
+            i + 1
+        } else if self.has(i, &["SH"]) {
+            // Germanic "holmsheim"
+            if self.has(i + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add_both("S");
+            } else {
+                self.add_both("X");
+            }
+            i + 2
+        } else if self.has(i, &["SIO", "SIA"]) {
+            // Italian and Armenian
+            if self.slavo_germanic {
+                self.add_both("S");
+            } else {
+                self.add("S", "X");
+            }
+            i + 3
+        } else if (i == 0 && self.has(i + 1, &["M", "N", "L", "W"])) || self.has(i + 1, &["Z"]) {
+            // "smith" matching "schmidt", "snider" matching "schneider"
+            self.add("S", "X");
+            i + if self.has(i + 1, &["Z"]) { 2 } else { 1 }
+        } else if self.has(i, &["SC"]) {
+            if self.at(i + 2) == 'H' {
+                // Schlesinger's rule
+                if self.has(i + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                    // Dutch "school", "schooner", or "schermerhorn", "schenker"
+                    if self.has(i + 3, &["ER", "EN"]) {
+                        self.add("X", "SK");
+                    } else {
+                        self.add_both("SK");
+                    }
+                } else if i == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                    self.add("X", "S");
+                } else {
+                    self.add_both("X");
+                }
+            } else if self.has(i + 2, &["I", "E", "Y"]) {
+                self.add_both("S");
+            } else {
+                self.add_both("SK");
+            }
+            i + 3
+        } else {
+            // French "resnais", "artois"
+            if i == self.last() && self.has(i - 2, &["AI", "OI"]) {
+                self.add("", "S");
+            } else {
+                self.add_both("S");
+            }
+            i + if self.has(i + 1, &["S", "Z"]) { 2 } else { 1 }
+        }
+    }
+
+    /// Encode the T at `i`, returning the index to continue from.
+    fn encode_t(&mut self, i: isize) -> isize {
+        if self.has(i, &["TION", "TIA", "TCH"]) {
+            self.add_both("X");
+            i + 3
+        } else if self.has(i, &["TH", "TTH"]) {
+            // "thomas", "thames" or Germanic
+            if self.has(i + 2, &["OM", "AM"]) || self.germanic() {
+                self.add_both("T");
+            } else {
+                self.add("0", "T");
+            }
+            i + 2
+        } else {
+            self.add_both("T");
+            i + if self.has(i + 1, &["T", "D"]) { 2 } else { 1 }
+        }
+    }
+
+    /// Encode the W at `i`, returning the index to continue from.
+    fn encode_w(&mut self, i: isize) -> isize {
+        if self.has(i, &["WR"]) {
+            self.add_both("R");
+            i + 2
+        } else if i == 0 && (self.is_vowel(i + 1) || self.has(i, &["WH"])) {
+            if self.is_vowel(i + 1) {
+                // "wasserman" matching "vasserman"
+                self.add("A", "F");
+            } else {
+                // "uomo" matching "womo"
+                self.add_both("A");
+            }
+            i + 1
+        } else if (i == self.last() && self.is_vowel(i - 1))
+            || self.has(i - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.has(0, &["SCH"])
+        {
+            // "arnow" matching "arnoff"
+            self.add("", "F");
+            i + 1
+        } else if self.has(i, &["WICZ", "WITZ"]) {
+            // Polish "filipowicz"
+            self.add("TS", "FX");
+            i + 4
+        } else {
+            i + 1
+        }
+    }
+}
+
+/// Return the primary and alternate Double Metaphone codes of `word`, each at most four
+/// characters long. The codes stand for how the word is pronounced, so words that sound
+/// alike share a code even if they are spelt differently, with the alternate code
+/// covering a second, often foreign, pronunciation. `0` stands for the "th" sound and
+/// `X` for the "sh" sound.
+///
+/// # Arguments
+///
+/// * `word` - The word to encode.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::phonetic::metaphone;
+/// let code = |word| metaphone(word).0;
+/// assert_eq!(code("knight"), code("nite"));
+/// assert_eq!(code("phone"), code("fone"));
+/// assert_eq!(metaphone("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+/// assert_eq!(metaphone("Jose"), ("HS".to_string(), "HS".to_string()));
+/// assert_eq!(metaphone("Smith"), ("SM0".to_string(), "XMT".to_string()));
+/// ```
+pub fn metaphone(word: &str) -> (String, String) {
+    let chars: Vec<char> = word.trim().chars().flat_map(char::to_uppercase).collect();
+    let text: String = chars.iter().collect();
+    let mut encoder = Encoder {
+        slavo_germanic: ["W", "K", "CZ", "WITZ"].iter().any(|s| text.contains(s)),
+        chars,
+        primary: String::new(),
+        alternate: String::new(),
+    };
+
+    // Skip the silent first letter of "gnome", "knight", "pneumatic", "wrong" and
+    // "psychology".
+    let mut i = if encoder.has(0, &["GN", "KN", "PN", "WR", "PS"]) {
+        1
+    } else {
+        0
+    };
+    while i <= encoder.last()
+        && (encoder.primary.len() < METAPHONE_LENGTH || encoder.alternate.len() < METAPHONE_LENGTH)
+    {
+        i = encoder.encode_at(i);
+    }
+
+    let truncate = |mut code: String| {
+        code.truncate(METAPHONE_LENGTH);
+        code
+    };
+    (truncate(encoder.primary), truncate(encoder.alternate))
+}
+
+/// Return whether `a` and `b` sound alike by Double Metaphone, that is whether any of the
+/// codes of one equals any of the codes of the other.
+///
+/// # Arguments
+///
+/// * `a` - The first word to compare.
+/// * `b` - The second word to compare.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::phonetic::metaphones_match;
+/// assert!(metaphones_match("nite", "knight"));
+/// assert!(metaphones_match("Smith", "Schmidt"));
+/// assert!(!metaphones_match("nite", "nine"));
+/// ```
+pub fn metaphones_match(a: &str, b: &str) -> bool {
+    let (a_primary, a_alternate) = metaphone(a);
+    let (b_primary, b_alternate) = metaphone(b);
+
+    [&a_primary, &a_alternate]
+        .iter()
+        .any(|code| !code.is_empty() && (**code == b_primary || **code == b_alternate))
+}
+
+/// Return the ending of `word` that words rhyming with it share, approximated by its last
+/// run of vowels and the consonants after it, such as "ight" for "light". A `y` after the
+/// first letter counts as a vowel, and words without vowels are their own ending.
+///
+/// # Arguments
+///
+/// * `word` - The word to find the ending of.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::phonetic::rhyme_ending;
+/// assert_eq!(rhyme_ending("light"), "ight");
+/// assert_eq!(rhyme_ending("station"), "ion");
+/// assert_eq!(rhyme_ending("happy"), "y");
+/// assert_eq!(rhyme_ending("brr"), "brr");
+/// ```
+pub fn rhyme_ending(word: &str) -> &str {
+    let is_vowel = |(i, c): (usize, char)| {
+        matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u') || (i > 0 && c == 'y')
+    };
+
+    let mut start = None;
+    let mut in_vowels = false;
+    for (i, c) in word.char_indices() {
+        match (is_vowel((i, c)), in_vowels) {
+            (true, false) => {
+                start = Some(i);
+                in_vowels = true;
+            }
+            (false, true) => in_vowels = false,
+            _ => {}
+        }
+    }
+    &word[start.unwrap_or(0)..]
+}
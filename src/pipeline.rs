@@ -0,0 +1,306 @@
+use crate::{
+    source::{candidates, Source, Suggestion},
+    MatchOptions, Ranking, UNRANKED,
+};
+use std::{borrow::Cow, collections::HashSet};
+
+/// The first stage of a [`Pipeline`]: turns the search term and each word into the form
+/// they are compared in, such as lowercase.
+pub trait Normalizer {
+    /// Return `word` as it is compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The search term or a word of the word lists.
+    fn normalize<'a>(&self, word: &'a str) -> Cow<'a, str>;
+}
+
+/// The second stage of a [`Pipeline`]: cheaply drops words that cannot be suggested,
+/// before they are scored.
+pub trait Prefilter {
+    /// Return whether `word` should be scored against `term`. Both are normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The search term.
+    /// * `word` - A word of the word lists.
+    fn keep(&self, term: &str, word: &str) -> bool;
+}
+
+/// The third stage of a [`Pipeline`]: measures how far each word is from the search term.
+pub trait Scorer {
+    /// Return the distance between `term` and `word`, lower being closer, or `usize::MAX`
+    /// if `word` cannot be ranked against `term`, such as a word that does not sound like
+    /// it. Both are normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The characters of the search term.
+    /// * `word` - A word of the word lists.
+    fn score(&self, term: &[char], word: &str) -> usize;
+}
+
+/// The fourth stage of a [`Pipeline`]: puts the scored words in order, best first.
+pub trait Ranker {
+    /// Sort `suggestions`, best first.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The search term, as given.
+    /// * `suggestions` - The scored words, in the order of the word lists.
+    fn rank(&self, term: &str, suggestions: &mut Vec<Suggestion<'_>>);
+}
+
+/// The last stage of a [`Pipeline`]: adjusts the ranked suggestions, such as dropping
+/// those too far away or moving some up.
+pub trait PostProcessor {
+    /// Adjust `suggestions`, keeping them in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The search term, as given.
+    /// * `suggestions` - The ranked suggestions.
+    fn process(&self, term: &str, suggestions: &mut Vec<Suggestion<'_>>);
+}
+
+impl Normalizer for MatchOptions {
+    fn normalize<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        MatchOptions::normalize(self, word)
+    }
+}
+
+impl Scorer for Ranking {
+    fn score(&self, term: &[char], word: &str) -> usize {
+        self.distance(term, word)
+    }
+}
+
+/// A [`Prefilter`] dropping the words whose length differs from the search term's by more
+/// than this many characters. Such words are at least that many edits away.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxLengthDifference(pub usize);
+
+impl Prefilter for MaxLengthDifference {
+    fn keep(&self, term: &str, word: &str) -> bool {
+        term.chars().count().abs_diff(word.chars().count()) <= self.0
+    }
+}
+
+/// A [`Ranker`] putting the closest words first. Words at the same distance keep the
+/// order of the word lists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByDistance;
+
+impl Ranker for ByDistance {
+    fn rank(&self, _term: &str, suggestions: &mut Vec<Suggestion<'_>>) {
+        suggestions.sort_by_key(|suggestion| suggestion.distance);
+    }
+}
+
+/// A [`PostProcessor`] dropping the suggestions further than this distance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Within(pub usize);
+
+impl PostProcessor for Within {
+    fn process(&self, _term: &str, suggestions: &mut Vec<Suggestion<'_>>) {
+        suggestions.retain(|suggestion| suggestion.distance <= self.0);
+    }
+}
+
+/// A [`PostProcessor`] ranking some words, such as product or company names, as if they
+/// were `by` closer than they are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Boost {
+    words: HashSet<String>,
+    by: usize,
+}
+
+impl Boost {
+    /// Create a booster of `words`.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to move up.
+    /// * `by` - How much closer to make them.
+    pub fn new<I, S>(words: I, by: usize) -> Boost
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Boost {
+            words: words.into_iter().map(Into::into).collect(),
+            by,
+        }
+    }
+}
+
+impl PostProcessor for Boost {
+    fn process(&self, _term: &str, suggestions: &mut Vec<Suggestion<'_>>) {
+        for suggestion in suggestions.iter_mut() {
+            if self.words.contains(suggestion.word) {
+                suggestion.distance = suggestion.distance.saturating_sub(self.by);
+            }
+        }
+        suggestions.sort_by_key(|suggestion| suggestion.distance);
+    }
+}
+
+/// The stages suggestions go through, in order: normalizing the search term and each
+/// word, prefiltering the words, scoring them, ranking them and post-processing the
+/// result. Any stage can be swapped for one of your own.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{pipeline::{Boost, MaxLengthDifference, Pipeline}, source::{Source, SourceKind}};
+/// let words = ["acme", "acne", "acre", "ache"].map(String::from).to_vec();
+/// let sources = [Source::new(SourceKind::Custom, "words", words)];
+///
+/// let pipeline = Pipeline::new().prefilter(MaxLengthDifference(1)).n(2);
+/// let found: Vec<&str> = pipeline.run("acke", &sources).iter().map(|s| s.word).collect();
+/// assert_eq!(found, vec!["acme", "acne"]);
+///
+/// let pipeline = pipeline.post_process(Boost::new(["ache"], 1));
+/// let found: Vec<&str> = pipeline.run("acke", &sources).iter().map(|s| s.word).collect();
+/// assert_eq!(found, vec!["ache", "acme"]);
+/// ```
+pub struct Pipeline {
+    normalizers: Vec<Box<dyn Normalizer>>,
+    prefilters: Vec<Box<dyn Prefilter>>,
+    scorer: Box<dyn Scorer>,
+    ranker: Box<dyn Ranker>,
+    post_processors: Vec<Box<dyn PostProcessor>>,
+    n: usize,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Pipeline {
+            normalizers: vec![],
+            prefilters: vec![],
+            scorer: Box::new(Ranking::EditDistance),
+            ranker: Box::new(ByDistance),
+            post_processors: vec![],
+            n: 0,
+        }
+    }
+}
+
+impl Pipeline {
+    /// Create a pipeline scoring by [`crate::edit_distance`], closest first, returning
+    /// every word.
+    pub fn new() -> Pipeline {
+        Pipeline::default()
+    }
+
+    /// Add a normalizer, run after those added before.
+    ///
+    /// # Arguments
+    ///
+    /// * `normalizer` - The normalizer to add.
+    pub fn normalize(mut self, normalizer: impl Normalizer + 'static) -> Self {
+        self.normalizers.push(Box::new(normalizer));
+        self
+    }
+
+    /// Add a prefilter. A word must pass every prefilter to be scored.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefilter` - The prefilter to add.
+    pub fn prefilter(mut self, prefilter: impl Prefilter + 'static) -> Self {
+        self.prefilters.push(Box::new(prefilter));
+        self
+    }
+
+    /// Set the scorer, replacing [`Ranking::EditDistance`].
+    ///
+    /// # Arguments
+    ///
+    /// * `scorer` - The scorer to use.
+    pub fn scorer(mut self, scorer: impl Scorer + 'static) -> Self {
+        self.scorer = Box::new(scorer);
+        self
+    }
+
+    /// Set the ranker, replacing [`ByDistance`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ranker` - The ranker to use.
+    pub fn ranker(mut self, ranker: impl Ranker + 'static) -> Self {
+        self.ranker = Box::new(ranker);
+        self
+    }
+
+    /// Add a post-processor, run after those added before.
+    ///
+    /// # Arguments
+    ///
+    /// * `post_processor` - The post-processor to add.
+    pub fn post_process(mut self, post_processor: impl PostProcessor + 'static) -> Self {
+        self.post_processors.push(Box::new(post_processor));
+        self
+    }
+
+    /// Set the number of suggestions to return, zero for all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of suggestions.
+    pub fn n(mut self, n: usize) -> Self {
+        self.n = n;
+        self
+    }
+
+    /// Return `word` put through every normalizer.
+    fn normalized<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        self.normalizers
+            .iter()
+            .fold(Cow::Borrowed(word), |word, normalizer| {
+                match normalizer.normalize(&word) {
+                    Cow::Borrowed(_) => word,
+                    Cow::Owned(normalized) => Cow::Owned(normalized),
+                }
+            })
+    }
+
+    /// Return the suggestions for `term` from the words of `sources`. A word in several
+    /// sources is suggested once, from the earliest of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    /// * `sources` - The word lists to search.
+    pub fn run<'a>(&self, term: &str, sources: &'a [Source]) -> Vec<Suggestion<'a>> {
+        let normalized_term = self.normalized(term);
+        let term_chars: Vec<char> = normalized_term.chars().collect();
+
+        // Words that cannot be ranked are dropped before boosting could make them look
+        // rankable.
+        let mut suggestions: Vec<Suggestion> = candidates(sources)
+            .filter_map(|((word, source), _)| {
+                let normalized = self.normalized(word);
+                self.prefilters
+                    .iter()
+                    .all(|prefilter| prefilter.keep(&normalized_term, &normalized))
+                    .then(|| self.scorer.score(&term_chars, &normalized))
+                    .filter(|&distance| distance != UNRANKED)
+                    .map(|distance| Suggestion {
+                        word,
+                        distance,
+                        source,
+                    })
+            })
+            .collect();
+
+        self.ranker.rank(term, &mut suggestions);
+        for post_processor in &self.post_processors {
+            post_processor.process(term, &mut suggestions);
+        }
+        if self.n > 0 {
+            suggestions.truncate(self.n);
+        }
+        suggestions
+    }
+}
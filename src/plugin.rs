@@ -0,0 +1,114 @@
+use crate::source::Suggestion;
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind, Write},
+    process::{Command, Stdio},
+};
+
+/// The version of the protocol spoken with plugins, sent with every request.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// A suggestion as a plugin returns it.
+#[derive(Deserialize)]
+struct Ranked {
+    word: String,
+    distance: Option<usize>,
+}
+
+/// Let the external scorer `command` rerank and filter `suggestions` for `term`.
+///
+/// The plugin is sent a line of JSON on its standard input holding the protocol version,
+/// the search term and the candidates in their current order:
+///
+/// ```json
+/// {"version": 1, "term": "helo", "candidates": [{"word": "hello", "distance": 1, "source": "en"}]}
+/// ```
+///
+/// It answers with a JSON array on its standard output holding the candidates to keep,
+/// best first, each with a new distance or, leaving it out, its old one:
+///
+/// ```json
+/// [{"word": "hello", "distance": 0}, {"word": "help"}]
+/// ```
+///
+/// Words that were not among the candidates are ignored, as suggestions only come from
+/// the word lists.
+///
+/// # Arguments
+///
+/// * `command` - The plugin process to run, with its arguments set.
+/// * `term` - The search term.
+/// * `suggestions` - The candidates, replaced by those the plugin kept.
+pub fn rescore<'a>(
+    mut command: Command,
+    term: &str,
+    suggestions: &mut Vec<Suggestion<'a>>,
+) -> Result<(), Error> {
+    let candidates: Vec<_> = suggestions
+        .iter()
+        .map(|suggestion| {
+            json!({
+                "word": suggestion.word,
+                "distance": suggestion.distance,
+                "source": suggestion.source,
+            })
+        })
+        .collect();
+    let request = json!({
+        "version": PROTOCOL_VERSION,
+        "term": term,
+        "candidates": candidates,
+    });
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // A plugin that answers without reading everything closes its end early, which is
+    // not an error.
+    if let Some(mut stdin) = child.stdin.take() {
+        match writeln!(stdin, "{}", request) {
+            Err(error) if error.kind() != ErrorKind::BrokenPipe => return Err(error),
+            _ => {}
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(Error::other(format!(
+            "The plugin failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let ranked: Vec<Ranked> = serde_json::from_slice(&output.stdout).map_err(|error| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("The plugin answered with invalid JSON: {}", error),
+        )
+    })?;
+
+    let mut by_word: HashMap<&str, Suggestion<'a>> = suggestions
+        .iter()
+        .map(|suggestion| (suggestion.word, *suggestion))
+        .collect();
+    *suggestions = ranked
+        .into_iter()
+        .filter_map(|ranked| {
+            let suggestion = by_word.remove(ranked.word.as_str());
+            if suggestion.is_none() {
+                tracing::debug!(word = ?ranked.word, "ignoring a word from the plugin, not a candidate");
+            }
+            suggestion.map(|suggestion| Suggestion {
+                distance: ranked.distance.unwrap_or(suggestion.distance),
+                ..suggestion
+            })
+        })
+        .collect();
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use phf::phf_map;
+
+/// Built-in presets, mapping a preset name to the shell command that prints its candidates.
+/// Presets of the same name in the configuration file take precedence.
+pub static BUILTIN_PRESETS: phf::Map<&'static str, &'static str> = phf_map! {
+    "git-branches" => "git for-each-ref --format='%(refname:short)' refs/heads refs/remotes",
+    "git-tags" => "git tag",
+    "k8s-pods" => "kubectl get pods -o name",
+    "k8s-deployments" => "kubectl get deployments -o name",
+    "k8s-services" => "kubectl get services -o name",
+    "docker-containers" => "docker ps -a --format '{{.Names}}'",
+    "docker-images" => "docker images --format '{{.Repository}}:{{.Tag}}'",
+};
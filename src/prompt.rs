@@ -0,0 +1,197 @@
+use colored::*;
+use console::{truncate_str, Key, Term};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::{Error, ErrorKind},
+};
+
+/// The most lines of a preview shown under the items.
+const PREVIEW_LINES: usize = 8;
+
+/// An interactive menu of items drawn on stderr, with configurable prompt text and keys.
+pub struct Menu<'a> {
+    prompt: &'a str,
+    items: &'a [String],
+    default_index: usize,
+    clear: bool,
+    vim_keys: bool,
+    preview: Option<Box<dyn Fn(usize) -> String + 'a>>,
+    previews: RefCell<HashMap<usize, String>>,
+}
+
+impl<'a> Menu<'a> {
+    /// Create a menu of `items` under `prompt`, highlighting the first item.
+    ///
+    /// # Arguments
+    ///
+    /// * `prompt` - The line shown above the items.
+    /// * `items` - The items to choose from.
+    pub fn new(prompt: &'a str, items: &'a [String]) -> Menu<'a> {
+        Menu {
+            prompt,
+            items,
+            default_index: 0,
+            clear: false,
+            vim_keys: true,
+            preview: None,
+            previews: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Highlight the item at `index` when the menu opens.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the item, clamped to the last one.
+    pub fn default_index(mut self, index: usize) -> Menu<'a> {
+        self.default_index = index;
+        self
+    }
+
+    /// Remove the menu from the terminal once a choice is made.
+    ///
+    /// # Arguments
+    ///
+    /// * `clear` - Whether to remove the menu.
+    pub fn clear(mut self, clear: bool) -> Menu<'a> {
+        self.clear = clear;
+        self
+    }
+
+    /// Move with `j` and `k` as well as the arrow keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `vim_keys` - Whether `j` and `k` move.
+    pub fn vim_keys(mut self, vim_keys: bool) -> Menu<'a> {
+        self.vim_keys = vim_keys;
+        self
+    }
+
+    /// Show the text `preview` returns for the highlighted item under the items. Each
+    /// item's preview is only asked for once.
+    ///
+    /// # Arguments
+    ///
+    /// * `preview` - Returns the preview of the item at the index it is given.
+    pub fn preview(mut self, preview: impl Fn(usize) -> String + 'a) -> Menu<'a> {
+        self.preview = Some(Box::new(preview));
+        self
+    }
+
+    /// Let the user choose one item, returning its index or `None` if they cancelled.
+    /// Pressing 1 to 9 chooses that item straight away.
+    pub fn select(&self) -> Result<Option<usize>, Error> {
+        Ok(self.interact(false)?.map(|indices| indices[0]))
+    }
+
+    /// Let the user toggle any number of items, returning their indices or `None` if they
+    /// cancelled. Pressing 1 to 9 toggles that item.
+    pub fn multi_select(&self) -> Result<Option<Vec<usize>>, Error> {
+        self.interact(true)
+    }
+
+    /// Draw the menu and handle keys until the user confirms or cancels.
+    fn interact(&self, multiple: bool) -> Result<Option<Vec<usize>>, Error> {
+        let term = Term::stderr();
+        if !term.is_term() {
+            return Err(Error::new(ErrorKind::NotConnected, "not a terminal"));
+        }
+        if self.items.is_empty() {
+            return Ok(None);
+        }
+
+        let len = self.items.len();
+        let mut cursor = self.default_index.min(len - 1);
+        let mut checked = vec![false; len];
+        let mut drawn = 0;
+
+        term.hide_cursor()?;
+        let chosen = loop {
+            if drawn > 0 {
+                term.clear_last_lines(drawn)?;
+            }
+            drawn = self.render(&term, cursor, multiple.then_some(&checked))?;
+
+            match term.read_key()? {
+                Key::ArrowUp => cursor = (cursor + len - 1) % len,
+                Key::ArrowDown => cursor = (cursor + 1) % len,
+                Key::Char('k') if self.vim_keys => cursor = (cursor + len - 1) % len,
+                Key::Char('j') if self.vim_keys => cursor = (cursor + 1) % len,
+                Key::Char(' ') if multiple => checked[cursor] = !checked[cursor],
+                // Digits pick the item of that number without moving to it first.
+                Key::Char(digit @ '1'..='9') => {
+                    let index = digit as usize - '1' as usize;
+                    if index >= len {
+                        continue;
+                    }
+                    if !multiple {
+                        break Some(vec![index]);
+                    }
+                    checked[index] = !checked[index];
+                    cursor = index;
+                }
+                Key::Enter if multiple => {
+                    break Some((0..len).filter(|i| checked[*i]).collect());
+                }
+                Key::Enter => break Some(vec![cursor]),
+                Key::Escape | Key::Char('q') => break None,
+                _ => {}
+            }
+        };
+
+        if self.clear {
+            term.clear_last_lines(drawn)?;
+        }
+        term.show_cursor()?;
+
+        Ok(chosen)
+    }
+
+    /// Draw the prompt and the items, returning the number of lines written.
+    fn render(
+        &self,
+        term: &Term,
+        cursor: usize,
+        checked: Option<&Vec<bool>>,
+    ) -> Result<usize, Error> {
+        term.write_line(&format!("{} {}", "?".yellow(), self.prompt.bold()))?;
+
+        for (i, item) in self.items.iter().enumerate() {
+            let pointer = if i == cursor {
+                "❯".green()
+            } else {
+                " ".normal()
+            };
+            let check = match checked {
+                Some(checked) if checked[i] => format!("{} ", "✔".green()),
+                Some(_) => format!("{} ", "⬚".dimmed()),
+                None => String::new(),
+            };
+            term.write_line(&format!("{} {}{}", pointer, check, item))?;
+        }
+
+        let mut lines = self.items.len() + 1;
+        if let Some(preview) = &self.preview {
+            let mut previews = self.previews.borrow_mut();
+            let text = previews.entry(cursor).or_insert_with(|| preview(cursor));
+
+            // Keep to the width of the terminal, so no line wraps and every line is
+            // cleared on the next draw.
+            let width = term.size().1 as usize;
+            term.write_line(&"─".repeat(width.min(40)).dimmed().to_string())?;
+            for line in text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .take(PREVIEW_LINES)
+            {
+                term.write_line(&truncate_str(line, width, "…").dimmed().to_string())?;
+                lines += 1;
+            }
+            lines += 1;
+        }
+
+        Ok(lines)
+    }
+}
@@ -0,0 +1,347 @@
+use std::io::{Error, ErrorKind, Read, Write};
+
+/// A MessagePack value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bin(Vec<u8>),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    /// An extension type and its data, such as a Neovim buffer handle.
+    Ext(i8, Vec<u8>),
+}
+
+impl Value {
+    /// Return the string held by the value, if it is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Return the integer held by the value, if it is an integer.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Return the values held by the value, if it is an array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::Str(s.to_string())
+    }
+}
+
+impl From<usize> for Value {
+    fn from(i: usize) -> Value {
+        Value::Int(i as i64)
+    }
+}
+
+/// Read `N` bytes from `reader`.
+fn read_bytes<const N: usize, R: Read>(reader: &mut R) -> Result<[u8; N], Error> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Read `len` bytes from `reader`.
+fn read_vec<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![];
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < len {
+        return Err(Error::from(ErrorKind::UnexpectedEof));
+    }
+    Ok(bytes)
+}
+
+/// Read a length of `size` bytes, big-endian, from `reader`.
+fn read_len<R: Read>(reader: &mut R, size: usize) -> Result<usize, Error> {
+    Ok(match size {
+        1 => u8::from_be_bytes(read_bytes(reader)?) as usize,
+        2 => u16::from_be_bytes(read_bytes(reader)?) as usize,
+        _ => u32::from_be_bytes(read_bytes(reader)?) as usize,
+    })
+}
+
+/// Read one MessagePack value from `reader`, or `None` if it is at its end.
+///
+/// # Arguments
+///
+/// * `reader` - Where to read the value from.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::rpc::{read_value, Value};
+/// let bytes = [0x92, 0x01, 0xa2, b'h', b'i'];
+/// let value = read_value(&mut &bytes[..]).unwrap();
+/// assert_eq!(value, Some(Value::Array(vec![Value::Int(1), Value::from("hi")])));
+/// assert_eq!(read_value(&mut &[][..]).unwrap(), None);
+/// ```
+pub fn read_value<R: Read>(reader: &mut R) -> Result<Option<Value>, Error> {
+    let mut marker = [0];
+    if reader.read(&mut marker)? == 0 {
+        return Ok(None);
+    }
+    decode(reader, marker[0]).map(Some)
+}
+
+/// Read the rest of the value that starts with `marker` from `reader`.
+fn decode<R: Read>(reader: &mut R, marker: u8) -> Result<Value, Error> {
+    let value = |reader: &mut R| match read_value(reader)? {
+        Some(value) => Ok(value),
+        None => Err(Error::from(ErrorKind::UnexpectedEof)),
+    };
+    let array = |reader: &mut R, len: usize| {
+        (0..len)
+            .map(|_| value(reader))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array)
+    };
+    let map = |reader: &mut R, len: usize| {
+        (0..len)
+            .map(|_| Ok((value(reader)?, value(reader)?)))
+            .collect::<Result<Vec<_>, Error>>()
+            .map(Value::Map)
+    };
+    let string = |bytes: Vec<u8>| {
+        String::from_utf8(bytes)
+            .map(Value::Str)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "string is not valid UTF-8"))
+    };
+    let ext = |reader: &mut R, len: usize| {
+        let [kind] = read_bytes(reader)?;
+        Ok(Value::Ext(kind as i8, read_vec(reader, len)?))
+    };
+
+    match marker {
+        0x00..=0x7f => Ok(Value::Int(marker as i64)),
+        0x80..=0x8f => map(reader, (marker & 0x0f) as usize),
+        0x90..=0x9f => array(reader, (marker & 0x0f) as usize),
+        0xa0..=0xbf => string(read_vec(reader, (marker & 0x1f) as usize)?),
+        0xc0 => Ok(Value::Nil),
+        0xc2 => Ok(Value::Bool(false)),
+        0xc3 => Ok(Value::Bool(true)),
+        0xc4..=0xc6 => {
+            let len = read_len(reader, 1 << (marker - 0xc4))?;
+            Ok(Value::Bin(read_vec(reader, len)?))
+        }
+        0xc7..=0xc9 => {
+            let len = read_len(reader, 1 << (marker - 0xc7))?;
+            ext(reader, len)
+        }
+        0xca => Ok(Value::Float(f32::from_be_bytes(read_bytes(reader)?) as f64)),
+        0xcb => Ok(Value::Float(f64::from_be_bytes(read_bytes(reader)?))),
+        0xcc => Ok(Value::Int(u8::from_be_bytes(read_bytes(reader)?) as i64)),
+        0xcd => Ok(Value::Int(u16::from_be_bytes(read_bytes(reader)?) as i64)),
+        0xce => Ok(Value::Int(u32::from_be_bytes(read_bytes(reader)?) as i64)),
+        0xcf => i64::try_from(u64::from_be_bytes(read_bytes(reader)?))
+            .map(Value::Int)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "integer out of range")),
+        0xd0 => Ok(Value::Int(i8::from_be_bytes(read_bytes(reader)?) as i64)),
+        0xd1 => Ok(Value::Int(i16::from_be_bytes(read_bytes(reader)?) as i64)),
+        0xd2 => Ok(Value::Int(i32::from_be_bytes(read_bytes(reader)?) as i64)),
+        0xd3 => Ok(Value::Int(i64::from_be_bytes(read_bytes(reader)?))),
+        0xd4..=0xd8 => ext(reader, 1 << (marker - 0xd4)),
+        0xd9..=0xdb => {
+            let len = read_len(reader, 1 << (marker - 0xd9))?;
+            string(read_vec(reader, len)?)
+        }
+        0xdc | 0xdd => {
+            let len = read_len(reader, if marker == 0xdc { 2 } else { 4 })?;
+            array(reader, len)
+        }
+        0xde | 0xdf => {
+            let len = read_len(reader, if marker == 0xde { 2 } else { 4 })?;
+            map(reader, len)
+        }
+        0xe0..=0xff => Ok(Value::Int(marker as i8 as i64)),
+        0xc1 => Err(Error::new(
+            ErrorKind::InvalidData,
+            "invalid MessagePack marker",
+        )),
+    }
+}
+
+/// Write the header of a string, binary, array or map of `len` items, choosing the
+/// smallest of the `markers` for 8, 16 and 32 bit lengths.
+fn write_len<W: Write>(writer: &mut W, len: usize, markers: [u8; 3]) -> Result<(), Error> {
+    match len {
+        0..=0xff if markers[0] != 0 => writer.write_all(&[markers[0], len as u8]),
+        0..=0xffff => {
+            writer.write_all(&[markers[1]])?;
+            writer.write_all(&(len as u16).to_be_bytes())
+        }
+        _ => {
+            writer.write_all(&[markers[2]])?;
+            writer.write_all(&(len as u32).to_be_bytes())
+        }
+    }
+}
+
+/// Write `value` to `writer` as MessagePack, in its most compact form.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the value.
+/// * `value` - The value to write.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::rpc::{write_value, Value};
+/// let mut bytes = vec![];
+/// write_value(&mut bytes, &Value::Array(vec![Value::Int(1), Value::from("hi")])).unwrap();
+/// assert_eq!(bytes, vec![0x92, 0x01, 0xa2, b'h', b'i']);
+/// ```
+pub fn write_value<W: Write>(writer: &mut W, value: &Value) -> Result<(), Error> {
+    match value {
+        Value::Nil => writer.write_all(&[0xc0]),
+        Value::Bool(b) => writer.write_all(&[if *b { 0xc3 } else { 0xc2 }]),
+        Value::Int(i) => match *i {
+            0..=0x7f => writer.write_all(&[*i as u8]),
+            -32..=-1 => writer.write_all(&[*i as i8 as u8]),
+            0x80..=0xff => writer.write_all(&[0xcc, *i as u8]),
+            0x100..=0xffff => {
+                writer.write_all(&[0xcd])?;
+                writer.write_all(&(*i as u16).to_be_bytes())
+            }
+            0x10000..=0xffff_ffff => {
+                writer.write_all(&[0xce])?;
+                writer.write_all(&(*i as u32).to_be_bytes())
+            }
+            -0x80..=-33 => writer.write_all(&[0xd0, *i as i8 as u8]),
+            -0x8000..=-0x81 => {
+                writer.write_all(&[0xd1])?;
+                writer.write_all(&(*i as i16).to_be_bytes())
+            }
+            -0x8000_0000..=-0x8001 => {
+                writer.write_all(&[0xd2])?;
+                writer.write_all(&(*i as i32).to_be_bytes())
+            }
+            _ => {
+                writer.write_all(&[0xd3])?;
+                writer.write_all(&i.to_be_bytes())
+            }
+        },
+        Value::Float(f) => {
+            writer.write_all(&[0xcb])?;
+            writer.write_all(&f.to_be_bytes())
+        }
+        Value::Str(s) => {
+            if s.len() < 32 {
+                writer.write_all(&[0xa0 | s.len() as u8])?;
+            } else {
+                write_len(writer, s.len(), [0xd9, 0xda, 0xdb])?;
+            }
+            writer.write_all(s.as_bytes())
+        }
+        Value::Bin(bytes) => {
+            write_len(writer, bytes.len(), [0xc4, 0xc5, 0xc6])?;
+            writer.write_all(bytes)
+        }
+        Value::Array(values) => {
+            if values.len() < 16 {
+                writer.write_all(&[0x90 | values.len() as u8])?;
+            } else {
+                write_len(writer, values.len(), [0, 0xdc, 0xdd])?;
+            }
+            values.iter().try_for_each(|v| write_value(writer, v))
+        }
+        Value::Map(entries) => {
+            if entries.len() < 16 {
+                writer.write_all(&[0x80 | entries.len() as u8])?;
+            } else {
+                write_len(writer, entries.len(), [0, 0xde, 0xdf])?;
+            }
+            entries.iter().try_for_each(|(k, v)| {
+                write_value(writer, k)?;
+                write_value(writer, v)
+            })
+        }
+        Value::Ext(kind, data) => {
+            write_len(writer, data.len(), [0xc7, 0xc8, 0xc9])?;
+            writer.write_all(&[*kind as u8])?;
+            writer.write_all(data)
+        }
+    }
+}
+
+/// Answer MessagePack-RPC requests read from `input` until it ends, writing the responses
+/// to `output`. Each request is answered with what `handle` returns for its method and
+/// parameters, an `Err` becoming the error of the response. Notifications are handled the
+/// same way, without a response.
+///
+/// # Arguments
+///
+/// * `input` - Where to read requests from, such as standard input.
+/// * `output` - Where to write responses to, such as standard output.
+/// * `handle` - Returns the result of calling a method with the given parameters.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::rpc::{read_value, serve, write_value, Value};
+/// let mut input = vec![];
+/// let request = Value::Array(vec![0.into(), 7.into(), "echo".into(), Value::Array(vec!["hi".into()])]);
+/// write_value(&mut input, &request).unwrap();
+///
+/// let mut output = vec![];
+/// serve(&input[..], &mut output, |method, params| match method {
+///     "echo" => Ok(params[0].clone()),
+///     _ => Err(format!("no method {}", method)),
+/// })
+/// .unwrap();
+///
+/// let response = read_value(&mut &output[..]).unwrap().unwrap();
+/// assert_eq!(response, Value::Array(vec![1.into(), 7.into(), Value::Nil, "hi".into()]));
+/// ```
+pub fn serve<R, W, F>(mut input: R, mut output: W, mut handle: F) -> Result<(), Error>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&str, &[Value]) -> Result<Value, String>,
+{
+    let malformed = || Error::new(ErrorKind::InvalidData, "malformed MessagePack-RPC message");
+
+    while let Some(message) = read_value(&mut input)? {
+        let message = message.as_array().ok_or_else(malformed)?;
+        match message {
+            // A request: [0, id, method, params]
+            [Value::Int(0), id, Value::Str(method), Value::Array(params)] => {
+                let (error, result) = match handle(method, params) {
+                    Ok(result) => (Value::Nil, result),
+                    Err(error) => (Value::Str(error), Value::Nil),
+                };
+                let response = Value::Array(vec![Value::Int(1), id.clone(), error, result]);
+                write_value(&mut output, &response)?;
+                output.flush()?;
+            }
+            // A notification: [2, method, params]
+            [Value::Int(2), Value::Str(method), Value::Array(params)] => {
+                if let Err(error) = handle(method, params) {
+                    tracing::warn!(method, %error, "notification failed");
+                }
+            }
+            _ => return Err(malformed()),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use crate::{
+    source::{Source, Suggestion},
+    symspell::fingerprint,
+    Costs, MatchOptions, Ranking,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{read_to_string, write},
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+/// The version of the session format written by [`Session::save`].
+pub const FORMAT: u32 = 1;
+
+/// A word list searched in a recorded [`Session`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SourceRecord {
+    /// The name of the source.
+    pub name: String,
+    /// The number of words in the source.
+    pub words: usize,
+    /// The [`fingerprint`] of the words, in hexadecimal.
+    pub fingerprint: String,
+}
+
+impl SourceRecord {
+    /// Create the record of `source`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to record.
+    pub fn new(source: &Source) -> SourceRecord {
+        SourceRecord {
+            name: source.name.clone(),
+            words: source.words.len(),
+            fingerprint: format!("{:016x}", fingerprint(&source.words)),
+        }
+    }
+}
+
+/// How a recorded [`Session`] looked for words besides ranking the closest ones, so that
+/// replaying it runs the same search.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct SearchMode {
+    /// The ending words were searched for with, instead of the term.
+    pub suffix: Option<String>,
+    /// Whether words rhyming with the term were searched for.
+    pub rhyme: bool,
+    /// The number of letters anagrams of the term could differ by, if they were searched
+    /// for.
+    pub anagrams: Option<usize>,
+    /// Whether the words the term starts were suggested when it looked abbreviated.
+    pub complete: bool,
+    /// Whether ways of splitting the term into two words were suggested.
+    pub split: bool,
+    /// The command the suggestions were reranked with.
+    pub plugin: Option<String>,
+    /// The words whose distance was lowered.
+    pub boost_words: Vec<String>,
+    /// The largest difference in length between the term and the words searched.
+    pub max_length_difference: Option<usize>,
+}
+
+/// A suggestion of a recorded [`Session`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankedWord {
+    /// The suggested word.
+    pub word: String,
+    /// Its distance from the search term.
+    pub distance: usize,
+    /// The name of the source it came from.
+    pub source: String,
+}
+
+impl From<&Suggestion<'_>> for RankedWord {
+    fn from(suggestion: &Suggestion) -> RankedWord {
+        RankedWord {
+            word: suggestion.word.to_string(),
+            distance: suggestion.distance,
+            source: suggestion.source.to_string(),
+        }
+    }
+}
+
+/// A search and the ranking it gave, saved to reproduce the search later and tell whether
+/// a different version or word list ranks it differently.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{session::Session, source::{top_n_merged, Source, SourceKind}, MatchOptions, Ranking};
+/// let sources = [Source::new(SourceKind::Custom, "words", vec!["hello".into(), "help".into()])];
+/// let suggestions = top_n_merged("helo", &sources, 2, Ranking::Lcs, MatchOptions::default());
+/// let session = Session::new("helo", 2, Ranking::Lcs, MatchOptions::default(), false, &sources, &suggestions);
+///
+/// assert_eq!(session.ranking(), Ok(Ranking::Lcs));
+/// assert!(session.changed_sources(&sources).is_empty());
+/// assert_eq!(session.suggestions[0].word, "hello");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Session {
+    /// The version of the session format.
+    pub format: u32,
+    /// The version of didyoumean that recorded the session.
+    pub dym_version: String,
+    /// The search term.
+    pub term: String,
+    /// The number of suggestions asked for, zero for all close enough.
+    pub number: usize,
+    /// The ranking searched with, as returned by [`ranking_name`].
+    pub ranking: String,
+    /// Whether case was ignored.
+    pub ignore_case: bool,
+    /// Whether accents were ignored.
+    pub ignore_accents: bool,
+    /// Whether suggestions too far from the term were dropped.
+    pub auto_threshold: bool,
+    /// How the words were searched for. Sessions recorded before it was have the default.
+    #[serde(default)]
+    pub mode: SearchMode,
+    /// The word lists searched.
+    pub sources: Vec<SourceRecord>,
+    /// The suggestions, in order.
+    pub suggestions: Vec<RankedWord>,
+}
+
+impl Session {
+    /// Record a search for `term` over `sources` and the `suggestions` it gave.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The search term.
+    /// * `number` - The number of suggestions asked for.
+    /// * `ranking` - The ranking searched with.
+    /// * `options` - The differences ignored.
+    /// * `auto_threshold` - Whether suggestions too far from the term were dropped.
+    /// * `sources` - The word lists searched.
+    /// * `suggestions` - The suggestions, in order.
+    pub fn new(
+        term: &str,
+        number: usize,
+        ranking: Ranking,
+        options: MatchOptions,
+        auto_threshold: bool,
+        sources: &[Source],
+        suggestions: &[Suggestion],
+    ) -> Session {
+        Session {
+            format: FORMAT,
+            dym_version: env!("CARGO_PKG_VERSION").to_string(),
+            term: term.to_string(),
+            number,
+            ranking: ranking_name(ranking),
+            ignore_case: options.ignore_case,
+            ignore_accents: options.ignore_accents,
+            auto_threshold,
+            mode: SearchMode::default(),
+            sources: sources.iter().map(SourceRecord::new).collect(),
+            suggestions: suggestions.iter().map(RankedWord::from).collect(),
+        }
+    }
+
+    /// Record that the search was made in `mode` rather than by ranking the closest words
+    /// alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How the words were searched for.
+    pub fn with_mode(mut self, mode: SearchMode) -> Session {
+        self.mode = mode;
+        self
+    }
+
+    /// Return the ranking the session was recorded with.
+    pub fn ranking(&self) -> Result<Ranking, String> {
+        parse_ranking(&self.ranking)
+    }
+
+    /// Return the differences the session ignored.
+    pub fn options(&self) -> MatchOptions {
+        MatchOptions {
+            ignore_case: self.ignore_case,
+            ignore_accents: self.ignore_accents,
+        }
+    }
+
+    /// Return the names of the recorded sources that `sources` lacks or holds different
+    /// words for, followed by those of `sources` that were not recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - The word lists searched now.
+    pub fn changed_sources(&self, sources: &[Source]) -> Vec<String> {
+        let now: Vec<SourceRecord> = sources.iter().map(SourceRecord::new).collect();
+        self.sources
+            .iter()
+            .filter(|recorded| !now.contains(recorded))
+            .chain(
+                now.iter()
+                    .filter(|source| self.sources.iter().all(|r| r.name != source.name)),
+            )
+            .map(|source| source.name.clone())
+            .collect()
+    }
+
+    /// Save the session as JSON to the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        write(path, contents)
+    }
+
+    /// Load a session saved with [`Session::save`] from the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read.
+    pub fn load(path: &Path) -> Result<Session, Error> {
+        let session: Session = serde_json::from_str(&read_to_string(path)?).map_err(|error| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("{} is not a recorded session: {}", path.display(), error),
+            )
+        })?;
+        if session.format != FORMAT {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "{} was recorded in session format {}, this version reads {}",
+                    path.display(),
+                    session.format,
+                    FORMAT
+                ),
+            ));
+        }
+
+        Ok(session)
+    }
+}
+
+/// Return the name `ranking` is recorded under, which [`parse_ranking`] reads back.
+///
+/// # Arguments
+///
+/// * `ranking` - The ranking to name.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{session::{parse_ranking, ranking_name}, Costs, Ranking};
+/// assert_eq!(ranking_name(Ranking::JaroWinkler), "jaro-winkler");
+/// let costs = Ranking::Costs(Costs { insert: 2, ..Costs::default() });
+/// assert_eq!(ranking_name(costs), "costs:2,1,1,1");
+/// assert_eq!(parse_ranking("costs:2,1,1,1"), Ok(costs));
+/// ```
+pub fn ranking_name(ranking: Ranking) -> String {
+    match ranking {
+        Ranking::EditDistance => "edit-distance".to_string(),
+        Ranking::JaroWinkler => "jaro-winkler".to_string(),
+        Ranking::Soundex => "soundex".to_string(),
+        Ranking::Metaphone => "metaphone".to_string(),
+        Ranking::Keyboard => "keyboard".to_string(),
+        Ranking::Costs(costs) => format!(
+            "costs:{},{},{},{}",
+            costs.insert, costs.delete, costs.substitute, costs.transpose
+        ),
+        Ranking::Ngram => "ngram".to_string(),
+        Ranking::Lcs => "lcs".to_string(),
+    }
+}
+
+/// Return the ranking called `name` by [`ranking_name`].
+///
+/// # Arguments
+///
+/// * `name` - The name of the ranking.
+pub fn parse_ranking(name: &str) -> Result<Ranking, String> {
+    let ranking = match name {
+        "edit-distance" => Ranking::EditDistance,
+        "jaro-winkler" => Ranking::JaroWinkler,
+        "soundex" => Ranking::Soundex,
+        "metaphone" => Ranking::Metaphone,
+        "keyboard" => Ranking::Keyboard,
+        "ngram" => Ranking::Ngram,
+        "lcs" => Ranking::Lcs,
+        _ => {
+            let costs = name
+                .strip_prefix("costs:")
+                .map(|costs| {
+                    costs
+                        .split(',')
+                        .map(str::parse)
+                        .collect::<Result<Vec<usize>, _>>()
+                })
+                .and_then(Result::ok);
+            match costs.as_deref() {
+                Some(&[insert, delete, substitute, transpose]) => Ranking::Costs(Costs {
+                    insert,
+                    delete,
+                    substitute,
+                    transpose,
+                }),
+                _ => return Err(format!("{} is not a ranking", name)),
+            }
+        }
+    };
+
+    Ok(ranking)
+}
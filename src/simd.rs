@@ -0,0 +1,92 @@
+use std::arch::x86_64::*;
+
+/// The number of words compared at once, one in each 16-bit lane of an AVX2 register.
+pub(crate) const LANES: usize = 16;
+
+/// The longest words compared, so that no distance overflows a lane.
+pub(crate) const MAX_LEN: usize = 1024;
+
+/// Return whether this CPU can run [`edit_distances`].
+pub(crate) fn available() -> bool {
+    is_x86_feature_detected!("avx2")
+}
+
+/// Return the edit distances between `search` and each of up to [`LANES`] words in
+/// `known`, like [`crate::edit_distance`], running the dynamic programming of all the words
+/// side by side. Words are ASCII bytes no longer than [`MAX_LEN`], and distances past the
+/// number of words are zero.
+///
+/// # Safety
+///
+/// The CPU must support AVX2, as [`available`] checks.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn edit_distances(search: &[u8], known: &[&[u8]]) -> [usize; LANES] {
+    let n = search.len();
+    let longest = known.iter().map(|word| word.len()).max().unwrap_or(0);
+    let one = _mm256_set1_epi16(1);
+    let all_set = _mm256_set1_epi16(-1);
+
+    // Three columns of the matrix, for every word at once: cell i holds the distance
+    // between the first i characters of `search` and the first j characters of each word.
+    let mut before_last = vec![_mm256_setzero_si256(); n + 1];
+    let mut last: Vec<__m256i> = (0..=n).map(|i| _mm256_set1_epi16(i as i16)).collect();
+    let mut column = vec![_mm256_setzero_si256(); n + 1];
+
+    let mut distances = [0; LANES];
+    for (k, word) in known.iter().enumerate() {
+        if word.is_empty() {
+            distances[k] = n;
+        }
+    }
+
+    let mut last_chars = _mm256_setzero_si256();
+    for j in 1..=longest {
+        // Words that have ended are padded with a value no ASCII character equals.
+        let mut lane_chars = [-1i16; LANES];
+        for (k, word) in known.iter().enumerate() {
+            if let Some(c) = word.get(j - 1) {
+                lane_chars[k] = *c as i16;
+            }
+        }
+        let chars = _mm256_loadu_si256(lane_chars.as_ptr() as *const __m256i);
+
+        column[0] = _mm256_set1_epi16(j as i16);
+        for i in 1..=n {
+            let search_char = _mm256_set1_epi16(search[i - 1] as i16);
+            let equal = _mm256_cmpeq_epi16(search_char, chars);
+
+            // Substitution costs one where the characters differ.
+            let mut cell = _mm256_adds_epu16(last[i - 1], _mm256_andnot_si256(equal, one));
+            cell = _mm256_min_epu16(cell, _mm256_adds_epu16(last[i], one));
+            cell = _mm256_min_epu16(cell, _mm256_adds_epu16(column[i - 1], one));
+            if i > 1 && j > 1 {
+                let swapped = _mm256_and_si256(
+                    _mm256_cmpeq_epi16(search_char, last_chars),
+                    _mm256_cmpeq_epi16(_mm256_set1_epi16(search[i - 2] as i16), chars),
+                );
+                // Lanes without a transposition get the largest value, which min ignores.
+                let transposition = _mm256_or_si256(
+                    _mm256_adds_epu16(before_last[i - 2], one),
+                    _mm256_andnot_si256(swapped, all_set),
+                );
+                cell = _mm256_min_epu16(cell, transposition);
+            }
+            column[i] = cell;
+        }
+
+        // Words of length j are done.
+        let mut lanes = [0u16; LANES];
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, column[n]);
+        for (k, word) in known.iter().enumerate() {
+            if word.len() == j {
+                distances[k] = lanes[k] as usize;
+            }
+        }
+
+        std::mem::swap(&mut before_last, &mut last);
+        std::mem::swap(&mut last, &mut column);
+        last_chars = chars;
+    }
+
+    distances
+}
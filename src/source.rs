@@ -0,0 +1,343 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{edit_distance, top_n_by, within_by, MatchOptions, Ranking};
+
+/// The file name of project word lists, looked for in the current directory and its
+/// ancestors.
+pub const PROJECT_WORDLIST: &str = ".dym-words";
+
+/// The kinds of word lists, in their default priority order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SourceKind {
+    /// The user's own word list.
+    Personal,
+    /// A word list kept in a project directory.
+    Project,
+    /// A word list given on the command line.
+    Custom,
+    /// A downloaded language.
+    Language,
+    /// Words from anywhere else, such as a command or the environment.
+    Other,
+}
+
+impl SourceKind {
+    /// Every kind, in the default priority order.
+    pub const ALL: [SourceKind; 5] = [
+        SourceKind::Personal,
+        SourceKind::Project,
+        SourceKind::Custom,
+        SourceKind::Language,
+        SourceKind::Other,
+    ];
+}
+
+impl fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SourceKind::Personal => "personal",
+            SourceKind::Project => "project",
+            SourceKind::Custom => "custom",
+            SourceKind::Language => "language",
+            SourceKind::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for SourceKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<SourceKind, String> {
+        SourceKind::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == name)
+            .ok_or_else(|| format!("{} is not a kind of word list", name))
+    }
+}
+
+/// A named list of words, such as a language, the personal word list or a custom file,
+/// searched alongside others.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Source {
+    /// The kind of word list the source is.
+    pub kind: SourceKind,
+    /// The name the source is reported by.
+    pub name: String,
+    /// The words of the source.
+    pub words: Vec<String>,
+}
+
+impl Source {
+    /// Create a source of `kind` called `name` holding `words`.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of word list the source is.
+    /// * `name` - The name the source is reported by.
+    /// * `words` - The words of the source.
+    pub fn new(kind: SourceKind, name: &str, words: Vec<String>) -> Source {
+        Source {
+            kind,
+            name: name.to_string(),
+            words,
+        }
+    }
+}
+
+/// Return the project word list closest to `start`, looking in `start` and then in each
+/// of its ancestors.
+///
+/// # Arguments
+///
+/// * `start` - The directory to start looking from.
+pub fn find_project_wordlist(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_WORDLIST))
+        .find(|path| path.is_file())
+}
+
+/// Order `sources` by the position of their kind in `priority`, so that words from
+/// higher priority sources rank first at equal distance. Kinds missing from `priority`
+/// go last, and sources of the same kind keep their order.
+///
+/// # Arguments
+///
+/// * `sources` - The sources to order.
+/// * `priority` - The kinds of source, highest priority first.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::source::{sort_by_priority, Source, SourceKind};
+/// let mut sources = [
+///     Source::new(SourceKind::Language, "en", vec![]),
+///     Source::new(SourceKind::Personal, "personal", vec![]),
+///     Source::new(SourceKind::Project, ".dym-words", vec![]),
+/// ];
+/// sort_by_priority(&mut sources, &[SourceKind::Project, SourceKind::Personal]);
+/// let names: Vec<&str> = sources.iter().map(|source| source.name.as_str()).collect();
+/// assert_eq!(names, vec![".dym-words", "personal", "en"]);
+/// ```
+pub fn sort_by_priority(sources: &mut [Source], priority: &[SourceKind]) {
+    sources.sort_by_key(|source| {
+        priority
+            .iter()
+            .position(|kind| *kind == source.kind)
+            .unwrap_or(priority.len())
+    });
+}
+
+/// A suggested word, with where it came from. Serialized as the objects of the `--json`
+/// output.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::source::Suggestion;
+/// let suggestion = Suggestion { word: "hello", distance: 1, source: "en" };
+/// let json = serde_json::to_string(&suggestion).unwrap();
+/// assert_eq!(json, r#"{"word":"hello","distance":1,"source":"en"}"#);
+/// assert_eq!(serde_json::from_str::<Suggestion>(&json).unwrap(), suggestion);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion<'a> {
+    /// The suggested word.
+    pub word: &'a str,
+    /// The distance between the search term and the word, under the ranking searched
+    /// with.
+    pub distance: usize,
+    /// The name of the source the word came from.
+    pub source: &'a str,
+}
+
+/// Return the `n` words from any of `sources` closest to `term` under `ranking` and
+/// `options`, closest first. Ties go to the earlier source, and a word in several sources
+/// is suggested once, from the earliest of them.
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `sources` - The sources to search.
+/// * `n` - The number of suggestions to return.
+/// * `ranking` - How to measure the distance between `term` and each word.
+/// * `options` - Which differences between `term` and each word to ignore.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{source::{top_n_merged, Source, SourceKind}, MatchOptions, Ranking};
+/// let sources = [
+///     Source::new(SourceKind::Personal, "personal", vec!["kubectl".to_string()]),
+///     Source::new(SourceKind::Language, "en", vec!["cube".to_string(), "kettle".to_string()]),
+/// ];
+/// let suggestions = top_n_merged("kubetcl", &sources, 2, Ranking::EditDistance, MatchOptions::default());
+/// assert_eq!((suggestions[0].word, suggestions[0].source), ("kubectl", "personal"));
+/// assert_eq!((suggestions[1].word, suggestions[1].source), ("cube", "en"));
+///
+/// let sources = [
+///     Source::new(SourceKind::Personal, "personal", vec!["cube".to_string()]),
+///     Source::new(SourceKind::Language, "en", vec!["cube".to_string(), "cub".to_string()]),
+/// ];
+/// let suggestions = top_n_merged("cubes", &sources, 2, Ranking::EditDistance, MatchOptions::default());
+/// assert_eq!((suggestions[0].word, suggestions[0].source), ("cube", "personal"));
+/// assert_eq!((suggestions[1].word, suggestions[1].source), ("cub", "en"));
+/// ```
+pub fn top_n_merged<'a>(
+    term: &str,
+    sources: &'a [Source],
+    n: usize,
+    ranking: Ranking,
+    options: MatchOptions,
+) -> Vec<Suggestion<'a>> {
+    top_n_by(term, candidates(sources), n, ranking, options)
+        .into_iter()
+        .map(|((word, source), distance)| Suggestion {
+            word,
+            distance,
+            source,
+        })
+        .collect()
+}
+
+/// Return every word from any of `sources` at most `max_distance` from `term` under
+/// `ranking` and `options`, closest first, merged like [`top_n_merged`].
+///
+/// # Arguments
+///
+/// * `term` - The possibly misspelled term.
+/// * `sources` - The sources to search.
+/// * `max_distance` - The largest distance to return words at.
+/// * `ranking` - How to measure the distance between `term` and each word.
+/// * `options` - Which differences between `term` and each word to ignore.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{source::{within_merged, Source, SourceKind}, MatchOptions, Ranking};
+/// let words = ["cat", "cut", "cart", "dog"].map(String::from).to_vec();
+/// let sources = [Source::new(SourceKind::Language, "en", words)];
+/// let suggestions = within_merged("cat", &sources, 1, Ranking::EditDistance, MatchOptions::default());
+/// let words: Vec<&str> = suggestions.iter().map(|suggestion| suggestion.word).collect();
+/// assert_eq!(words, vec!["cat", "cut", "cart"]);
+/// ```
+pub fn within_merged<'a>(
+    term: &str,
+    sources: &'a [Source],
+    max_distance: usize,
+    ranking: Ranking,
+    options: MatchOptions,
+) -> Vec<Suggestion<'a>> {
+    within_by(term, candidates(sources), max_distance, ranking, options)
+        .into_iter()
+        .map(|((word, source), distance)| Suggestion {
+            word,
+            distance,
+            source,
+        })
+        .collect()
+}
+
+/// Return how many distinct words of `sources` are at each edit distance from `term`
+/// under `options`, from zero to `max_distance`, followed by the number of words further
+/// away.
+///
+/// # Arguments
+///
+/// * `term` - The term to measure distances from.
+/// * `sources` - The sources to count the words of.
+/// * `max_distance` - The largest distance to count words at separately.
+/// * `options` - Which differences between `term` and each word to ignore.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{source::{distance_histogram, Source, SourceKind}, MatchOptions};
+/// let sources = [
+///     Source::new(SourceKind::Personal, "personal", vec!["cat".to_string()]),
+///     Source::new(SourceKind::Language, "en", ["cat", "cut", "cart", "dog"].map(String::from).to_vec()),
+/// ];
+/// let histogram = distance_histogram("cat", &sources, 2, MatchOptions::default());
+/// assert_eq!(histogram, vec![1, 2, 0, 1]);
+/// ```
+pub fn distance_histogram(
+    term: &str,
+    sources: &[Source],
+    max_distance: usize,
+    options: MatchOptions,
+) -> Vec<usize> {
+    let term = options.normalize(term);
+    let term_chars: Vec<char> = term.chars().collect();
+    let mut histogram = vec![0; max_distance + 2];
+    for (_, word) in candidates(sources) {
+        let dist = edit_distance(&term_chars, &options.normalize(word));
+        histogram[dist.min(max_distance + 1)] += 1;
+    }
+    histogram
+}
+
+/// Return the ways of splitting `term` into two words of `sources`, such as "hello world"
+/// for "helloworld", as the two words joined by a space, in the order of the split point.
+/// Halves are compared under `options` and spelled as in the earliest source holding them.
+///
+/// # Arguments
+///
+/// * `term` - The term that may be missing a space.
+/// * `sources` - The sources to find the halves in.
+/// * `options` - Which differences between the halves and each word to ignore.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::{source::{two_word_splits, Source, SourceKind}, MatchOptions};
+/// let words = ["hello", "world", "low", "hell", "oworld"].map(String::from).to_vec();
+/// let sources = [Source::new(SourceKind::Language, "en", words)];
+/// let splits = two_word_splits("helloworld", &sources, MatchOptions::default());
+/// assert_eq!(splits, vec!["hell oworld", "hello world"]);
+///
+/// let options = MatchOptions { ignore_case: true, ..MatchOptions::default() };
+/// assert_eq!(two_word_splits("HelloWorld", &sources, options), vec!["hell oworld", "hello world"]);
+/// ```
+pub fn two_word_splits(term: &str, sources: &[Source], options: MatchOptions) -> Vec<String> {
+    // The first spelling of every word, by how it compares.
+    let mut words = HashMap::new();
+    for (_, word) in candidates(sources) {
+        words.entry(options.normalize(word)).or_insert(word);
+    }
+
+    term.char_indices()
+        .skip(1)
+        .filter_map(|(i, _)| {
+            let (first, second) = term.split_at(i);
+            let first = words.get(&options.normalize(first))?;
+            let second = words.get(&options.normalize(second))?;
+            Some(format!("{} {}", first, second))
+        })
+        .collect()
+}
+
+/// Return the words of `sources` with the names of their sources, leaving out words
+/// already in an earlier source, which has the better claim to them.
+///
+/// # Arguments
+///
+/// * `sources` - The sources to take the words of.
+pub(crate) fn candidates(sources: &[Source]) -> impl Iterator<Item = ((&str, &str), &str)> {
+    let mut seen = HashSet::new();
+    sources
+        .iter()
+        .flat_map(|source| {
+            source
+                .words
+                .iter()
+                .map(move |word| ((word.as_str(), source.name.as_str()), word.as_str()))
+        })
+        .filter(move |(_, word)| sources.len() == 1 || seen.insert(*word))
+}
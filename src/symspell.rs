@@ -0,0 +1,276 @@
+use crate::edit_distance;
+use std::{
+    collections::HashSet,
+    io::{Cursor, Error, ErrorKind, Read, Seek, SeekFrom, Write},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The bytes a SymSpell index starts with.
+const MAGIC: &[u8; 4] = b"DYMS";
+
+/// The version of the file format written by [`write_index`].
+const VERSION: u32 = 1;
+
+/// The size of the header before the entries: the magic bytes, the version, the largest
+/// distance, the fingerprint, the number of words and the number of entries.
+const HEADER_LEN: u64 = 4 + 4 + 4 + 8 + 8 + 8;
+
+/// The size of an entry: the hash of a delete variant and the index of its word.
+const ENTRY_LEN: u64 = 8 + 4;
+
+/// Return the 64-bit FNV-1a hash of `bytes`, which unlike the standard library's hashers
+/// is the same on every platform and Rust version, so it can be saved to disk.
+fn fnv1a(bytes: &[u8], mut hash: u64) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// The starting value of an FNV-1a hash.
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// Return a hash of `words` and their order, saved in an index to tell whether it was
+/// built from the same word list.
+///
+/// # Arguments
+///
+/// * `words` - The words of the word list.
+pub fn fingerprint(words: &[String]) -> u64 {
+    words.iter().fold(FNV_OFFSET, |hash, word| {
+        fnv1a(b"\n", fnv1a(word.as_bytes(), hash))
+    })
+}
+
+/// Return `word` and every string made by deleting up to `k` of its graphemes.
+///
+/// # Arguments
+///
+/// * `word` - The word to delete from.
+/// * `k` - The most graphemes to delete.
+fn deletes(word: &str, k: usize) -> HashSet<String> {
+    let mut found = HashSet::from([word.to_string()]);
+    let mut frontier = vec![word.graphemes(true).collect::<Vec<&str>>()];
+    for _ in 0..k {
+        let mut next = vec![];
+        for graphemes in &frontier {
+            for i in 0..graphemes.len() {
+                let mut variant = graphemes.clone();
+                variant.remove(i);
+                if found.insert(variant.concat()) {
+                    next.push(variant);
+                }
+            }
+        }
+        frontier = next;
+    }
+    found
+}
+
+/// Write a SymSpell index of `words` to `writer`, for finding the words at most
+/// `max_distance` edits from a term. Every string made by deleting up to `max_distance`
+/// graphemes from a word is hashed and stored with the word's index, sorted by hash. Two
+/// words within `max_distance` of each other always share such a string, so a search only
+/// looks up the variants of the term instead of comparing it to every word.
+///
+/// # Arguments
+///
+/// * `words` - The words to index.
+/// * `max_distance` - The largest distance the index can find words at.
+/// * `writer` - Where to write the index.
+#[tracing::instrument(level = "debug", skip_all, fields(words = words.len(), max_distance))]
+pub fn write_index<W: Write>(
+    words: &[String],
+    max_distance: usize,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let mut entries: Vec<(u64, u32)> = vec![];
+    for (index, word) in words.iter().enumerate() {
+        for variant in deletes(word, max_distance) {
+            entries.push((fnv1a(variant.as_bytes(), FNV_OFFSET), index as u32));
+        }
+    }
+    entries.sort_unstable();
+    entries.dedup();
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&(max_distance as u32).to_le_bytes())?;
+    writer.write_all(&fingerprint(words).to_le_bytes())?;
+    writer.write_all(&(words.len() as u64).to_le_bytes())?;
+    writer.write_all(&(entries.len() as u64).to_le_bytes())?;
+    for (hash, index) in entries {
+        writer.write_all(&hash.to_le_bytes())?;
+        writer.write_all(&index.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// A SymSpell index written by [`write_index`], searched in place so that a lookup reads
+/// only the few entries it needs rather than the whole index.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::symspell::SymSpell;
+/// let words = ["cat", "cut", "cart", "dog"].map(String::from).to_vec();
+/// let mut index = SymSpell::build(&words, 2).unwrap();
+/// assert_eq!(index.find_within("cat", &words, 1).unwrap(), vec![(0, 0), (1, 1), (2, 1)]);
+/// assert_eq!(index.find_within("dgo", &words, 1).unwrap(), vec![(3, 1)]);
+/// ```
+#[derive(Debug)]
+pub struct SymSpell<R> {
+    reader: R,
+    max_distance: usize,
+    fingerprint: u64,
+    word_count: usize,
+    entry_count: u64,
+}
+
+impl SymSpell<Cursor<Vec<u8>>> {
+    /// Build an index of `words` in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to index.
+    /// * `max_distance` - The largest distance the index can find words at.
+    pub fn build(words: &[String], max_distance: usize) -> Result<Self, Error> {
+        let mut bytes = vec![];
+        write_index(words, max_distance, &mut bytes)?;
+        SymSpell::open(Cursor::new(bytes))
+    }
+}
+
+impl<R: Read + Seek> SymSpell<R> {
+    /// Open the index in `reader`, reading only its header.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The index, such as an open file.
+    pub fn open(mut reader: R) -> Result<Self, Error> {
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message.to_string());
+
+        let mut header = [0; HEADER_LEN as usize];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not a dym SymSpell index"));
+        }
+        let u32_at = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(header[at..at + 8].try_into().unwrap());
+        if u32_at(4) != VERSION {
+            return Err(invalid("unsupported dym SymSpell index version"));
+        }
+
+        let entry_count = u64_at(28);
+        let len = reader.seek(SeekFrom::End(0))?;
+        if len != HEADER_LEN + entry_count * ENTRY_LEN {
+            return Err(invalid("truncated dym SymSpell index"));
+        }
+
+        Ok(SymSpell {
+            reader,
+            max_distance: u32_at(8) as usize,
+            fingerprint: u64_at(12),
+            word_count: u64_at(20) as usize,
+            entry_count,
+        })
+    }
+
+    /// Return the largest distance the index can find words at.
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    /// Return the [`fingerprint`] of the words the index was built from.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Return the number of words the index was built from.
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    /// Return the indices of the words that may be at most `k` edits from `term`, in
+    /// order. All of the words that are within `k` are returned, along with a few that
+    /// are not. `k` is capped at the distance the index was built for.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    /// * `k` - The largest distance to look for words at.
+    pub fn candidates(&mut self, term: &str, k: usize) -> Result<Vec<usize>, Error> {
+        let mut found = vec![];
+        for variant in deletes(term, k.min(self.max_distance)) {
+            let hash = fnv1a(variant.as_bytes(), FNV_OFFSET);
+            let mut at = self.lower_bound(hash)?;
+            while at < self.entry_count {
+                let (entry_hash, index) = self.entry(at)?;
+                if entry_hash != hash {
+                    break;
+                }
+                found.push(index as usize);
+                at += 1;
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+        Ok(found)
+    }
+
+    /// Return the indices of `words` at most `k` edits from `term` with their distances,
+    /// by [`crate::edit_distance`], in the order of `words`. `words` must be the words the
+    /// index was built from, as [`SymSpell::fingerprint`] can check, and `k` is capped at
+    /// the distance the index was built for.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The possibly misspelled term.
+    /// * `words` - The words the index was built from.
+    /// * `k` - The largest distance to return words at.
+    pub fn find_within(
+        &mut self,
+        term: &str,
+        words: &[String],
+        k: usize,
+    ) -> Result<Vec<(usize, usize)>, Error> {
+        let k = k.min(self.max_distance);
+        let term_chars: Vec<char> = term.chars().collect();
+        Ok(self
+            .candidates(term, k)?
+            .into_iter()
+            .filter_map(|index| {
+                let dist = edit_distance(&term_chars, words.get(index)?);
+                (dist <= k).then_some((index, dist))
+            })
+            .collect())
+    }
+
+    /// Return the position of the first entry whose hash is not less than `hash`.
+    fn lower_bound(&mut self, hash: u64) -> Result<u64, Error> {
+        let (mut low, mut high) = (0, self.entry_count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.entry(mid)?.0 < hash {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Return the hash and word index of the entry at position `at`.
+    fn entry(&mut self, at: u64) -> Result<(u64, u32), Error> {
+        let mut bytes = [0; ENTRY_LEN as usize];
+        self.reader
+            .seek(SeekFrom::Start(HEADER_LEN + at * ENTRY_LEN))?;
+        self.reader.read_exact(&mut bytes)?;
+        Ok((
+            u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            u32::from_le_bytes(bytes[8..].try_into().unwrap()),
+        ))
+    }
+}
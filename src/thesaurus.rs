@@ -0,0 +1,83 @@
+use crate::wordlist;
+use std::{collections::HashMap, io::Error, path::Path};
+
+/// The synonyms of the words of a language, read from a thesaurus file installed next to
+/// its word list. Each line of the file holds a word followed by its synonyms, separated
+/// by commas or tabs, as in the Moby thesaurus.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::thesaurus::Thesaurus;
+/// let thesaurus = Thesaurus::parse("happy,glad,cheerful\nsad\tunhappy\thappy\n");
+/// assert_eq!(thesaurus.synonyms("happy"), ["glad", "cheerful"]);
+/// assert_eq!(thesaurus.synonyms("Sad"), ["unhappy", "happy"]);
+/// assert!(thesaurus.synonyms("glad").is_empty());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Thesaurus {
+    entries: HashMap<String, Vec<String>>,
+}
+
+impl Thesaurus {
+    /// Parse a thesaurus from `contents`. Words on several lines have the synonyms of all
+    /// of them, without repeats.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents of the thesaurus file.
+    pub fn parse(contents: &str) -> Thesaurus {
+        let mut entries: HashMap<String, Vec<String>> = HashMap::new();
+        for line in contents.lines() {
+            let mut fields = line
+                .split([',', '\t'])
+                .map(str::trim)
+                .filter(|field| !field.is_empty());
+            let word = match fields.next() {
+                Some(word) => word,
+                None => continue,
+            };
+
+            let synonyms = entries.entry(word.to_string()).or_default();
+            for synonym in fields {
+                if synonym != word && !synonyms.iter().any(|known| known == synonym) {
+                    synonyms.push(synonym.to_string());
+                }
+            }
+        }
+        Thesaurus { entries }
+    }
+
+    /// Read the thesaurus file at `path`, decompressing it if it is gzipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the thesaurus file.
+    pub fn load(path: &Path) -> Result<Thesaurus, Error> {
+        let (_, contents) = wordlist::read_contents(path)?;
+        Ok(Thesaurus::parse(&contents))
+    }
+
+    /// Return the synonyms of `word`, looking it up in lowercase if it has none as it is
+    /// written, so that words capitalized at the start of a sentence are found.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to look up.
+    pub fn synonyms(&self, word: &str) -> &[String] {
+        self.entries
+            .get(word)
+            .or_else(|| self.entries.get(&word.to_lowercase()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the number of words with an entry.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
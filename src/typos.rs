@@ -0,0 +1,206 @@
+use crate::float::hypot;
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use rand::{seq::SliceRandom, Rng};
+
+/// The rows of a QWERTY keyboard, used to find the keys next to a mistyped one.
+const QWERTY_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// The kinds of typing mistakes [`make_typo`] makes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypoKind {
+    /// A character replaced by one from a neighbouring key.
+    Substitution,
+    /// A character from a neighbouring key typed before or after the intended one, or
+    /// the intended one typed twice.
+    Insertion,
+    /// A character left out.
+    Deletion,
+    /// Two adjacent characters swapped.
+    Transposition,
+}
+
+/// Return the keys next to `c` on a QWERTY keyboard, on its own row and the rows above
+/// and below. Characters that are not lowercase ASCII letters have no neighbours.
+///
+/// # Arguments
+///
+/// * `c` - The intended character.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::typos::neighbours;
+/// assert_eq!(neighbours('s'), vec!['a', 'd', 'w', 'e', 'z', 'x']);
+/// assert!(neighbours('é').is_empty());
+/// ```
+pub fn neighbours(c: char) -> Vec<char> {
+    let position = QWERTY_ROWS
+        .iter()
+        .enumerate()
+        .find_map(|(row, keys)| keys.find(c).map(|col| (row, col)));
+    let (row, col) = match position {
+        Some(position) => position,
+        None => return vec![],
+    };
+
+    let mut keys = vec![];
+    let row_keys: Vec<char> = QWERTY_ROWS[row].chars().collect();
+    keys.extend(col.checked_sub(1).and_then(|c| row_keys.get(c)));
+    keys.extend(row_keys.get(col + 1));
+
+    // Rows are staggered, so the keys above are at the same column and the next one, and
+    // the keys below at the previous column and the same one.
+    if row > 0 {
+        let above: Vec<char> = QWERTY_ROWS[row - 1].chars().collect();
+        keys.extend(above.get(col));
+        keys.extend(above.get(col + 1));
+    }
+    if let Some(below) = QWERTY_ROWS.get(row + 1) {
+        let below: Vec<char> = below.chars().collect();
+        keys.extend(col.checked_sub(1).and_then(|c| below.get(c)));
+        keys.extend(below.get(col));
+    }
+
+    keys
+}
+
+/// Return the distance between the keys of `a` and `b` on a QWERTY keyboard, in key
+/// widths, or `None` if either is not a lowercase ASCII letter. Keys next to each other
+/// on a row are one apart, and keys on neighbouring rows slightly more.
+///
+/// # Arguments
+///
+/// * `a` - The first character.
+/// * `b` - The second character.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::typos::key_distance;
+/// assert_eq!(key_distance('s', 'a'), Some(1.0));
+/// assert!(key_distance('z', 'a').unwrap() > 1.0);
+/// assert!(key_distance('q', 'p').unwrap() > 8.0);
+/// assert_eq!(key_distance('s', 'é'), None);
+/// ```
+pub fn key_distance(a: char, b: char) -> Option<f64> {
+    // Each row starts half a key further right than the one above it.
+    let position = |c: char| {
+        QWERTY_ROWS.iter().enumerate().find_map(|(row, keys)| {
+            keys.find(c)
+                .map(|col| (row as f64, col as f64 + row as f64 * 0.5))
+        })
+    };
+    let ((a_row, a_col), (b_row, b_col)) = (position(a)?, position(b)?);
+
+    Some(hypot(a_row - b_row, a_col - b_col))
+}
+
+/// Return `word` with one typing mistake of `kind` at a random position, or `None` if
+/// `word` is too short for that kind of mistake.
+///
+/// # Arguments
+///
+/// * `word` - The intended word.
+/// * `kind` - The kind of mistake to make.
+/// * `rng` - The source of randomness.
+#[cfg(feature = "std")]
+pub fn apply_typo<R: Rng + ?Sized>(word: &str, kind: TypoKind, rng: &mut R) -> Option<String> {
+    let mut chars: Vec<char> = word.chars().collect();
+    let len = chars.len();
+
+    match kind {
+        TypoKind::Substitution if len > 0 => {
+            let i = rng.gen_range(0..len);
+            let replacement = *neighbours(chars[i].to_ascii_lowercase()).choose(rng)?;
+            chars[i] = replacement;
+        }
+        TypoKind::Insertion if len > 0 => {
+            let i = rng.gen_range(0..len);
+            let extra = neighbours(chars[i].to_ascii_lowercase())
+                .choose(rng)
+                .copied()
+                .unwrap_or(chars[i]);
+            chars.insert(i + rng.gen_range(0..=1), extra);
+        }
+        TypoKind::Deletion if len > 1 => {
+            chars.remove(rng.gen_range(0..len));
+        }
+        TypoKind::Transposition if len > 1 => {
+            let i = rng.gen_range(0..len - 1);
+            chars.swap(i, i + 1);
+        }
+        _ => return None,
+    }
+
+    Some(chars.into_iter().collect())
+}
+
+/// Return `word` with one or, one time in five, two realistic typing mistakes. Mistakes
+/// that leave the word unchanged, such as swapping two equal characters, are retried.
+///
+/// # Arguments
+///
+/// * `word` - The intended word, at least two characters long.
+/// * `rng` - The source of randomness.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::typos::make_typo;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let typo = make_typo("receive", &mut rng);
+/// assert_ne!(typo, "receive");
+/// ```
+#[cfg(feature = "std")]
+pub fn make_typo<R: Rng + ?Sized>(word: &str, rng: &mut R) -> String {
+    let kinds = [
+        TypoKind::Substitution,
+        TypoKind::Insertion,
+        TypoKind::Deletion,
+        TypoKind::Transposition,
+    ];
+    let mistakes = if rng.gen_ratio(1, 5) { 2 } else { 1 };
+
+    let mut typo = word.to_string();
+    for _ in 0..mistakes {
+        // Bounded, so words with no possible mistake cannot loop forever.
+        for _ in 0..16 {
+            let kind = *kinds.choose(rng).unwrap();
+            if let Some(next) = apply_typo(&typo, kind, rng).filter(|next| next != word) {
+                typo = next;
+                break;
+            }
+        }
+    }
+
+    typo
+}
+
+/// Sample `count` words of at least three characters from `words` and return each with a
+/// typo made in it, as `(typo, word)` pairs.
+///
+/// # Arguments
+///
+/// * `words` - The words to sample from.
+/// * `count` - The number of pairs to generate.
+/// * `rng` - The source of randomness.
+#[cfg(feature = "std")]
+pub fn corpus<R: Rng + ?Sized>(
+    words: &[String],
+    count: usize,
+    rng: &mut R,
+) -> Vec<(String, String)> {
+    let candidates: Vec<&String> = words.iter().filter(|w| w.chars().count() >= 3).collect();
+    if candidates.is_empty() {
+        return vec![];
+    }
+
+    (0..count)
+        .map(|_| {
+            let word = candidates.choose(rng).unwrap();
+            (make_typo(word, rng), word.to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+use std::{
+    fs::{create_dir_all, remove_file, rename},
+    io::Error,
+    path::{Path, PathBuf},
+};
+
+/// Return the path of the `n`th most recent previous version of the word list for `lang`.
+///
+/// # Arguments
+///
+/// * `data` - The didyoumean data directory.
+/// * `lang` - The locale code of the word list.
+/// * `n` - How many versions back to look, starting at 1.
+pub fn version_path(data: &Path, lang: &str, n: usize) -> PathBuf {
+    data.join("versions").join(format!("{}.{}", lang, n))
+}
+
+/// Move the installed word list for `lang` aside as its most recent previous version,
+/// keeping at most `keep` previous versions. With `keep` set to zero the list is deleted.
+///
+/// # Arguments
+///
+/// * `data` - The didyoumean data directory.
+/// * `lang` - The locale code of the word list.
+/// * `keep` - The number of previous versions to keep.
+pub fn retire(data: &Path, lang: &str, keep: usize) -> Result<(), Error> {
+    let current = data.join(lang);
+    if keep == 0 {
+        return remove_file(current);
+    }
+
+    create_dir_all(data.join("versions"))?;
+
+    // Drop the oldest version, then shift the rest back by one.
+    let oldest = version_path(data, lang, keep);
+    if oldest.is_file() {
+        remove_file(oldest)?;
+    }
+    for n in (1..keep).rev() {
+        let path = version_path(data, lang, n);
+        if path.is_file() {
+            rename(path, version_path(data, lang, n + 1))?;
+        }
+    }
+
+    rename(current, version_path(data, lang, 1))
+}
+
+/// Replace the installed word list for `lang` with its most recent previous version.
+/// Return `false` if there is no previous version to restore.
+///
+/// # Arguments
+///
+/// * `data` - The didyoumean data directory.
+/// * `lang` - The locale code of the word list.
+pub fn rollback(data: &Path, lang: &str) -> Result<bool, Error> {
+    let previous = version_path(data, lang, 1);
+    if !previous.is_file() {
+        return Ok(false);
+    }
+
+    rename(previous, data.join(lang))?;
+
+    // Shift the remaining versions forward by one.
+    let mut n = 2;
+    while version_path(data, lang, n).is_file() {
+        rename(version_path(data, lang, n), version_path(data, lang, n - 1))?;
+        n += 1;
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,294 @@
+use miniz_oxide::inflate::decompress_to_vec;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The formats a word list can be stored in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// One word per line.
+    Plain,
+    /// One `word<TAB>frequency` pair per line.
+    Frequency,
+    /// A hunspell `.dic` file: a word count followed by one `word/FLAGS` entry per line.
+    Hunspell,
+    /// A bilingual glossary: one `term<TAB>translation` pair per line.
+    Bilingual,
+}
+
+/// A word list file loaded into memory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordList {
+    path: PathBuf,
+    words: Vec<String>,
+}
+
+impl WordList {
+    /// Load the word list at `path`, as [`load`] does.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the word list file.
+    pub fn open(path: &Path) -> Result<WordList, Error> {
+        Ok(WordList {
+            path: path.to_path_buf(),
+            words: load(path)?,
+        })
+    }
+
+    /// Return the path the word list was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the words of the word list, in the order of the file.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+}
+
+/// Read the word list at `path`, detecting its format and decompressing it if it is
+/// gzipped.
+///
+/// # Arguments
+///
+/// * `path` - The path of the word list file.
+#[tracing::instrument(level = "debug")]
+pub fn load(path: &Path) -> Result<Vec<String>, Error> {
+    let (name, contents) = read_contents(path)?;
+    Ok(parse(&contents, detect_format(&name, &contents)))
+}
+
+/// Read the translations of the terms of the word list at `path`, if it is a bilingual
+/// glossary. Terms translated on several lines have their translations joined by "; ".
+/// Word lists in any other format have no translations.
+///
+/// # Arguments
+///
+/// * `path` - The path of the word list file.
+pub fn load_translations(path: &Path) -> Result<HashMap<String, String>, Error> {
+    let (name, contents) = read_contents(path)?;
+    if detect_format(&name, &contents) != Format::Bilingual {
+        return Ok(HashMap::new());
+    }
+
+    let mut translations: HashMap<String, String> = HashMap::new();
+    for (term, translation) in parse_translations(&contents) {
+        translations
+            .entry(term)
+            .and_modify(|known| {
+                known.push_str("; ");
+                known.push_str(&translation);
+            })
+            .or_insert(translation);
+    }
+    Ok(translations)
+}
+
+/// Read the file at `path` as text, decompressing it if it is gzipped, and return its
+/// file name without any `.gz` suffix with its contents.
+///
+/// # Arguments
+///
+/// * `path` - The path of the word list file.
+pub(crate) fn read_contents(path: &Path) -> Result<(String, String), Error> {
+    let mut bytes = read(path)?;
+
+    // Strip the gzip layer first, so the format is sniffed from the real contents.
+    let mut name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    if is_gzip(&bytes) {
+        bytes = gunzip(&bytes)?;
+        name = name.strip_suffix(".gz").unwrap_or(&name).to_string();
+    }
+
+    let contents = String::from_utf8(bytes).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{} is not valid UTF-8", path.display()),
+        )
+    })?;
+
+    Ok((name, contents))
+}
+
+/// Run `command` through the system shell and return the lines it prints as words.
+///
+/// # Arguments
+///
+/// * `command` - The shell command to run.
+pub fn from_command(command: &str) -> Result<Vec<String>, Error> {
+    #[cfg(windows)]
+    let output = Command::new("cmd").args(["/C", command]).output()?;
+    #[cfg(not(windows))]
+    let output = Command::new("sh").args(["-c", command]).output()?;
+
+    if !output.status.success() {
+        return Err(Error::other(format!(
+            "`{}` failed ({}): {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(parse(
+        &String::from_utf8_lossy(&output.stdout),
+        Format::Plain,
+    ))
+}
+
+/// Guess the format of a word list from its file name and contents.
+///
+/// # Arguments
+///
+/// * `name` - The file name of the word list, without any `.gz` suffix.
+/// * `contents` - The contents of the word list.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::wordlist::{detect_format, Format};
+/// assert_eq!(detect_format("en_US.dic", "2\nhello/S\nworld"), Format::Hunspell);
+/// assert_eq!(detect_format("words", "the\t2313\nof\t1312"), Format::Frequency);
+/// assert_eq!(detect_format("words", "cat\tchat\ndog\tchien"), Format::Bilingual);
+/// assert_eq!(detect_format("words", "the\nof"), Format::Plain);
+/// ```
+pub fn detect_format(name: &str, contents: &str) -> Format {
+    if name.ends_with(".dic") {
+        return Format::Hunspell;
+    }
+
+    let mut lines = contents.lines().map(str::trim).filter(|l| !l.is_empty());
+    let first = lines.next().unwrap_or_default();
+
+    // Hunspell dictionaries start with the number of entries on a line of its own.
+    if !first.is_empty() && first.chars().all(|c| c.is_ascii_digit()) {
+        return Format::Hunspell;
+    }
+
+    // Frequency lists have a numeric second column, and glossaries any other.
+    match first.split_once('\t') {
+        Some((_, frequency)) if frequency.trim().parse::<f64>().is_ok() => Format::Frequency,
+        Some((_, translation)) if !translation.trim().is_empty() => Format::Bilingual,
+        _ => Format::Plain,
+    }
+}
+
+/// Extract the words from `contents`, stored as `format`.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the word list.
+/// * `format` - The format the word list is stored in.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::wordlist::{parse, Format};
+/// assert_eq!(parse("2\nhello/S\nworld\n", Format::Hunspell), vec!["hello", "world"]);
+/// assert_eq!(parse("the\t2313\nof\t1312\n", Format::Frequency), vec!["the", "of"]);
+/// assert_eq!(parse("cat\tchat\ndog\tchien\ncat\tmatou\n", Format::Bilingual), vec!["cat", "dog"]);
+/// assert_eq!(parse("the\r\nof\n\n", Format::Plain), vec!["the", "of"]);
+/// ```
+pub fn parse(contents: &str, format: Format) -> Vec<String> {
+    let lines = contents.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    match format {
+        Format::Plain => lines.map(str::to_string).collect(),
+        Format::Frequency => lines
+            .filter_map(|l| l.split('\t').next())
+            .map(str::to_string)
+            .collect(),
+        // Terms with several translations are on several lines, but are one word.
+        Format::Bilingual => {
+            let mut seen = HashSet::new();
+            lines
+                .filter_map(|l| l.split('\t').next())
+                .filter(|term| seen.insert(*term))
+                .map(str::to_string)
+                .collect()
+        }
+        Format::Hunspell => lines
+            // Skip the entry count.
+            .skip(1)
+            .filter_map(|l| l.split(['/', '\t', ' ']).next())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+/// Extract the `(term, translation)` pairs from the bilingual glossary `contents`. Lines
+/// without a translation are left out.
+///
+/// # Arguments
+///
+/// * `contents` - The contents of the glossary.
+///
+/// # Examples
+///
+/// ```
+/// # use didyoumean::wordlist::parse_translations;
+/// let pairs = parse_translations("cat\tchat\ndog\tchien\nbird\n");
+/// assert_eq!(pairs, vec![("cat".into(), "chat".into()), ("dog".into(), "chien".into())]);
+/// ```
+pub fn parse_translations(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|l| l.split_once('\t'))
+        .map(|(term, translation)| (term.trim(), translation.trim()))
+        .filter(|(term, translation)| !term.is_empty() && !translation.is_empty())
+        .map(|(term, translation)| (term.to_string(), translation.to_string()))
+        .collect()
+}
+
+/// Return whether `bytes` start with the gzip magic number.
+fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x1f, 0x8b])
+}
+
+/// Decompress a gzip member.
+///
+/// # Arguments
+///
+/// * `bytes` - The gzipped data, header included.
+fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidData, "Invalid gzip data");
+
+    // The fixed part of the header is ten bytes long, followed by optional fields.
+    if bytes.len() < 10 || bytes[2] != 8 {
+        return Err(invalid());
+    }
+    let flags = bytes[3];
+    let mut offset = 10;
+
+    // FEXTRA: a little endian length followed by that many bytes.
+    if flags & 0x04 != 0 {
+        let len = bytes.get(offset..offset + 2).ok_or_else(invalid)?;
+        offset += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+    }
+    // FNAME and FCOMMENT: zero terminated strings.
+    for flag in [0x08, 0x10] {
+        if flags & flag != 0 {
+            let len = bytes
+                .get(offset..)
+                .and_then(|rest| rest.iter().position(|b| *b == 0))
+                .ok_or_else(invalid)?;
+            offset += len + 1;
+        }
+    }
+    // FHCRC: a two byte header checksum.
+    if flags & 0x02 != 0 {
+        offset += 2;
+    }
+
+    decompress_to_vec(bytes.get(offset..).ok_or_else(invalid)?).map_err(|_| invalid())
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "std")]
+
+use didyoumean::anagram::{difference, letters, AnagramIndex};
+
+#[test]
+fn find_test() {
+    let words = [
+        "stale", "least", "Tales", "steal", "slate", "stall", "tale", "", "1234",
+    ]
+    .map(String::from)
+    .to_vec();
+    let index = AnagramIndex::build(&words);
+
+    assert_eq!(
+        index.find("TESLA", 0),
+        vec![(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]
+    );
+    assert_eq!(index.find("tesla", 1).last(), Some(&(6, 1)));
+
+    // Near anagrams are exactly the words within the difference.
+    for max_differences in 0..4 {
+        let expected: Vec<usize> = (0..words.len())
+            .filter(|i| !letters(&words[*i]).is_empty())
+            .filter(|i| difference(&letters("tesla"), &letters(&words[*i])) <= max_differences)
+            .collect();
+        let mut found: Vec<usize> = index
+            .find("tesla", max_differences)
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect();
+        found.sort_unstable();
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let words = ["listen", "silent", "enlist", "cat"]
+        .map(String::from)
+        .to_vec();
+    let index = AnagramIndex::build(&words);
+    let mut bytes = vec![];
+    index.write_to(&mut bytes).unwrap();
+    assert_eq!(AnagramIndex::read_from(bytes.as_slice()).unwrap(), index);
+    assert!(AnagramIndex::read_from(&b"listen\t0 1 2\n"[..]).is_err());
+}
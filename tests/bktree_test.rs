@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use common::{brute_force_within, random_word, SMALL_ALPHABET};
+use didyoumean::{bktree::BkTree, metric::Metric};
+use rand::{rngs::StdRng, SeedableRng};
+
+mod common;
+
+#[test]
+fn find_within_test() {
+    let mut rng = StdRng::seed_from_u64(0x626b);
+    let words: Vec<String> = (0..2000)
+        .map(|_| random_word(&mut rng, SMALL_ALPHABET, 8))
+        .collect();
+
+    for metric in [Metric::Levenshtein, Metric::DamerauLevenshtein] {
+        let tree = BkTree::from_words(metric, words.iter().map(String::as_str));
+
+        let mut unique = words.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(tree.len(), unique.len());
+
+        for _ in 0..50 {
+            let term = random_word(&mut rng, SMALL_ALPHABET, 8);
+            for k in 0..3 {
+                let mut expected: Vec<(&str, usize)> =
+                    brute_force_within(&unique, k, |word| metric.distance(&term, word))
+                        .into_iter()
+                        .map(|(i, dist)| (unique[i].as_str(), dist))
+                        .collect();
+                expected.sort();
+
+                let mut found = tree.find_within(&term, k);
+                assert!(found.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+                found.sort();
+                assert_eq!(found, expected, "{:?} within {} of {:?}", metric, k, term);
+            }
+        }
+    }
+}
+
+#[test]
+fn empty_tree_test() {
+    let tree = BkTree::new(Metric::Levenshtein);
+    assert!(tree.is_empty());
+    assert_eq!(tree.find_within("anything", 10), vec![]);
+}
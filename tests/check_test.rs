@@ -0,0 +1,57 @@
+#![cfg(feature = "std")]
+
+use didyoumean::{
+    check::{check_text, Checker, Filter, Skip},
+    source::{Source, SourceKind},
+};
+
+fn sources() -> Vec<Source> {
+    let words = ["the", "café", "naïve", "world", "word", "nasa"];
+    vec![Source::new(
+        SourceKind::Language,
+        "en",
+        words.map(String::from).to_vec(),
+    )]
+}
+
+#[test]
+fn spans_test() {
+    let text = "Thé naïve cafe, wrold";
+    let findings = check_text(text, &sources());
+
+    let spans: Vec<&str> = findings.iter().map(|f| &text[f.span.clone()]).collect();
+    assert_eq!(spans, vec!["Thé", "cafe", "wrold"]);
+    for finding in &findings {
+        assert_eq!(text[finding.span.clone()], finding.word);
+    }
+    assert_eq!(findings[1].suggestions[0], "café");
+    assert_eq!(findings[2].suggestions[..2], ["world", "word"]);
+}
+
+#[test]
+fn known_words_test() {
+    let sources = sources();
+    let checker = Checker::new(&sources);
+
+    // Capitalized known words are known, but known words in capitals are not lowercase.
+    assert!(checker.is_known("The"));
+    assert!(checker.is_known("NASA"));
+    assert!(!checker.is_known("Wrold"));
+    assert!(checker.check_text("The World. THE WORLD!").is_empty());
+}
+
+#[test]
+fn filter_test() {
+    let sources = sources();
+    let text = "teh wrold at https://wrold.example ABC x1y2z3";
+    let checker = Checker::new(&sources).number(1);
+    assert_eq!(checker.check_text(text).len(), 10);
+
+    let filter = Filter {
+        skip: Skip::ALL.to_vec(),
+        min_len: 4,
+    };
+    let findings = Checker::new(&sources).filter(filter).check_text(text);
+    let words: Vec<&str> = findings.iter().map(|f| f.word.as_str()).collect();
+    assert_eq!(words, vec!["wrold"]);
+}
@@ -0,0 +1,179 @@
+#![cfg(feature = "cli")]
+
+use common::dym;
+use std::{
+    fs::{create_dir_all, remove_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+mod common;
+
+const WORDS: &[&str] = &[
+    "hello",
+    "help",
+    "world",
+    "hellowords",
+    "sing",
+    "king",
+    "ring",
+    "running",
+    "singing",
+    "listen",
+    "silent",
+    "enlist",
+    "abbreviate",
+    "abbreviated",
+    "abbreviating",
+    "abbreviation",
+    "abbreviations",
+];
+
+/// Return a fresh home directory for the test called `name`, with an English word list
+/// installed in its data directory.
+fn home(name: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("didyoumean_cli_test_{}", name));
+    let _ = remove_dir_all(&home);
+    let data_dir = home.join(".local").join("share").join("didyoumean");
+    create_dir_all(&data_dir).unwrap();
+    write(data_dir.join("en"), WORDS.join("\n")).unwrap();
+    home
+}
+
+/// Return the words `dym` prints with `args` and `home` as its home directory.
+fn words(home: &Path, args: &[&str]) -> Vec<String> {
+    let output = dym(home, &[&["-c"], args].concat());
+    assert!(output.status.success(), "{:?} failed", args);
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Build the index of the English word list in `home`.
+fn build_index(home: &Path) {
+    assert!(dym(home, &["index", "build", "en"]).status.success());
+}
+
+#[test]
+fn narrowed_search_test() {
+    let home = home("narrowed_search");
+    let without = [&["-n", "2", "helo"][..], &["-n", "0", "helo"]].map(|args| words(&home, args));
+    build_index(&home);
+    let with = [&["-n", "2", "helo"][..], &["-n", "0", "helo"]].map(|args| words(&home, args));
+
+    // The index only saves comparisons, the suggestions stay the same.
+    assert_eq!(with, without);
+    assert_eq!(with[0], ["hello", "help"]);
+    remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn anagrams_test() {
+    let home = home("anagrams");
+    build_index(&home);
+
+    // Anagrams are found among all the words, not only those close to the term, and the
+    // saved letter index holds them all.
+    for _ in 0..2 {
+        assert_eq!(
+            words(&home, &["--anagrams", "listen"]),
+            ["listen", "silent", "enlist"]
+        );
+    }
+    assert_eq!(
+        words(&home, &["--anagrams", "tinsel"]),
+        ["listen", "silent", "enlist"]
+    );
+
+    // A language from a system directory is searched without a data directory.
+    let system_dir = home.join("system");
+    let data_dir = home.join(".local").join("share").join("didyoumean");
+    create_dir_all(&system_dir).unwrap();
+    std::fs::rename(data_dir.join("en"), system_dir.join("en")).unwrap();
+    remove_dir_all(&data_dir).unwrap();
+    let config_dir = home.join(".config").join("didyoumean");
+    create_dir_all(&config_dir).unwrap();
+    let config = format!(r#"{{ "system-dirs": [{:?}] }}"#, system_dir);
+    write(config_dir.join("config.json"), config).unwrap();
+    assert_eq!(
+        words(&home, &["--anagrams", "listen"]),
+        ["listen", "silent", "enlist"]
+    );
+    remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn endings_test() {
+    let home = home("endings");
+    build_index(&home);
+
+    // Words far from the suffix or the rhymed term still end like them, even when every
+    // close word is asked for.
+    for n in ["5", "0"] {
+        let suffixed = words(&home, &["-n", n, "--suffix", "ing"]);
+        assert!(suffixed.contains(&"running".to_string()), "-n {}", n);
+        let rhymes = words(&home, &["-n", n, "--rhyme", "sing"]);
+        assert!(rhymes.contains(&"abbreviating".to_string()), "-n {}", n);
+    }
+    remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn completions_test() {
+    let home = home("completions");
+    build_index(&home);
+
+    // An abbreviation is completed to the words it starts, however far they are from it.
+    assert_eq!(
+        words(&home, &["-n", "0", "--complete", "abbr"]),
+        [
+            "abbreviate",
+            "abbreviated",
+            "abbreviating",
+            "abbreviation",
+            "abbreviations"
+        ]
+    );
+    remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn splits_test() {
+    let home = home("splits");
+    build_index(&home);
+
+    // The halves of a compound are found even when a word is close to the compound.
+    assert_eq!(
+        words(&home, &["-n", "1", "--split", "helloworld"]),
+        ["hello world"]
+    );
+    remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn replay_test() {
+    let home = home("replay");
+    let session = home.join("session.json");
+    let session = session.to_str().unwrap();
+
+    // A suffix search replays as one, and the index built since leaves the word lists it
+    // fingerprinted unchanged.
+    let recorded = words(&home, &["-n", "0", "--suffix", "ing", "--record", session]);
+    assert!(recorded.contains(&"running".to_string()));
+    build_index(&home);
+    let output = dym(&home, &["--replay", session]);
+    assert!(output.status.success());
+    let messages = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        messages.contains("The ranking is unchanged"),
+        "{}",
+        messages
+    );
+    assert!(!messages.contains("differs"), "{}", messages);
+
+    // So do the anagrams of a term.
+    words(&home, &["--anagrams", "tinsel", "--record", session]);
+    assert!(dym(&home, &["--replay", session]).status.success());
+    remove_dir_all(&home).unwrap();
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+// Each test file uses only some of the helpers.
+#![allow(dead_code)]
+
+use rand::{rngs::StdRng, Rng};
+use std::{
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+/// A small alphabet with an accented letter, so that many random words are close to each
+/// other and characters outside ASCII come up often.
+pub const SMALL_ALPHABET: &[&str] = &["a", "b", "c", "d", "é"];
+
+/// Return a random word of up to `max_len` letters drawn from `alphabet`.
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator to draw from.
+/// * `alphabet` - The letters, or graphemes, to build the word from.
+/// * `max_len` - The largest number of letters in the word.
+pub fn random_word(rng: &mut StdRng, alphabet: &[&str], max_len: usize) -> String {
+    let len = rng.gen_range(0..=max_len);
+    (0..len)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+        .collect()
+}
+
+/// Return the index and distance of every word of `words` within `k` of the term, found
+/// by comparing it with each one, in the order of `words`. The oracle the search
+/// structures are checked against.
+///
+/// # Arguments
+///
+/// * `words` - The words to search.
+/// * `k` - The largest distance to return words at.
+/// * `distance` - Return the distance between the term and a word.
+pub fn brute_force_within(
+    words: &[String],
+    k: usize,
+    distance: impl Fn(&str) -> usize,
+) -> Vec<(usize, usize)> {
+    words
+        .iter()
+        .map(|word| distance(word))
+        .enumerate()
+        .filter(|(_, dist)| *dist <= k)
+        .collect()
+}
+
+/// Run `dym` with `args` and `home` as its home directory, so that it reads its
+/// configuration and word lists from there.
+///
+/// # Arguments
+///
+/// * `home` - The home directory to run in.
+/// * `args` - The arguments to pass.
+#[cfg(feature = "cli")]
+pub fn dym(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dym"))
+        .args(args)
+        .env("HOME", home)
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "cli")]
+
+use common::dym;
+use std::fs::{create_dir_all, remove_dir_all, write};
+
+mod common;
+
+#[test]
+fn number_zero_test() {
+    let home = std::env::temp_dir().join("didyoumean_config_test_number_zero");
+    let _ = remove_dir_all(&home);
+    create_dir_all(&home).unwrap();
+
+    // Zero asks for every word close enough, so it is a valid default.
+    assert!(dym(&home, &["config", "set", "number", "0"])
+        .status
+        .success());
+    let output = dym(&home, &["config", "get", "number"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "0\n");
+
+    // A configuration file saying so loads too.
+    let config_dir = home.join(".config").join("didyoumean");
+    write(config_dir.join("config.json"), r#"{ "number": 0 }"#).unwrap();
+    let output = dym(&home, &["config", "get", "number"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "0\n");
+
+    assert!(!dym(&home, &["config", "set", "number", "-1"])
+        .status
+        .success());
+    remove_dir_all(&home).unwrap();
+}
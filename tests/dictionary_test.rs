@@ -0,0 +1,130 @@
+#![cfg(feature = "std")]
+
+use didyoumean::{
+    bktree::BkTree,
+    dictionary::Dictionary,
+    engine::SuggestionEngine,
+    metric::Metric,
+    pattern::LengthIndex,
+    source::{Source, SourceKind},
+    top_n,
+    wordlist::WordList,
+    Ranking,
+};
+use std::collections::{BTreeSet, HashSet};
+
+/// Check that `dictionary` holds exactly `expected`, in any order.
+fn check(dictionary: &impl Dictionary, expected: &[String]) {
+    let mut words: Vec<&str> = dictionary.words().collect();
+    words.sort_unstable();
+    let mut sorted: Vec<&str> = expected.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+    assert_eq!(words, sorted);
+
+    assert_eq!(dictionary.len(), expected.len());
+    assert_eq!(dictionary.is_empty(), expected.is_empty());
+    for word in expected {
+        assert!(dictionary.contains(word), "{:?} is missing", word);
+    }
+    for word in ["", "hel", "helloo", "wrold", "Hello"] {
+        assert!(!dictionary.contains(word), "{:?} is not a word", word);
+    }
+}
+
+#[test]
+fn implementations_test() {
+    let words: Vec<String> = ["hello", "help", "world", "héllo", "a"]
+        .map(String::from)
+        .to_vec();
+
+    check(&words, &words);
+    check(
+        &words.iter().map(String::as_str).collect::<Vec<_>>(),
+        &words,
+    );
+    check(&words.iter().cloned().collect::<HashSet<_>>(), &words);
+    check(&words.iter().cloned().collect::<BTreeSet<_>>(), &words);
+    check(
+        &Source::new(SourceKind::Custom, "words", words.clone()),
+        &words,
+    );
+    check(
+        &BkTree::from_words(Metric::Levenshtein, words.iter().map(String::as_str)),
+        &words,
+    );
+    check(&LengthIndex::new(&words), &words);
+
+    let path = std::env::temp_dir().join(format!("dym-dictionary-test-{}", std::process::id()));
+    std::fs::write(&path, words.join("\n")).unwrap();
+    let list = WordList::open(&path);
+    std::fs::remove_file(&path).unwrap();
+    let list = list.unwrap();
+    assert_eq!(list.path(), path);
+    check(&list, &words);
+
+    check(&Vec::<String>::new(), &[]);
+}
+
+#[test]
+fn hash_set_order_test() {
+    // Equally close words are suggested in the same order however the set was built.
+    let words = ["cot", "cut", "cit", "cab", "cap"].map(String::from);
+    let forwards: HashSet<String> = words.iter().cloned().collect();
+    let backwards: HashSet<String> = words.iter().rev().cloned().collect();
+    assert!(forwards.words().eq(backwards.words()));
+
+    let engine = SuggestionEngine::builder()
+        .dictionary(forwards.words())
+        .limit(3)
+        .build();
+    let suggestions: Vec<&str> = engine.suggest("cat").iter().map(|s| s.word).collect();
+    assert_eq!(suggestions, ["cab", "cap", "cit"]);
+}
+
+#[test]
+fn suggest_multi_test() {
+    let words: Vec<String> = ["phone", "fine", "phase", "fun", "photo"]
+        .map(String::from)
+        .to_vec();
+    let rankings = [
+        Ranking::EditDistance,
+        Ranking::Soundex,
+        Ranking::Keyboard,
+        Ranking::Ngram,
+    ];
+    let ranked = words.suggest_multi("fone", &rankings, 5);
+    assert_eq!(ranked.len(), rankings.len());
+
+    // Each ranking matches a scan of its own.
+    assert_eq!(ranked[0], top_n("fone", words.words(), 5));
+    let term: Vec<char> = "fone".chars().collect();
+    for (ranking, ranked) in rankings.iter().zip(&ranked) {
+        for window in ranked.windows(2) {
+            assert!(window[0].1 <= window[1].1);
+        }
+        for (word, dist) in ranked {
+            assert_eq!(ranking.distance(&term, word), *dist);
+        }
+    }
+
+    // Words that do not sound alike are left out rather than ranked last.
+    assert_eq!(ranked[0].len(), words.len());
+    assert!(ranked[1].iter().all(|(word, _)| *word != "phase"));
+    assert!(words
+        .suggest_multi("fone", &rankings, 0)
+        .iter()
+        .all(Vec::is_empty));
+}
+
+#[test]
+fn unranked_test() {
+    // Soundex accepts any distance, but words that do not sound alike are never suggested.
+    let engine = SuggestionEngine::builder()
+        .dictionary(["phone", "phase", "fun"])
+        .ranking(Ranking::Soundex)
+        .limit(0)
+        .build();
+    let suggestions: Vec<&str> = engine.suggest("fone").iter().map(|s| s.word).collect();
+    assert!(!suggestions.contains("phase"));
+    assert!(suggestions.contains("phone"));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use common::random_word;
+use didyoumean::{edit_distance, edit_distance_seq, edit_ops, EditOp};
+use rand::{rngs::StdRng, SeedableRng};
+use unicode_segmentation::UnicodeSegmentation;
+
+mod common;
+
+/// Graphemes to build words from, including a decomposed accented letter.
+const GRAPHEMES: &[&str] = &["a", "b", "c", "e\u{301}"];
+
+/// Return `a` with `ops` applied, taking inserted and substituted graphemes from `b`.
+fn apply(a: &str, b: &str, ops: &[EditOp]) -> String {
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    let mut result = String::new();
+    let mut next = 0;
+    for op in ops {
+        let (i, skip, text) = match *op {
+            EditOp::Insert { i, j } => (i, 0, b[j].to_string()),
+            EditOp::Delete { i, .. } => (i, 1, String::new()),
+            EditOp::Substitute { i, j } => (i, 1, b[j].to_string()),
+            EditOp::Transpose { i, .. } => (i, 2, format!("{}{}", a[i + 1], a[i])),
+        };
+        assert!(i >= next, "edits out of order: {:?}", ops);
+        result.push_str(&a[next..i].concat());
+        result.push_str(&text);
+        next = i + skip;
+    }
+    result.push_str(&a[next..].concat());
+    result
+}
+
+#[test]
+fn edit_ops_test() {
+    let mut rng = StdRng::seed_from_u64(0x6f70);
+    for _ in 0..5000 {
+        let (a, b) = (
+            random_word(&mut rng, GRAPHEMES, 7),
+            random_word(&mut rng, GRAPHEMES, 7),
+        );
+        let ops = edit_ops(&a, &b);
+        let a_chars: Vec<char> = a.chars().collect();
+        assert_eq!(ops.len(), edit_distance(&a_chars, &b), "{:?} to {:?}", a, b);
+        assert_eq!(apply(&a, &b, &ops), b, "{:?} to {:?}: {:?}", a, b, ops);
+    }
+}
+
+#[test]
+fn edit_distance_seq_test() {
+    let mut rng = StdRng::seed_from_u64(0x7365);
+    for _ in 0..5000 {
+        let (a, b) = (
+            random_word(&mut rng, GRAPHEMES, 7),
+            random_word(&mut rng, GRAPHEMES, 7),
+        );
+        let a_graphemes: Vec<&str> = a.graphemes(true).collect();
+        let b_graphemes: Vec<&str> = b.graphemes(true).collect();
+        let a_chars: Vec<char> = a.chars().collect();
+        assert_eq!(
+            edit_distance_seq(&a_graphemes, &b_graphemes),
+            edit_distance(&a_chars, &b),
+            "{:?} to {:?}",
+            a,
+            b
+        );
+    }
+
+    // Whole words are compared as items.
+    let a = "the quick brown fox".split(' ').collect::<Vec<_>>();
+    let b = "the brown quick fox jumps".split(' ').collect::<Vec<_>>();
+    assert_eq!(edit_distance_seq(&a, &b), 2);
+}
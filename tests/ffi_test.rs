@@ -0,0 +1,66 @@
+#![cfg(feature = "ffi")]
+
+use didyoumean::ffi::*;
+use std::{
+    ffi::{CStr, CString},
+    ptr, slice,
+};
+
+/// Return the words and distances of `suggestions`, and free them.
+fn take(suggestions: DymSuggestions) -> Vec<(String, usize)> {
+    let words = match suggestions.items.is_null() {
+        true => vec![],
+        false => unsafe { slice::from_raw_parts(suggestions.items, suggestions.len) }
+            .iter()
+            .map(|item| {
+                let word = unsafe { CStr::from_ptr(item.word) };
+                (word.to_str().unwrap().to_string(), item.distance)
+            })
+            .collect(),
+    };
+    unsafe { dym_suggestions_free(suggestions) };
+    words
+}
+
+#[test]
+fn ffi_test() {
+    let c = |s: &str| CString::new(s).unwrap();
+    let version = unsafe { CStr::from_ptr(dym_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+
+    let (kitten, sitting) = (c("kitten"), c("sitting"));
+    unsafe {
+        assert_eq!(dym_edit_distance(kitten.as_ptr(), sitting.as_ptr()), 3);
+        assert_eq!(dym_edit_distance(kitten.as_ptr(), ptr::null()), usize::MAX);
+        assert_eq!(dym_similarity(kitten.as_ptr(), kitten.as_ptr()), 1.0);
+        assert_eq!(dym_similarity(ptr::null(), kitten.as_ptr()), -1.0);
+    }
+
+    // Null and invalid candidates are skipped.
+    let invalid = CString::new(vec![0xff]).unwrap();
+    let words = [c("hello"), c("help"), c("world")];
+    let mut pointers: Vec<_> = words.iter().map(|word| word.as_ptr()).collect();
+    pointers.extend([ptr::null(), invalid.as_ptr()]);
+    let term = c("helo");
+    let found = take(unsafe { dym_suggest(term.as_ptr(), pointers.as_ptr(), pointers.len(), 2) });
+    assert_eq!(found, [("hello".to_string(), 1), ("help".to_string(), 1)]);
+    assert!(take(unsafe { dym_suggest(ptr::null(), pointers.as_ptr(), 5, 2) }).is_empty());
+
+    let engine = unsafe { dym_engine_new(pointers.as_ptr(), pointers.len(), 1) };
+    let term = c("wrld");
+    let found = take(unsafe { dym_engine_suggest(engine, term.as_ptr()) });
+    assert_eq!(found, [("world".to_string(), 1)]);
+    unsafe { dym_engine_free(engine) };
+
+    let path = std::env::temp_dir().join(format!("dym-ffi-test-{}", std::process::id()));
+    std::fs::write(&path, "cat\ncut\ndog\n").unwrap();
+    let c_path = c(path.to_str().unwrap());
+    let engine = unsafe { dym_engine_from_file(c_path.as_ptr(), 0) };
+    std::fs::remove_file(&path).unwrap();
+    assert!(!engine.is_null());
+    let term = c("cot");
+    let found = take(unsafe { dym_engine_suggest(engine, term.as_ptr()) });
+    assert_eq!(found, [("cat".to_string(), 1), ("cut".to_string(), 1)]);
+    unsafe { dym_engine_free(engine) };
+    assert!(unsafe { dym_engine_from_file(c_path.as_ptr(), 0) }.is_null());
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "std")]
+
+use didyoumean::git::{find_git_dir, ref_names};
+use std::fs::{create_dir_all, remove_dir_all, write};
+
+#[test]
+fn ref_names_test() {
+    let repo = std::env::temp_dir().join("didyoumean_git_test");
+    let git_dir = repo.join(".git");
+    create_dir_all(git_dir.join("refs/heads/feature")).unwrap();
+    create_dir_all(git_dir.join("refs/tags")).unwrap();
+    create_dir_all(repo.join("src")).unwrap();
+
+    // One loose branch, one nested loose branch and a packed branch and tag.
+    write(git_dir.join("refs/heads/main"), "0000\n").unwrap();
+    write(git_dir.join("refs/heads/feature/login"), "0000\n").unwrap();
+    write(
+        git_dir.join("packed-refs"),
+        "# pack-refs with: peeled fully-peeled sorted\n0000 refs/heads/develop\n0000 refs/tags/v1.0\n^0000\n",
+    )
+    .unwrap();
+
+    let found = find_git_dir(&repo.join("src")).unwrap();
+    let names = ref_names(&found).unwrap();
+    remove_dir_all(&repo).unwrap();
+
+    assert_eq!(found, git_dir);
+    assert_eq!(names, vec!["develop", "feature/login", "main", "v1.0"]);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "std")]
+
+use common::{brute_force_within, random_word, SMALL_ALPHABET};
+use didyoumean::{
+    edit_distance,
+    index::{SuffixIndex, Trie},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+mod common;
+
+#[test]
+fn find_within_test() {
+    let mut rng = StdRng::seed_from_u64(0x7472);
+    let words: Vec<String> = (0..2000)
+        .map(|_| random_word(&mut rng, SMALL_ALPHABET, 8))
+        .collect();
+    let trie = Trie::from_words(&words);
+
+    let mut unique = words.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(trie.len(), unique.len());
+    assert!(unique.iter().all(|word| trie.contains(word)));
+
+    for _ in 0..50 {
+        let term = random_word(&mut rng, SMALL_ALPHABET, 8);
+        let term_chars: Vec<char> = term.chars().collect();
+        for k in 0..3 {
+            let mut expected: Vec<(String, usize)> =
+                brute_force_within(&unique, k, |word| edit_distance(&term_chars, word))
+                    .into_iter()
+                    .map(|(i, dist)| (unique[i].clone(), dist))
+                    .collect();
+            expected.sort_by(|(a, a_dist), (b, b_dist)| a_dist.cmp(b_dist).then(a.cmp(b)));
+
+            assert_eq!(
+                trie.find_within(&term, k),
+                expected,
+                "within {} of {:?}",
+                k,
+                term
+            );
+        }
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let trie = Trie::from_words(["hello", "help", "world", "", "héllo"]);
+    let mut bytes = vec![];
+    trie.write_to(&mut bytes).unwrap();
+    assert_eq!(Trie::read_from(&mut bytes.as_slice()).unwrap(), trie);
+
+    let path = std::env::temp_dir().join(format!("dym-index-test-{}", std::process::id()));
+    trie.save(&path).unwrap();
+    let loaded = Trie::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, trie);
+    assert_eq!(loaded.find_within("helo", 1).len(), 2);
+
+    // Truncated and foreign files are errors rather than panics.
+    assert!(Trie::read_from(&mut &bytes[..bytes.len() - 1]).is_err());
+    assert!(Trie::read_from(&mut &b"not a trie"[..]).is_err());
+}
+
+#[test]
+fn suffix_test() {
+    let mut rng = StdRng::seed_from_u64(0x7366);
+    let words: Vec<String> = (0..2000)
+        .map(|_| random_word(&mut rng, SMALL_ALPHABET, 8))
+        .collect();
+    let index = SuffixIndex::new(&words);
+
+    for _ in 0..50 {
+        let suffix: String = random_word(&mut rng, SMALL_ALPHABET, 8)
+            .chars()
+            .take(3)
+            .collect();
+        let mut expected: Vec<&String> = words
+            .iter()
+            .filter(|word| word.ends_with(&suffix))
+            .collect();
+        expected.sort();
+        expected.dedup();
+
+        let found = index.ending_with(&suffix);
+        assert!(found
+            .windows(2)
+            .all(|pair| pair[0].chars().count() <= pair[1].chars().count()));
+        let mut found: Vec<&String> = found.iter().collect();
+        found.sort();
+        assert_eq!(found, expected, "ending with {:?}", suffix);
+    }
+}
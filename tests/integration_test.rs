@@ -1,3 +1,5 @@
+#![cfg(feature = "clipboard")]
+
 use cli_clipboard::{ClipboardContext, ClipboardProvider};
 use didyoumean::yank;
 
@@ -8,12 +10,13 @@ fn yank_test() {
     let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
 
     // Run the yank function.
-    yank(string);
+    yank(string).unwrap();
 
     // Sleep to allow the function time to write to the clipboard.
     std::thread::sleep(std::time::Duration::from_secs(1));
 
     // Get the clipboard contents.
+    #[allow(clippy::useless_format)]
     let clipboard = format!("{}", ctx.get_contents().unwrap());
 
     assert_eq!(clipboard, string);
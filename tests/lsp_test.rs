@@ -0,0 +1,89 @@
+#![cfg(feature = "std")]
+
+use didyoumean::{check::Finding, lsp::serve};
+use serde_json::{json, Value};
+
+/// Frame `message` as a Language Server Protocol message.
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+/// Split the messages written by the server.
+fn unframe(output: &[u8]) -> Vec<Value> {
+    let output = String::from_utf8(output.to_vec()).unwrap();
+    output
+        .split("Content-Length: ")
+        .filter(|message| !message.is_empty())
+        .map(|message| serde_json::from_str(message.split_once("\r\n\r\n").unwrap().1).unwrap())
+        .collect()
+}
+
+#[test]
+fn serve_test() {
+    let uri = "file:///notes.md";
+    let input = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "text": "hello\nwrold" } },
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "textDocument/codeAction",
+            "params": {
+                "textDocument": { "uri": uri },
+                "range": {
+                    "start": { "line": 1, "character": 2 },
+                    "end": { "line": 1, "character": 2 },
+                },
+            },
+        }),
+        json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/hover", "params": {} }),
+        json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]
+    .into_iter()
+    .map(frame)
+    .collect::<String>();
+
+    let mut output = vec![];
+    serve(input.as_bytes(), &mut output, |text| {
+        text.match_indices("wrold")
+            .map(|(start, word)| Finding {
+                span: start..start + word.len(),
+                word: word.to_string(),
+                suggestions: vec!["world".to_string()],
+            })
+            .collect()
+    })
+    .unwrap();
+
+    let messages = unframe(&output);
+    assert_eq!(messages.len(), 5);
+    assert_eq!(messages[0]["result"]["capabilities"]["textDocumentSync"], 1);
+
+    let diagnostics = &messages[1]["params"]["diagnostics"];
+    assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 1, "character": 0 })
+    );
+    assert_eq!(
+        diagnostics[0]["range"]["end"],
+        json!({ "line": 1, "character": 5 })
+    );
+
+    let action = &messages[2]["result"][0];
+    assert_eq!(messages[2]["id"], 2);
+    assert_eq!(action["edit"]["changes"][uri][0]["newText"], "world");
+
+    assert_eq!(messages[3]["error"]["code"], -32601);
+    assert_eq!(
+        messages[4],
+        json!({ "jsonrpc": "2.0", "id": 4, "result": null })
+    );
+}
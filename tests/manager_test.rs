@@ -0,0 +1,225 @@
+#![cfg(feature = "std")]
+
+use didyoumean::{
+    error::DymError,
+    fetcher::MemoryFetcher,
+    manager::{DownloadEvent, WordlistManager},
+};
+use std::{
+    fs::{create_dir_all, read_to_string, remove_dir_all, write},
+    path::PathBuf,
+};
+
+const WORDS: &[u8] = b"hello\nhelp\nworld\nword\n";
+
+/// Return a fresh data directory for the test called `name`.
+fn data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("didyoumean_manager_test_{}", name));
+    let _ = remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn download_test() {
+    let dir = data_dir("download");
+    let manager = WordlistManager::new(dir.clone());
+    let url = manager.url("en");
+    let manager = manager.fetcher(Box::new(MemoryFetcher::new().with_file(&url, WORDS)));
+
+    let mut events = vec![];
+    manager
+        .fetch("en", &mut |event| {
+            if let DownloadEvent::Started { total_bytes, .. } = event {
+                events.push(total_bytes);
+            }
+        })
+        .unwrap();
+
+    assert_eq!(events, vec![Some(WORDS.len() as u64)]);
+    assert_eq!(manager.remote_size("en"), Some(WORDS.len() as u64));
+    assert_eq!(
+        manager.load("en").unwrap(),
+        vec!["hello", "help", "world", "word"]
+    );
+
+    let log = manager.audit_log().unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(
+        (log[0].lang.as_str(), log[0].bytes),
+        ("en", WORDS.len() as u64)
+    );
+    assert_eq!(
+        log[0].sha256,
+        "ca9dde344557975ca5742d7722619fcd4a686f8255601a29c56456913887fee0"
+    );
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn resume_test() {
+    let dir = data_dir("resume");
+    let manager = WordlistManager::new(dir.clone());
+    let url = manager.url("en");
+    let manager = manager.fetcher(Box::new(
+        MemoryFetcher::new()
+            .with_file(&url, WORDS)
+            .interrupt_after(7),
+    ));
+
+    // The first attempt fails, keeping what it got so far.
+    assert!(manager.fetch("en", &mut |_| {}).is_err());
+    assert!(!manager.is_installed("en"));
+    assert_eq!(read_to_string(manager.part_path("en")).unwrap(), "hello\nh");
+
+    // The second picks up where the first stopped.
+    let mut first_progress = None;
+    manager
+        .fetch("en", &mut |event| {
+            if let DownloadEvent::Progress { bytes, .. } = event {
+                first_progress.get_or_insert(bytes);
+            }
+        })
+        .unwrap();
+
+    assert!(first_progress.unwrap() > 7);
+    assert!(!manager.part_path("en").exists());
+    assert_eq!(
+        read_to_string(manager.path("en")).unwrap().as_bytes(),
+        WORDS
+    );
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn corrupt_download_test() {
+    let dir = data_dir("corrupt");
+    let manager = WordlistManager::new(dir.clone());
+    let url = manager.url("en");
+    let manager = manager.fetcher(Box::new(MemoryFetcher::new().with_file_and_length(
+        &url,
+        WORDS,
+        Some(WORDS.len() as u64 + 10),
+    )));
+
+    // A short body is discarded instead of installed or kept for resuming.
+    assert!(manager.fetch("en", &mut |_| {}).is_err());
+    assert!(!manager.is_installed("en"));
+    assert!(!manager.part_path("en").exists());
+    assert!(manager.audit_log().unwrap().is_empty());
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn failed_update_test() {
+    let dir = data_dir("failed_update");
+    create_dir_all(&dir).unwrap();
+    let manager = WordlistManager::new(dir.clone());
+    let url = manager.url("en");
+    write(manager.path("en"), "old\n").unwrap();
+    write(manager.part_path("en"), "stale").unwrap();
+    let manager = manager.fetcher(Box::new(
+        MemoryFetcher::new()
+            .with_file(&url, WORDS)
+            .interrupt_after(7),
+    ));
+
+    // The stale partial download is not resumed, and the installed list survives.
+    assert!(manager.update("en", &mut |_| {}).is_err());
+    assert_eq!(read_to_string(manager.path("en")).unwrap(), "old\n");
+    assert_eq!(read_to_string(manager.part_path("en")).unwrap(), "hello\nh");
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn system_dir_test() {
+    let dir = data_dir("system");
+    let system_dir = dir.join("system");
+    create_dir_all(&system_dir).unwrap();
+    write(system_dir.join("en"), WORDS).unwrap();
+
+    // No files to serve, so any download attempt fails.
+    let manager = WordlistManager::new(dir.join("user"))
+        .system_dirs(vec![system_dir.clone()])
+        .fetcher(Box::new(MemoryFetcher::new()));
+
+    assert!(manager.fetch("en", &mut |_| {}).is_ok());
+    assert!(manager.is_read_only("en"));
+    assert_eq!(manager.locate("en"), Some(system_dir.join("en")));
+    assert_eq!(manager.installed().unwrap(), vec!["en"]);
+    assert_eq!(manager.load("en").unwrap().len(), 4);
+    assert!(manager.update("en", &mut |_| {}).is_err());
+    assert!(!manager.data_dir().exists());
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn thesaurus_test() {
+    let dir = data_dir("thesaurus");
+    let system_dir = dir.join("system");
+    let manager = WordlistManager::new(dir.join("user")).system_dirs(vec![system_dir.clone()]);
+    assert_eq!(manager.load_thesaurus("en").unwrap(), None);
+
+    // The user's thesaurus is used unless a system one is installed.
+    create_dir_all(manager.data_dir()).unwrap();
+    write(manager.thesaurus_path("en"), "hello,hi,greetings\n").unwrap();
+    let thesaurus = manager.load_thesaurus("en").unwrap().unwrap();
+    assert_eq!(thesaurus.synonyms("hello"), ["hi", "greetings"]);
+
+    create_dir_all(&system_dir).unwrap();
+    write(system_dir.join("en.thesaurus"), "hello\thowdy\n").unwrap();
+    let thesaurus = manager.load_thesaurus("en").unwrap().unwrap();
+    assert_eq!(thesaurus.synonyms("hello"), ["howdy"]);
+    assert_eq!(manager.load_thesaurus("fr").unwrap(), None);
+    remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn process_fetcher_test() {
+    use didyoumean::fetcher::{Fetcher, ProcessFetcher};
+
+    // A stand-in for the fetch process, answering every request with the same body.
+    let script = |script: &str| {
+        ProcessFetcher::new(
+            PathBuf::from("sh"),
+            vec!["-c".to_string(), script.to_string(), "sh".to_string()],
+        )
+    };
+
+    let fetcher = script(r#"printf '{"content_length":6,"resumed":false}\nhello\n'"#);
+    assert_eq!(
+        fetcher.content_length("https://example.com/en").unwrap(),
+        Some(6)
+    );
+    assert_eq!(fetcher.get("https://example.com/en").unwrap(), b"hello\n");
+
+    let fetcher =
+        script(r#"printf '{"content_length":6}\nhel'; echo 'connection reset' >&2; exit 1"#);
+    let error = fetcher.get("https://example.com/en").unwrap_err();
+    assert!(error.to_string().contains("connection reset"));
+
+    let fetcher = script("echo 'no route to host' >&2; exit 1");
+    let error = fetcher.stream("https://example.com/en", 0).err().unwrap();
+    assert!(error.to_string().contains("no route to host"));
+}
+
+#[test]
+fn error_test() {
+    let dir = data_dir("error");
+    let manager = WordlistManager::new(dir.clone()).fetcher(Box::new(MemoryFetcher::new()));
+
+    // Unknown and uninstalled languages are reported as such, and failed downloads as
+    // network errors.
+    assert!(matches!(
+        manager.fetch("xx", &mut |_| {}),
+        Err(DymError::MissingLanguage(lang)) if lang == "xx"
+    ));
+    assert!(matches!(
+        manager.load("en"),
+        Err(DymError::MissingLanguage(_))
+    ));
+    let error = manager.fetch("en", &mut |_| {}).unwrap_err();
+    assert!(matches!(&error, DymError::Network { url, .. } if *url == manager.url("en")));
+    assert_eq!(error.exit_code(), 69);
+    remove_dir_all(&dir).unwrap();
+}
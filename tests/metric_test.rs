@@ -0,0 +1,52 @@
+#![cfg(feature = "std")]
+
+use didyoumean::metric::{check_invariants, Metric};
+use proptest::prelude::*;
+
+/// Return a strategy for the metrics under test.
+fn metric() -> impl Strategy<Value = Metric> {
+    prop_oneof![Just(Metric::Levenshtein), Just(Metric::DamerauLevenshtein)]
+}
+
+/// Return a strategy for strings of up to six characters over a small alphabet, so that
+/// repeated characters, and therefore transpositions, are common.
+fn short_string() -> impl Strategy<Value = String> {
+    "[abcé]{0,6}"
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn identity_test(metric in metric(), a in short_string(), b in short_string()) {
+        prop_assert_eq!(metric.distance(&a, &a), 0);
+        prop_assert_eq!(metric.distance(&a, &b) == 0, a == b);
+    }
+
+    #[test]
+    fn symmetry_test(metric in metric(), a in short_string(), b in short_string()) {
+        prop_assert_eq!(metric.distance(&a, &b), metric.distance(&b, &a));
+    }
+
+    #[test]
+    fn triangle_inequality_test(
+        metric in metric(),
+        a in short_string(),
+        b in short_string(),
+        c in short_string(),
+    ) {
+        prop_assert!(metric.distance(&a, &c) <= metric.distance(&a, &b) + metric.distance(&b, &c));
+        prop_assert_eq!(check_invariants(metric, &a, &b, &c), Ok(()));
+    }
+}
+
+#[test]
+fn damerau_levenshtein_test() {
+    let dist = |a, b| Metric::DamerauLevenshtein.distance(a, b);
+
+    assert_eq!(dist("", ""), 0);
+    assert_eq!(dist("", "abc"), 3);
+    assert_eq!(dist("ca", "abc"), 2);
+    assert_eq!(dist("abcdef", "badcfe"), 3);
+    assert_eq!(dist("sunday", "saturday"), 3);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "std")]
+
+use didyoumean::pattern::{LengthIndex, Pattern};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Return whether `word` matches `pattern`, trying every length for each `*`.
+fn naive_matches(pattern: &[char], word: &[char]) -> bool {
+    match pattern.split_first() {
+        None => word.is_empty(),
+        Some(('*', rest)) => (0..=word.len()).any(|i| naive_matches(rest, &word[i..])),
+        Some((p, rest)) => match word.split_first() {
+            Some((c, word)) => (*p == '_' || p == c) && naive_matches(rest, word),
+            None => false,
+        },
+    }
+}
+
+#[test]
+fn matches_test() {
+    let mut rng = StdRng::seed_from_u64(0x7074);
+    for _ in 0..5000 {
+        let pattern: String = (0..rng.gen_range(1..6))
+            .map(|_| ['a', 'b', '_', '*'][rng.gen_range(0..4)])
+            .collect();
+        let word: String = (0..rng.gen_range(0..8))
+            .map(|_| ['a', 'b'][rng.gen_range(0..2)])
+            .collect();
+
+        let parsed: Pattern = pattern.parse().unwrap();
+        let expected = naive_matches(
+            &pattern.chars().collect::<Vec<_>>(),
+            &word.chars().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            parsed.matches(&word),
+            expected,
+            "{} against {}",
+            pattern,
+            word
+        );
+    }
+}
+
+#[test]
+fn find_test() {
+    let words = ["crane", "crate", "grate", "cat", "créer", "crater"]
+        .map(String::from)
+        .to_vec();
+    let index = LengthIndex::new(&words);
+
+    let find = |pattern: &str| index.find(&pattern.parse().unwrap());
+    assert_eq!(find("cra_e"), vec!["crane", "crate"]);
+    assert_eq!(find("?r?te*"), vec!["crate", "grate", "crater"]);
+    assert_eq!(find("cr_er"), vec!["créer"]);
+    assert!(find("c_t_").is_empty());
+    assert!("".parse::<Pattern>().is_err());
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "std")]
+
+use didyoumean::{
+    pipeline::{Boost, Pipeline, PostProcessor, Prefilter, Within},
+    source::{top_n_merged, Source, SourceKind, Suggestion},
+    MatchOptions, Ranking,
+};
+
+fn sources() -> Vec<Source> {
+    vec![
+        Source::new(
+            SourceKind::Personal,
+            "personal",
+            ["Acme", "hello"].map(String::from).to_vec(),
+        ),
+        Source::new(
+            SourceKind::Language,
+            "en",
+            ["hello", "help", "acne", "acre", "world", "Zebra"]
+                .map(String::from)
+                .to_vec(),
+        ),
+    ]
+}
+
+/// A prefilter keeping the words starting with a capital letter.
+struct Capitalized;
+
+impl Prefilter for Capitalized {
+    fn keep(&self, _term: &str, word: &str) -> bool {
+        word.starts_with(char::is_uppercase)
+    }
+}
+
+/// A post-processor moving the suggestions from the personal word list to the front.
+struct PersonalFirst;
+
+impl PostProcessor for PersonalFirst {
+    fn process(&self, _term: &str, suggestions: &mut Vec<Suggestion<'_>>) {
+        suggestions.sort_by_key(|suggestion| suggestion.source != "personal");
+    }
+}
+
+/// Return the words of `suggestions`.
+fn words<'a>(suggestions: Vec<Suggestion<'a>>) -> Vec<&'a str> {
+    suggestions
+        .iter()
+        .map(|suggestion| suggestion.word)
+        .collect()
+}
+
+#[test]
+fn default_pipeline_test() {
+    // The default stages give the same suggestions as the plain search.
+    let sources = sources();
+    let options = MatchOptions {
+        ignore_case: true,
+        ..MatchOptions::default()
+    };
+    for term in ["helo", "acme", "wrld", "zebra", ""] {
+        for n in [1, 3, 10] {
+            let pipeline = Pipeline::new().normalize(options).n(n);
+            assert_eq!(
+                pipeline.run(term, &sources),
+                top_n_merged(term, &sources, n, Ranking::EditDistance, options),
+                "{} suggestions for {:?}",
+                n,
+                term
+            );
+        }
+    }
+}
+
+#[test]
+fn custom_stages_test() {
+    let sources = sources();
+    let pipeline = Pipeline::new().prefilter(Capitalized);
+    assert_eq!(words(pipeline.run("acne", &sources)), vec!["Acme", "Zebra"]);
+
+    let pipeline = Pipeline::new()
+        .post_process(Within(1))
+        .post_process(PersonalFirst);
+    assert_eq!(
+        words(pipeline.run("acme", &sources)),
+        vec!["Acme", "acne", "acre"]
+    );
+}
+
+#[test]
+fn unranked_test() {
+    // Words that do not sound like the term are never suggested, however far the others
+    // are allowed to be or whichever words are boosted.
+    let sources = sources();
+    let pipeline = Pipeline::new()
+        .scorer(Ranking::Soundex)
+        .n(0)
+        .post_process(Boost::new(["world"], 1))
+        .post_process(Within(usize::MAX));
+    assert_eq!(words(pipeline.run("helo", &sources)), vec!["hello"]);
+    assert_eq!(
+        words(pipeline.run("helo", &sources)),
+        words(top_n_merged(
+            "helo",
+            &sources,
+            10,
+            Ranking::Soundex,
+            MatchOptions::default()
+        ))
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(all(unix, feature = "std"))]
+
+use didyoumean::{plugin::rescore, source::Suggestion};
+use std::process::Command;
+
+/// Return a plugin running the shell `script`.
+fn plugin(script: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", script]);
+    command
+}
+
+fn candidates() -> Vec<Suggestion<'static>> {
+    ["hello", "help", "yellow"]
+        .iter()
+        .enumerate()
+        .map(|(distance, word)| Suggestion {
+            word,
+            distance,
+            source: "en",
+        })
+        .collect()
+}
+
+#[test]
+fn rescore_test() {
+    // The plugin sees the request and can reorder, rescore and drop candidates. Words that
+    // were not candidates are ignored.
+    let mut suggestions = candidates();
+    let script = r#"
+        read request
+        case "$request" in *'"term":"helo"'*) ;; *) exit 1 ;; esac
+        case "$request" in *'"word":"yellow"'*) ;; *) exit 1 ;; esac
+        echo '[{"word": "yellow", "distance": 0}, {"word": "world"}, {"word": "hello"}]'
+    "#;
+    rescore(plugin(script), "helo", &mut suggestions).unwrap();
+    assert_eq!(
+        suggestions,
+        vec![
+            Suggestion {
+                word: "yellow",
+                distance: 0,
+                source: "en"
+            },
+            Suggestion {
+                word: "hello",
+                distance: 0,
+                source: "en"
+            },
+        ]
+    );
+}
+
+#[test]
+fn failing_plugin_test() {
+    let mut suggestions = candidates();
+    let error = rescore(plugin("echo broken >&2; exit 2"), "helo", &mut suggestions).unwrap_err();
+    assert!(error.to_string().contains("broken"));
+
+    assert!(rescore(plugin("echo not json"), "helo", &mut suggestions).is_err());
+    // Answering without reading the request is fine.
+    rescore(plugin("echo []"), "helo", &mut suggestions).unwrap();
+    assert!(suggestions.is_empty());
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+use didyoumean::rpc::{read_value, serve, write_value, Value};
+
+#[test]
+fn round_trip_test() {
+    let values = vec![
+        Value::Nil,
+        Value::Bool(true),
+        Value::Int(-1),
+        Value::Int(-200),
+        Value::Int(300),
+        Value::Int(i64::MIN),
+        Value::Int(1 << 40),
+        Value::Float(0.5),
+        Value::Str("x".repeat(40)),
+        Value::Str("y".repeat(70000)),
+        Value::Bin(vec![1, 2, 3]),
+        Value::Array((0..20).map(Value::Int).collect()),
+        Value::Map(vec![(Value::from("key"), Value::Array(vec![]))]),
+        Value::Ext(0, vec![7]),
+    ];
+
+    let mut bytes = vec![];
+    for value in &values {
+        write_value(&mut bytes, value).unwrap();
+    }
+
+    let mut reader = &bytes[..];
+    for value in &values {
+        assert_eq!(read_value(&mut reader).unwrap().as_ref(), Some(value));
+    }
+    assert_eq!(read_value(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn serve_test() {
+    let mut input = vec![];
+    let request = |id: usize, method: &str| {
+        Value::Array(vec![
+            0.into(),
+            id.into(),
+            method.into(),
+            Value::Array(vec![]),
+        ])
+    };
+    write_value(&mut input, &request(1, "ping")).unwrap();
+    write_value(
+        &mut input,
+        &Value::Array(vec![2.into(), "ping".into(), Value::Array(vec![])]),
+    )
+    .unwrap();
+    write_value(&mut input, &request(2, "missing")).unwrap();
+
+    let mut calls = 0;
+    let mut output = vec![];
+    serve(&input[..], &mut output, |method, _| {
+        calls += 1;
+        match method {
+            "ping" => Ok("pong".into()),
+            _ => Err("no such method".to_string()),
+        }
+    })
+    .unwrap();
+    assert_eq!(calls, 3);
+
+    // The notification gets no response.
+    let mut reader = &output[..];
+    let responses: Vec<Value> = std::iter::from_fn(|| read_value(&mut reader).unwrap()).collect();
+    assert_eq!(
+        responses,
+        vec![
+            Value::Array(vec![1.into(), 1.into(), Value::Nil, "pong".into()]),
+            Value::Array(vec![
+                1.into(),
+                2.into(),
+                "no such method".into(),
+                Value::Nil
+            ]),
+        ]
+    );
+}
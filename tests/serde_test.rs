@@ -0,0 +1,26 @@
+#![cfg(feature = "serde")]
+
+use didyoumean::{check::Finding, suggest, Suggestion};
+
+#[test]
+fn round_trip_test() {
+    let suggestions = suggest("helo", ["hello", "help", "world"], 2);
+    let json = serde_json::to_string(&suggestions).unwrap();
+    assert!(json.starts_with(r#"[{"word":"hello","distance":1,"score":0.8}"#));
+    assert_eq!(
+        serde_json::from_str::<Vec<Suggestion>>(&json).unwrap(),
+        suggestions
+    );
+
+    let finding = Finding {
+        span: 4..8,
+        word: "wrld".to_string(),
+        suggestions: vec!["world".to_string(), "word".to_string()],
+    };
+    let json = serde_json::to_string(&finding).unwrap();
+    assert_eq!(
+        json,
+        r#"{"span":{"start":4,"end":8},"word":"wrld","suggestions":["world","word"]}"#
+    );
+    assert_eq!(serde_json::from_str::<Finding>(&json).unwrap(), finding);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+
+use didyoumean::{
+    session::{parse_ranking, ranking_name, SearchMode, Session},
+    source::{top_n_merged, Source, SourceKind},
+    Costs, MatchOptions, Ranking,
+};
+
+#[test]
+fn ranking_name_test() {
+    let rankings = [
+        Ranking::EditDistance,
+        Ranking::JaroWinkler,
+        Ranking::Soundex,
+        Ranking::Metaphone,
+        Ranking::Keyboard,
+        Ranking::Costs(Costs {
+            insert: 1,
+            delete: 2,
+            substitute: 3,
+            transpose: 4,
+        }),
+        Ranking::Ngram,
+        Ranking::Lcs,
+    ];
+    for ranking in rankings {
+        assert_eq!(parse_ranking(&ranking_name(ranking)), Ok(ranking));
+    }
+    for name in ["", "levenshtein", "costs:1,2,3", "costs:a,b,c,d"] {
+        assert!(parse_ranking(name).is_err(), "{:?} is not a ranking", name);
+    }
+}
+
+#[test]
+fn save_load_test() {
+    let words = ["hello", "help", "world"].map(String::from).to_vec();
+    let mut sources = vec![Source::new(SourceKind::Language, "en", words)];
+    let options = MatchOptions {
+        ignore_case: true,
+        ..MatchOptions::default()
+    };
+    let suggestions = top_n_merged("Helo", &sources, 2, Ranking::EditDistance, options);
+    let session = Session::new(
+        "Helo",
+        2,
+        Ranking::EditDistance,
+        options,
+        true,
+        &sources,
+        &suggestions,
+    )
+    .with_mode(SearchMode {
+        suffix: Some("lo".to_string()),
+        boost_words: vec!["help".to_string()],
+        ..SearchMode::default()
+    });
+
+    let path = std::env::temp_dir().join(format!("dym-session-test-{}", std::process::id()));
+    session.save(&path).unwrap();
+    let loaded = Session::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    let loaded = loaded.unwrap();
+    assert_eq!(loaded, session);
+    assert_eq!(loaded.options(), options);
+    assert_eq!(loaded.mode.suffix.as_deref(), Some("lo"));
+
+    // Changed, missing and new word lists are all reported.
+    assert!(loaded.changed_sources(&sources).is_empty());
+    sources[0].words.push("yellow".to_string());
+    sources.push(Source::new(SourceKind::Personal, "personal", vec![]));
+    assert_eq!(loaded.changed_sources(&sources), vec!["en", "personal"]);
+    assert_eq!(loaded.changed_sources(&[]), vec!["en"]);
+
+    // Files that are not sessions are errors.
+    std::fs::write(&path, "{}").unwrap();
+    let loaded = Session::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(loaded.is_err());
+}
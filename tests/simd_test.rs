@@ -0,0 +1,36 @@
+#![cfg(feature = "std")]
+
+use common::random_word;
+use didyoumean::{edit_distance, edit_distances};
+use rand::{rngs::StdRng, SeedableRng};
+
+mod common;
+
+/// Letters to build words from, so that words share letters and transpositions come up
+/// often.
+const LETTERS: &[&str] = &["a", "b", "c", "d", "e"];
+
+#[test]
+fn edit_distances_test() {
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..50 {
+        let term = random_word(&mut rng, LETTERS, 12);
+        let term_chars: Vec<char> = term.chars().collect();
+        let mut words: Vec<String> = (0..100)
+            .map(|_| random_word(&mut rng, LETTERS, 16))
+            .collect();
+        words.push("naïve".to_string());
+
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let expected: Vec<usize> = words
+            .iter()
+            .map(|word| edit_distance(&term_chars, word))
+            .collect();
+        assert_eq!(
+            edit_distances(&term_chars, &words),
+            expected,
+            "term {:?}",
+            term
+        );
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+
+use common::{brute_force_within, random_word, SMALL_ALPHABET};
+use didyoumean::{
+    edit_distance,
+    symspell::{self, SymSpell},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::io::Cursor;
+
+mod common;
+
+#[test]
+fn find_within_test() {
+    let mut rng = StdRng::seed_from_u64(0x7373);
+    let words: Vec<String> = (0..2000)
+        .map(|_| random_word(&mut rng, SMALL_ALPHABET, 8))
+        .collect();
+    let mut index = SymSpell::build(&words, 2).unwrap();
+    assert_eq!(index.word_count(), words.len());
+    assert_eq!(index.fingerprint(), symspell::fingerprint(&words));
+
+    for _ in 0..50 {
+        let term = random_word(&mut rng, SMALL_ALPHABET, 8);
+        let term_chars: Vec<char> = term.chars().collect();
+        for k in 0..=2 {
+            let expected = brute_force_within(&words, k, |word| edit_distance(&term_chars, word));
+
+            let found = index.find_within(&term, &words, k).unwrap();
+            assert_eq!(found, expected, "within {} of {:?}", k, term);
+        }
+    }
+}
+
+#[test]
+fn open_test() {
+    let words = ["hello", "help", "world"].map(String::from).to_vec();
+    let mut bytes = vec![];
+    symspell::write_index(&words, 1, &mut bytes).unwrap();
+
+    let mut index = SymSpell::open(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(index.max_distance(), 1);
+    assert_eq!(
+        index.find_within("helo", &words, 5).unwrap(),
+        vec![(0, 1), (1, 1)]
+    );
+
+    // Any other word list has another fingerprint.
+    let other = ["hello", "world", "help"].map(String::from).to_vec();
+    assert_ne!(symspell::fingerprint(&other), index.fingerprint());
+
+    // Truncated and foreign files are errors rather than panics.
+    bytes.pop();
+    assert!(SymSpell::open(Cursor::new(bytes)).is_err());
+    assert!(SymSpell::open(Cursor::new(b"not an index at all, really not".repeat(2))).is_err());
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use didyoumean::wordlist::{load, load_translations};
+use miniz_oxide::deflate::compress_to_vec;
+
+#[test]
+fn load_gzipped_hunspell_test() {
+    let path = std::env::temp_dir().join("didyoumean_wordlist_test.dic.gz");
+
+    // Build a minimal gzip member around the deflated dictionary.
+    let mut bytes = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 255];
+    bytes.extend(compress_to_vec(b"3\nhello/S\nworld\ncafe/M\n", 6));
+    bytes.extend([0; 8]);
+    std::fs::write(&path, bytes).unwrap();
+
+    let words = load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(words, vec!["hello", "world", "cafe"]);
+}
+
+#[test]
+fn load_translations_test() {
+    let path = std::env::temp_dir().join("didyoumean_wordlist_test.tsv");
+    std::fs::write(&path, "horse\tcheval\nhouse\tmaison\nhorse\tcanasson\n").unwrap();
+    let words = load(&path);
+    let translations = load_translations(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(words.unwrap(), vec!["horse", "house"]);
+    let translations = translations.unwrap();
+    assert_eq!(translations.len(), 2);
+    assert_eq!(translations["horse"], "cheval; canasson");
+    assert_eq!(translations["house"], "maison");
+
+    // Other word lists have no translations.
+    let path = std::env::temp_dir().join("didyoumean_wordlist_test.txt");
+    std::fs::write(&path, "horse\nhouse\n").unwrap();
+    let translations = load_translations(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(translations.unwrap().is_empty());
+}